@@ -0,0 +1,26 @@
+// Fuzz the ex04 text field: random edits on arbitrary Unicode, checking
+// the same invariants as the property tests after every edit
+// Run with: cargo +nightly fuzz run text_field
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The field comes from the lab library; the random edits are the ones
+// the exercise's property tests use
+#[allow(dead_code)]
+#[path = "../../src/bin/ex04_input_prompt/edits.rs"]
+mod edits;
+
+use edits::{check_invariants, field_with_mask, Edit};
+
+// A mask, a box width and a list of (edit kind, text) pairs
+fuzz_target!(|input: (u8, u8, Vec<(u8, String)>)| {
+    let (mask, width, edits) = input;
+    let width = 2 + width as usize % 40;
+
+    let mut field = field_with_mask(mask);
+    for (op, text) in edits {
+        Edit::from_raw(op, text).apply(&mut field);
+        check_invariants(&mut field, width, mask % 3 == 0);
+    }
+});
@@ -1,28 +1,17 @@
-use std::io;
 use color_eyre::Result;
-use crossterm::{event, execute};
+use crossterm::event;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use ratatui::backend::CrosstermBackend;
 use ratatui::prelude::Modifier;
 use ratatui::style::{Color, Style, Stylize};
-use ratatui::Terminal;
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::text::{Line, Span};
+use rust_tui_lab::terminal::Session;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    // Enter raw mode
-    enable_raw_mode()?;
-
-    // Enter alternate screen
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    // Create Terminal<CrosstermBackend<_>>
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode and the alternate screen, until the session is finished
+    let mut terminal = Session::start()?;
 
     // state mode
     let mut is_highlight_mode = false;
@@ -119,24 +108,21 @@ fn main() -> Result<()> {
         })?;
 
         // wait for input
-        if let Event::Key(key) = event::read()? {
-           if key.kind == KeyEventKind::Press {
-               match key.code {
-                   KeyCode::Char('q') | KeyCode::Esc => break,
-                   KeyCode::Char('h') => {
-                       is_highlight_mode = !is_highlight_mode;
-                   }
-                   _ => {}
-               }
-           }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('h') => {
+                    is_highlight_mode = !is_highlight_mode;
+                }
+                _ => {}
+            }
         }
     }
 
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    terminal.finish()?;
 
     Ok(())
 }
\ No newline at end of file
@@ -1,26 +1,15 @@
-use std::io;
 use color_eyre::Result;
-use crossterm::{event, execute};
+use crossterm::event;
 use crossterm::event::{Event, KeyCode};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use ratatui::backend::CrosstermBackend;
-use ratatui::Terminal;
 use ratatui::widgets::{Block, Borders, Paragraph};
-use ratatui::text::{Line, Span};
+use ratatui::text::Line;
+use rust_tui_lab::terminal::Session;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    // Enter raw mode
-    enable_raw_mode()?;
-
-    // Enter alternate screen
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    // Create Terminal<CrosstermBackend<_>>
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode and the alternate screen, until the session is finished
+    let mut terminal = Session::start()?;
 
     // draw loop
     loop {
@@ -45,22 +34,16 @@ fn main() -> Result<()> {
         })?;
 
         // wait for input
-        if event::poll(std::time::Duration::from_millis(250))? {
-            if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
-                }else if key.code == KeyCode::Esc {
-                    break;
-                }
-            }
+        if event::poll(std::time::Duration::from_millis(250))?
+            && let Event::Key(key) = event::read()?
+            && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+            break;
         }
     }
 
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    terminal.finish()?;
 
     Ok(())
 }
\ No newline at end of file
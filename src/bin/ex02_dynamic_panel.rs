@@ -1,14 +1,11 @@
 use color_eyre::Result;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use crossterm::{event, execute};
-use ratatui::backend::CrosstermBackend;
+use crossterm::event;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
-use ratatui::Terminal;
-use std::io;
 use ratatui::prelude::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
+use rust_tui_lab::terminal::Session;
 
 
 struct AppState {
@@ -27,13 +24,8 @@ fn main() -> Result<()> {
     color_eyre::install()?;
 
 
-    enable_raw_mode()?;
-
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode and the alternate screen, until the session is finished
+    let mut terminal = Session::start()?;
 
     let mut state = AppState{
         mode: AppMode::None,
@@ -150,36 +142,34 @@ fn main() -> Result<()> {
         })?;
 
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
-                    KeyCode::Char('1') => {
-                        state.mode = AppMode::Mode1;
-                        state.display_text = "Mode 1 active".to_string();
-                    },
-                    KeyCode::Char('2') => {
-                        state.mode = AppMode::Mode2;
-                        state.display_text = "Mode 2 active".to_string();
-                    },
-                    KeyCode::Char('3') => {
-                        state.mode = AppMode::Mode3;
-                        state.display_text = "Mode 3 active".to_string();
-                    },
-                    KeyCode::Char('4') => {
-                        state.mode = AppMode::None;
-                        state.display_text = "No mode selected".to_string();
-                    }
-                    _ => {}
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('1') => {
+                    state.mode = AppMode::Mode1;
+                    state.display_text = "Mode 1 active".to_string();
+                },
+                KeyCode::Char('2') => {
+                    state.mode = AppMode::Mode2;
+                    state.display_text = "Mode 2 active".to_string();
+                },
+                KeyCode::Char('3') => {
+                    state.mode = AppMode::Mode3;
+                    state.display_text = "Mode 3 active".to_string();
+                },
+                KeyCode::Char('4') => {
+                    state.mode = AppMode::None;
+                    state.display_text = "No mode selected".to_string();
                 }
+                _ => {}
             }
         }
     }
 
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    // Restore terminal
+    terminal.finish()?;
 
     Ok(())
 }
\ No newline at end of file
@@ -1,26 +1,17 @@
 use color_eyre::Result;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use crossterm::{event, execute};
-use ratatui::backend::CrosstermBackend;
+use crossterm::event;
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::prelude::Modifier;
 use ratatui::style::{Color, Style};
-use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
-use ratatui::Terminal;
-use std::io;
+use rust_tui_lab::terminal::Session;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    enable_raw_mode()?;
-
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode and the alternate screen, until the session is finished
+    let mut terminal = Session::start()?;
 
 
     loop {
@@ -107,20 +98,18 @@ fn main() -> Result<()> {
 
         })?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
-                    _ => {}
-                }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                _ => {}
             }
         }
 
     }
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    // Restore terminal
+    terminal.finish()?;
 
     Ok(())
 }
\ No newline at end of file
@@ -1,82 +1,37 @@
 use color_eyre::Result;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use crossterm::{event, execute};
-use ratatui::backend::CrosstermBackend;
+use crossterm::event;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::Modifier;
 use ratatui::style::{Color, Style};
 use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
-use ratatui::Terminal;
-use std::io;
-
-struct StateFullList {
-    state: ListState,
-    items: Vec<String>,
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use rust_tui_lab::style::list_highlight_style;
+use rust_tui_lab::terminal::Session;
+use rust_tui_lab::widgets::StatefulList;
+
+// The list and its selection come from the lab library; the app adds
+// the item confirmed with Enter
+struct ListAction {
+    list: StatefulList<String>,
     last_confirmed: Option<String>,
 }
 
-
-impl StateFullList {
+impl ListAction {
 
     fn new() -> Self {
-        let items = vec![
+        let list = StatefulList::new(vec![
             "Item1".to_string(),
             "Item2".to_string(),
             "Item3".to_string(),
             "Item4".to_string(),
-        ];
-
-        let mut state = ListState::default();
-        state.select(Some(0));
+        ]);
         let last_confirmed = None;
 
-        Self { state, items, last_confirmed }
-
-    }
-
-
-    fn next(&mut self) {
-        // Take currently active index
-        let i = match self.state.selected() {
-            Some(i) => {
-                // if index at the bottom, go back to the top ( looping )
-                if i >= self.items.len() - 1 {
-                    0
-                }else {
-                    i + 1
-                }
-            }
-
-            // if not selected , default select
-            None => 0,
-        };
-
-        // save new index to list state
-        self.state.select(Some(i));
-    }
-    fn previous(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                //  If at the top, jump to the bottom
-                if i == 0 {
-                    self.items.len() - 1
-                }else {
-                    i - 1
-                }
-            }
-            None => 0,
-        };
-
-        self.state.select(Some(i));
+        Self { list, last_confirmed }
     }
 
     fn selected_items(&mut self) {
-        if let Some(i) = self.state.selected() {
-            let item = &self.items[i];
-            self.last_confirmed = Some(item.clone());
-        }
+        self.last_confirmed = self.list.selected().cloned();
     }
 
 }
@@ -84,26 +39,20 @@ impl StateFullList {
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    enable_raw_mode()?;
-
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode and the alternate screen, until the session is finished
+    let mut terminal = Session::start()?;
 
     // init instance
 
-    let mut state = StateFullList::new();
+    let mut state = ListAction::new();
 
     loop {
         terminal.draw(|f| {
 
             let frame = f.area();
 
-            let current = state.state
+            let current = state.list
                 .selected()
-                .and_then(|i| state.items.get(i))
                 .map(String::as_str)
                 .unwrap_or("None");
 
@@ -128,6 +77,7 @@ fn main() -> Result<()> {
 
             // change all string to ListItem
             let items: Vec<ListItem> = state
+                .list
                 .items
                 .iter()
                 .map(|i| ListItem::new(i.as_str()))
@@ -141,12 +91,7 @@ fn main() -> Result<()> {
                         .title("List")
                 )
                 // style for selected items
-                .highlight_style(
-                    Style::default()
-                        .fg(Color::White)
-                        .bg(Color::LightBlue)
-                        .add_modifier(Modifier::BOLD)
-                )
+                .highlight_style(list_highlight_style())
                 // symbol for selected item
                 .highlight_symbol(">> ");
 
@@ -158,29 +103,27 @@ fn main() -> Result<()> {
                            ));
 
             // render with render stateful widget so list state available to read
-            f.render_stateful_widget(list, vertical_layout[0], &mut state.state);
+            f.render_stateful_widget(list, vertical_layout[0], &mut state.list.state);
             f.render_widget(info_panel, vertical_layout[1]);
 
         })?;
 
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
-                    KeyCode::Up => state.previous(),
-                    KeyCode::Down => state.next(),
-                    KeyCode::Enter => state.selected_items(),
-                    _ => {}
-                }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Up => state.list.previous(),
+                KeyCode::Down => state.list.next(),
+                KeyCode::Enter => state.selected_items(),
+                _ => {}
             }
         }
     }
 
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    // Restore terminal
+    terminal.finish()?;
 
     Ok(())
 }
\ No newline at end of file
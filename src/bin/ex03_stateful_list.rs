@@ -1,87 +1,32 @@
-use std::io;
 use color_eyre::Result;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
-use crossterm::{event, execute};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use ratatui::backend::CrosstermBackend;
+use crossterm::event;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::Modifier;
-use ratatui::style::{Color, Style};
-use ratatui::Terminal;
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
-
-struct StateFullList {
-    state: ListState,
-    items: Vec<String>
-}
-
-impl StateFullList {
-
-    fn new() -> Self {
-        let items = vec![
-            "Item 1".to_string(),
-            "Item 2".to_string(),
-            "Item 3".to_string(),
-            "Item 4".to_string(),
-        ];
-
-        let mut state = ListState::default();
-        state.select(Some(0));
-
-        Self { state, items }
-    }
-
-
-    fn next(&mut self) {
-        // Take currently active index
-        let i = match self.state.selected() {
-            Some(i) => {
-                // if index at the bottom, go back to the top ( looping )
-                if i >= self.items.len() - 1 {
-                    0
-                }else {
-                    i + 1
-                }
-            }
-
-            // if not selected , default select
-            None => 0,
-        };
-
-        // save new index to list state
-        self.state.select(Some(i));
-    }
-    fn previous(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                //  If at the top, jump to the bottom
-                if i == 0 {
-                    self.items.len() - 1
-                }else {
-                    i - 1
-                }
-            }
-            None => 0,
-        };
-
-        self.state.select(Some(i));
-    }
+use ratatui::widgets::{Block, Borders, List, ListItem};
+use rust_tui_lab::style::list_highlight_style;
+use rust_tui_lab::terminal::Session;
+use rust_tui_lab::widgets::StatefulList;
+
+// The list and its selection come from the lab library: Up/Down move the
+// selection and wrap around at the ends
+fn items() -> StatefulList<String> {
+    StatefulList::new(vec![
+        "Item 1".to_string(),
+        "Item 2".to_string(),
+        "Item 3".to_string(),
+        "Item 4".to_string(),
+    ])
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    enable_raw_mode()?;
-
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode and the alternate screen, until the session is finished
+    let mut terminal = Session::start()?;
 
     // init instance
 
-    let mut state = StateFullList::new();
+    let mut state = items();
 
     loop {
         terminal.draw(|f| {
@@ -109,12 +54,7 @@ fn main() -> Result<()> {
                         .title("List")
                 )
                 // style for selected items
-                .highlight_style(
-                    Style::default()
-                        .fg(Color::White)
-                        .bg(Color::LightBlue)
-                        .add_modifier(Modifier::BOLD)
-                )
+                .highlight_style(list_highlight_style())
                 // symbol for selected item
                 .highlight_symbol(">> ");
 
@@ -123,22 +63,20 @@ fn main() -> Result<()> {
         })?;
 
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
-                    KeyCode::Up => state.previous(),
-                    KeyCode::Down => state.next(),
-                    _ => {}
-                }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Up => state.previous(),
+                KeyCode::Down => state.next(),
+                _ => {}
             }
         }
     }
 
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    // Restore terminal
+    terminal.finish()?;
 
     Ok(())
 }
\ No newline at end of file
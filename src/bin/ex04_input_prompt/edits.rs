@@ -0,0 +1,258 @@
+// RANDOM EDITS
+// The field is a state machine: text + cursor + selection + kill ring...
+// changed by one edit at a time. This file turns raw random data into
+// edits and checks that no sequence of them can break the field
+// Two things drive it:
+// - the property tests below (`cargo test --bin ex04_input_prompt`)
+// - the fuzz target in fuzz/ (`cargo +nightly fuzz run text_field`)
+
+// Key events, to press keys like a user would
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+// Counting characters the way a person does (see field.rs)
+use unicode_segmentation::UnicodeSegmentation;
+
+use rust_tui_lab::field::{grapheme_width, Keymap, TextField};
+use rust_tui_lab::mask::Mask;
+
+// One thing that can happen to a field
+#[derive(Clone, Debug)]
+pub enum Edit {
+    Type(char),        // a key with a character on it
+    Insert(String),    // text inserted at the cursor (what history and yank do)
+    Paste(String),     // bracketed paste
+    Key(KeyEvent),     // an editing key, in whichever keymap is on
+    ToggleKeymap,      // emacs <-> vi ([m] in the app)
+    SetValue(String),  // replace everything (history, Ctrl-R)
+    Submit,            // Enter in the app
+}
+
+// The editing keys worth pressing: everything the field does something with
+const KEYS: [(KeyCode, KeyModifiers); 31] = {
+    const NONE: KeyModifiers = KeyModifiers::NONE;
+    const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+    const ALT: KeyModifiers = KeyModifiers::ALT;
+    const SHIFT: KeyModifiers = KeyModifiers::SHIFT;
+    const CTRL_SHIFT: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::SHIFT);
+    [
+        (KeyCode::Left, NONE), (KeyCode::Right, NONE), (KeyCode::Home, NONE), (KeyCode::End, NONE),
+        (KeyCode::Backspace, NONE), (KeyCode::Delete, NONE), (KeyCode::Esc, NONE), (KeyCode::Tab, NONE),
+        (KeyCode::Left, CTRL), (KeyCode::Right, CTRL), (KeyCode::Backspace, ALT),
+        (KeyCode::Left, SHIFT), (KeyCode::Right, SHIFT), (KeyCode::Home, SHIFT), (KeyCode::End, SHIFT),
+        (KeyCode::Left, CTRL_SHIFT), (KeyCode::Right, CTRL_SHIFT),
+        (KeyCode::Char('a'), CTRL), (KeyCode::Char('e'), CTRL), (KeyCode::Char('d'), CTRL),
+        (KeyCode::Char('w'), CTRL), (KeyCode::Char('u'), CTRL), (KeyCode::Char('k'), CTRL),
+        (KeyCode::Char('y'), CTRL), (KeyCode::Char('y'), ALT), (KeyCode::Char('d'), ALT),
+        (KeyCode::Char('b'), ALT), (KeyCode::Char('f'), ALT),
+        (KeyCode::Char('c'), CTRL), (KeyCode::Char('x'), CTRL), (KeyCode::Char('v'), CTRL),
+    ]
+};
+
+// vi command keys are plain letters: typing them in insert mode is fine too
+const VI_KEYS: &str = "hl0^$wbxXDCpPiaIA";
+
+impl Edit {
+    // Turn a random number and a random string into an edit
+    // (the fuzzer hands us raw data, this gives it a meaning)
+    pub fn from_raw(op: u8, text: String) -> Self {
+        let pick = op as usize / 8; // the low bits choose the kind of edit
+        match op % 8 {
+            0 => Edit::Type(text.chars().next().unwrap_or('a')),
+            1 => Edit::Insert(text),
+            2 => Edit::Paste(text),
+            3 | 4 => {
+                let (code, modifiers) = KEYS[pick % KEYS.len()];
+                Edit::Key(KeyEvent::new(code, modifiers))
+            }
+            5 => {
+                let c = VI_KEYS.chars().nth(pick % VI_KEYS.len()).unwrap_or('h');
+                Edit::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+            }
+            6 if pick.is_multiple_of(2) => Edit::ToggleKeymap,
+            6 => Edit::SetValue(text),
+            _ => Edit::Submit,
+        }
+    }
+
+    pub fn apply(&self, field: &mut TextField) {
+        match self {
+            Edit::Type(c) => field.enter_char(*c),
+            Edit::Insert(text) => field.insert_text(text),
+            Edit::Paste(text) => field.paste(text),
+            Edit::Key(key) => {
+                field.handle_key(*key);
+            }
+            Edit::ToggleKeymap => {
+                field.keymap = match field.keymap {
+                    Keymap::Emacs => Keymap::Vi,
+                    Keymap::Vi => Keymap::Emacs,
+                };
+            }
+            Edit::SetValue(text) => field.set_value(text),
+            Edit::Submit => {
+                field.submit();
+            }
+        }
+    }
+}
+
+// Which field the edits run on: every mask draws the text differently
+pub fn field_with_mask(mask: u8) -> TextField {
+    match mask % 3 {
+        0 => TextField::new(),
+        1 => TextField::new().mask(Mask::Password),
+        _ => TextField::new().mask(Mask::Template("____-__-__")),
+    }
+}
+
+// What must hold after ANY edit; panics with what went wrong
+// `width` is the width of the box the field is drawn in, `plain` says
+// the field has no mask (so it draws exactly what was typed)
+pub fn check_invariants(field: &mut TextField, width: usize, plain: bool) {
+    // The cursor is between two grapheme clusters, never past the end
+    let count = field.grapheme_count();
+    assert!(field.character_index <= count, "cursor {} past the end ({count})", field.character_index);
+
+    // The text is valid UTF-8 and the cursor's byte index cuts it on a
+    // cluster boundary
+    let byte = field.byte_index();
+    assert!(std::str::from_utf8(field.input.as_bytes()).is_ok());
+    assert!(field.input.is_char_boundary(byte), "cursor at byte {byte} splits a char");
+    let before = field.input[..byte].to_string();
+    assert_eq!(before.graphemes(true).count(), field.character_index, "cursor inside a cluster");
+
+    // The selection is a range of clusters inside the text
+    if let Some(selection) = field.selection() {
+        assert!(selection.start <= selection.end && selection.end <= count);
+    }
+
+    // In a plain field the cursor column is the width of the text before it
+    if plain {
+        let width_before: usize = before.graphemes(true).map(grapheme_width).sum();
+        assert_eq!(field.cursor_column(), width_before);
+    }
+
+    // The cursor is drawn inside the box, right after what is drawn
+    // before it: cluster by cluster, the view's text adds up exactly to
+    // the cursor column (no wide character cut in half)
+    let view = field.input_view(width);
+    assert!(view.cursor_x < width.max(1), "cursor at column {} of {width}", view.cursor_x);
+    let offset = if view.overflow { 1 } else { 0 }; // the '<' column
+    let target = view.cursor_x - offset;
+
+    let mut drawn = String::new();
+    let mut columns = 0;
+    for grapheme in view.text.graphemes(true).chain(std::iter::repeat_n(" ", view.padding)) {
+        if columns >= target {
+            break;
+        }
+        columns += grapheme_width(grapheme);
+        drawn.push_str(grapheme);
+    }
+    assert_eq!(columns, target, "cursor in the middle of {drawn:?}");
+
+    // ...and what is drawn before the cursor is what was typed before it
+    // (a zero-width cluster right before the cursor takes no column, so
+    // the cursor is in the same place with or without it)
+    if plain {
+        let typed = before
+            .graphemes(true)
+            .rev()
+            .skip_while(|grapheme| grapheme_width(grapheme) == 0)
+            .collect::<Vec<_>>();
+        let typed: String = typed.into_iter().rev().collect();
+        assert!(typed.ends_with(&drawn), "{drawn:?} drawn before the cursor, {before:?} typed");
+    }
+}
+
+
+// TESTS
+// Run with: cargo test --bin ex04_input_prompt
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Any Unicode at all, and text full of the tricky clusters: combining
+    // accents, joiners, skin tones, flags, wide CJK, tabs and line breaks
+    fn text() -> impl Strategy<Value = String> {
+        prop_oneof![
+            any::<String>(),
+            "[ab 日é\u{301}\u{200D}\u{1F44D}\u{1F3FD}\u{1F1E6}\u{1F1E7}\t\n_.-]{0,8}",
+        ]
+    }
+
+    fn edit() -> impl Strategy<Value = Edit> {
+        (any::<u8>(), text()).prop_map(|(op, text)| Edit::from_raw(op, text))
+    }
+
+    // Keys that work together: moving; selecting and yanking over the
+    // selection; selecting and the clipboard; killing and yanking back.
+    // A burst of keys from one group makes combinations like "select five,
+    // Ctrl-Y, Alt-Y" likely instead of one in millions
+    const KEY_GROUPS: [&[(KeyCode, KeyModifiers)]; 4] = [
+        &[
+            (KeyCode::Home, KeyModifiers::NONE), (KeyCode::End, KeyModifiers::NONE),
+            (KeyCode::Left, KeyModifiers::NONE), (KeyCode::Right, KeyModifiers::NONE),
+        ],
+        &[
+            (KeyCode::Left, KeyModifiers::SHIFT), (KeyCode::Right, KeyModifiers::SHIFT),
+            (KeyCode::End, KeyModifiers::SHIFT),
+            (KeyCode::Char('y'), KeyModifiers::CONTROL), (KeyCode::Char('y'), KeyModifiers::ALT),
+        ],
+        &[
+            (KeyCode::Left, KeyModifiers::SHIFT), (KeyCode::Right, KeyModifiers::SHIFT),
+            (KeyCode::Home, KeyModifiers::SHIFT),
+            (KeyCode::Char('c'), KeyModifiers::CONTROL), (KeyCode::Char('x'), KeyModifiers::CONTROL),
+            (KeyCode::Char('v'), KeyModifiers::CONTROL),
+        ],
+        &[
+            (KeyCode::Char('w'), KeyModifiers::CONTROL), (KeyCode::Char('u'), KeyModifiers::CONTROL),
+            (KeyCode::Char('k'), KeyModifiers::CONTROL), (KeyCode::Char('d'), KeyModifiers::ALT),
+            (KeyCode::Char('y'), KeyModifiers::CONTROL), (KeyCode::Char('y'), KeyModifiers::ALT),
+        ],
+    ];
+
+    fn key_burst() -> impl Strategy<Value = Vec<Edit>> {
+        (0..KEY_GROUPS.len(), prop::collection::vec(any::<prop::sample::Index>(), 1..12)).prop_map(
+            |(group, picks)| {
+                picks.iter()
+                    .map(|pick| {
+                        let (code, modifiers) = *pick.get(KEY_GROUPS[group]);
+                        Edit::Key(KeyEvent::new(code, modifiers))
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    // Any edit, pressed 1 to 5 times in a row
+    fn repeated_edit() -> impl Strategy<Value = Vec<Edit>> {
+        (edit(), 1usize..6).prop_map(|(edit, times)| vec![edit; times])
+    }
+
+    // A few short words, so the keys above have text to work on
+    fn words() -> impl Strategy<Value = Vec<Edit>> {
+        "[a-z]{1,6}( [a-z]{1,6}){0,3}".prop_map(|text| vec![Edit::Insert(text)])
+    }
+
+    fn edits() -> impl Strategy<Value = Vec<Edit>> {
+        let step = prop_oneof![2 => repeated_edit(), 1 => words(), 3 => key_burst()];
+        prop::collection::vec(step, 0..60).prop_map(|steps| steps.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn no_edit_sequence_breaks_the_field(
+            mask in 0u8..3,
+            width in 2usize..30,
+            edits in edits(),
+        ) {
+            let mut field = field_with_mask(mask);
+            for edit in &edits {
+                edit.apply(&mut field);
+                check_invariants(&mut field, width, mask == 0);
+            }
+        }
+    }
+}
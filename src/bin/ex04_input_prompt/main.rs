@@ -0,0 +1,958 @@
+// Standard library for input/output to the terminal
+use std::io;
+// Files and paths, for saving the history
+use std::fs;
+use std::path::PathBuf;
+
+// OSC 52 sends copied text to the terminal's clipboard in base64
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+// A library that gives better error messages (nice stack traces)
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+
+// Crossterm handles keyboard events and terminal control
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::{event, execute};
+
+// Ratatui is the TUI (Text User Interface) framework
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+
+// Splits text into grapheme clusters: what a person sees as ONE character,
+// even when it is made of several chars (e.g. "e" + accent, or 👍🏽)
+use unicode_segmentation::UnicodeSegmentation;
+
+// The terminal session, the shared styles and the input box come from
+// the lab library (src/lib.rs): field.rs edits the text, validator.rs
+// checks it, mask.rs hides it and complete.rs suggests words for it;
+// textarea.rs builds a multi-line box on top of the field
+use rust_tui_lab::complete::{FilePaths, WordList};
+use rust_tui_lab::field::{single_line, Keymap, TextField};
+use rust_tui_lab::mask::Mask;
+use rust_tui_lab::style::{default_style_text, title_text};
+use rust_tui_lab::terminal::Session;
+use rust_tui_lab::textarea::TextArea;
+use rust_tui_lab::validator::Validator;
+
+// Random edits for the property tests (and the fuzz target in fuzz/)
+#[cfg(test)]
+mod edits;
+
+
+// APPLICATION STATE
+// This struct stores ALL data of our app
+// Think of it as the "brain" of the program
+struct App {
+    field: TextField,          // The input box: text, cursor, rules...
+    field_name: &'static str,  // Its label
+    field_index: usize,        // Which example field ([f]) is shown
+    input_mode: InputMode,     // Are we typing or not?
+    last_submitted: Option<String>, // Last confirmed input
+    history: Vec<String>,      // Submitted inputs, oldest first
+    history_index: Option<usize>, // Which entry Up/Down is showing (None = the draft)
+    draft: String,             // What was typed before browsing the history
+    search: Option<HistorySearch>, // The Ctrl-R search, while searching
+    history_file: Option<PathBuf>, // Where the history is saved (--history FILE)
+    message: Option<String>,   // An error to show, e.g. the history couldn't be saved
+    notes: TextArea,           // A multi-line box next to the field ([n])
+}
+
+// This enum represents the modes of the app
+enum InputMode {
+    Normal,     // User is not typing
+    Editing,    // User is typing (the field knows about vi's command mode)
+    Search,     // Typing a Ctrl-R search through the history
+    Notes,      // Typing in the multi-line notes box
+}
+
+// A reverse incremental search, like Ctrl-R in a shell
+struct HistorySearch {
+    query: String,          // what was typed so far
+    found: Option<usize>,   // the history entry that matches
+    failed: bool,           // nothing (older) matches the query
+    original: String,       // the input before searching, for Esc
+}
+
+// How many submitted inputs the history keeps
+const HISTORY_SIZE: usize = 100;
+
+// How many example fields [f] cycles through
+const FIELD_COUNT: usize = 9;
+
+// The notes box submits with Ctrl-Enter, or Ctrl-S for terminals that
+// send Ctrl-Enter as a plain Enter
+const NOTES_SUBMIT_KEYS: [(KeyCode, KeyModifiers); 2] = [
+    (KeyCode::Enter, KeyModifiers::CONTROL),
+    (KeyCode::Char('s'), KeyModifiers::CONTROL),
+];
+
+// What the Language field suggests
+const LANGUAGES: [&str; 12] = [
+    "C", "C++", "Elixir", "Go", "Haskell", "Java", "JavaScript", "Kotlin", "Python", "Ruby", "Rust", "TypeScript",
+];
+
+// The example fields: a label and the field with its rules
+fn example_field(index: usize) -> (&'static str, TextField) {
+    match index {
+        1 => ("Name", TextField::new().validator(Validator::NonEmpty).validator(Validator::MaxLength(20))),
+        2 => ("Age", TextField::new().validator(Validator::NonEmpty).validator(Validator::Range(0.0..=150.0))),
+        3 => (
+            "Date",
+            TextField::new().mask(Mask::Template("____-__-__")).validator(Validator::Date("%Y-%m-%d")),
+        ),
+        4 => ("Time", TextField::new().mask(Mask::Template("__:__")).validator(Validator::Date("%H:%M"))),
+        5 => (
+            "Email",
+            TextField::new().validator(
+                Validator::regex(r"[^@\s]+@[^@\s]+\.[^@\s]+", "an email address").expect("the pattern is valid"),
+            ),
+        ),
+        6 => (
+            "Password",
+            TextField::new()
+                .mask(Mask::Password)
+                .validator(Validator::NonEmpty)
+                .validator(Validator::MaxLength(64)),
+        ),
+        7 => ("Path", TextField::new().completer(FilePaths::new())),
+        8 => ("Language", TextField::new().completer(WordList::new(LANGUAGES))),
+        _ => ("Input", TextField::new()),
+    }
+}
+
+
+
+// APPLICATION LOGIC
+impl App {
+
+    // Create a new App with default values
+    fn new() -> Self {
+        Self {
+            field: TextField::new(),       // a plain input box
+            field_name: "Input",
+            field_index: 0,
+            input_mode: InputMode::Normal,// start in Normal mode
+            last_submitted: None,          // nothing submitted yet
+            history: Vec::new(),           // nothing submitted yet
+            history_index: None,           // editing the draft
+            draft: String::new(),
+            search: None,                  // not searching
+            history_file: None,            // history is not saved
+            message: None,
+            notes: TextArea::new()         // numbered lines, at most 500 characters
+                .line_numbers(true)
+                .validator(Validator::MaxLength(500))
+                .submit_keys(NOTES_SUBMIT_KEYS),
+        }
+    }
+
+    // Switch to the next example field ([f])
+    // The keymap and the clipboard are the user's, so they come along
+    fn next_field(&mut self) {
+        self.field_index = (self.field_index + 1) % FIELD_COUNT;
+        let (name, mut field) = example_field(self.field_index);
+
+        field.keymap = self.field.keymap;
+        field.clipboard = std::mem::take(&mut self.field.clipboard);
+        self.field = field;
+        self.field_name = name;
+    }
+
+    // Switch between the emacs and vi keymaps
+    // (the notes box uses the same one)
+    fn toggle_keymap(&mut self) {
+        self.field.keymap = match self.field.keymap {
+            Keymap::Emacs => Keymap::Vi,
+            Keymap::Vi => Keymap::Emacs,
+        };
+        self.notes.editor.keymap = self.field.keymap;
+    }
+
+    // Clear the buffer and stop typing, without submitting
+    fn cancel_input(&mut self) {
+        self.field.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    // Keys while typing: Enter and the history are handled here, the
+    // field does the editing (in both keymaps)
+    fn handle_editing_key(&mut self, key: KeyEvent) {
+        // An open completion dropdown gets Enter, Up and Down first
+        if self.field.handle_completion_key(key) {
+            return;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        // A password must not come back with Up, so it has no history
+        let history = !self.field.is_secret();
+        let vi_command = self.field.vi_command;
+
+        match key.code {
+            KeyCode::Enter => self.submit_input(),
+            KeyCode::Up if history => self.history_previous(),
+            KeyCode::Down if history => self.history_next(),
+            KeyCode::Char('p') if ctrl && history => self.history_previous(),
+            KeyCode::Char('n') if ctrl && history => self.history_next(),
+            KeyCode::Char('r') if ctrl && history => self.start_search(),
+            KeyCode::Char('k') if vi_command && history => self.history_previous(),
+            KeyCode::Char('j') if vi_command && history => self.history_next(),
+            // Esc that the field doesn't use (emacs, or vi command mode)
+            _ => {
+                if !self.field.handle_key(key) {
+                    self.cancel_input();
+                }
+            }
+        }
+    }
+
+    // Keys in the notes box: its submit keys hand the notes in, Esc
+    // leaves the box (the text stays for later), the box does the rest
+    fn handle_notes_key(&mut self, key: KeyEvent) {
+        if self.notes.is_submit_key(key) {
+            self.submit_notes();
+        } else if !self.notes.handle_key(key) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    // Show the notes as last_submitted, on one line
+    // They don't go in the history: Up in the field recalls single lines
+    fn submit_notes(&mut self) {
+        let Some(notes) = self.notes.submit() else {
+            return; // too long: the error shows under the box
+        };
+
+        if !notes.is_empty() {
+            self.last_submitted = Some(notes.replace('\n', " ⏎ "));
+        }
+        self.input_mode = InputMode::Normal;
+    }
+
+    // Save input as last_submitted and reset buffer
+    // An invalid value stays in the box, with the error under it
+    fn submit_input(&mut self) {
+        let Some(value) = self.field.submit() else {
+            return; // keep typing
+        };
+
+        if !value.is_empty() {
+            if self.field.is_secret() {
+                // show that something was entered, but not what
+                self.last_submitted = Some("•".repeat(value.graphemes(true).count()));
+            } else {
+                self.last_submitted = Some(value.clone());
+                self.add_to_history(value);
+            }
+        }
+
+        self.history_index = None;
+        self.draft.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    // Remember a submitted input, and save the history when it has a file
+    fn add_to_history(&mut self, entry: String) {
+        // Submitting the same thing twice in a row is remembered once
+        if self.history.last() != Some(&entry) {
+            self.history.push(entry);
+            if self.history.len() > HISTORY_SIZE {
+                self.history.remove(0); // forget the oldest entry
+            }
+        }
+
+        if let Some(path) = &self.history_file {
+            let mut text = self.history.join("\n");
+            text.push('\n');
+            if let Err(err) = fs::write(path, text) {
+                self.message = Some(format!("History not saved to {}: {err}", path.display()));
+            }
+        }
+    }
+
+    // Read the history saved by an earlier run, one entry per line
+    // A file that doesn't exist yet is just an empty history
+    fn load_history(&mut self, path: PathBuf) -> Result<()> {
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                self.history = text.lines().filter(|line| !line.is_empty()).map(String::from).collect();
+                let extra = self.history.len().saturating_sub(HISTORY_SIZE);
+                self.history.drain(..extra); // keep only the newest entries
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(eyre!("reading history {}: {err}", path.display())),
+        }
+
+        self.history_file = Some(path);
+        Ok(())
+    }
+
+    // Show the entry before the one shown now (Up, Ctrl-P)
+    // The first step up keeps what was typed, so Down can bring it back
+    fn history_previous(&mut self) {
+        let index = match self.history_index {
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = self.field.value();
+                self.history.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+
+        self.history_index = Some(index);
+        self.field.set_value(&self.history[index]);
+    }
+
+    // Show the entry after the one shown now, and the draft after the
+    // newest one (Down, Ctrl-N)
+    fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return; // already at the draft
+        };
+
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.field.set_value(&self.history[index + 1]);
+        } else {
+            self.history_index = None;
+            let draft = std::mem::take(&mut self.draft);
+            self.field.set_value(&draft);
+        }
+    }
+
+    // Start a Ctrl-R search through the history
+    fn start_search(&mut self) {
+        self.search = Some(HistorySearch {
+            query: String::new(),
+            found: None,
+            failed: false,
+            original: self.field.value(),
+        });
+        self.input_mode = InputMode::Search;
+    }
+
+    // Look for the newest entry before `before` that contains the query,
+    // and show it with the cursor on the match
+    fn search_history(&mut self, before: usize) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+
+        if search.query.is_empty() {
+            search.found = None;
+            search.failed = false;
+            self.field.set_value(&search.original);
+            return;
+        }
+
+        let found = self.history[..before.min(self.history.len())]
+            .iter()
+            .rposition(|entry| entry.contains(&search.query));
+
+        // A failed search keeps showing the last match
+        search.failed = found.is_none();
+        let Some(index) = found else {
+            return;
+        };
+        search.found = Some(index);
+
+        let entry = &self.history[index];
+        let at = entry.find(&search.query).unwrap_or(0);
+        self.field.set_value(entry);
+        self.field.character_index = entry[..at].graphemes(true).count().min(self.field.grapheme_count());
+    }
+
+    // Keys while searching the history
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            // Ctrl-R again: an older entry with the same query
+            KeyCode::Char('r') if ctrl => {
+                let before = search.found.unwrap_or(self.history.len());
+                self.search_history(before);
+            }
+            // Esc, Ctrl-G: give up and put the original input back
+            KeyCode::Esc => self.cancel_search(),
+            KeyCode::Char('g') if ctrl => self.cancel_search(),
+            KeyCode::Char(c) if !ctrl => {
+                search.query.push(c);
+                // a longer query can still match the entry found now
+                let before = search.found.map_or(self.history.len(), |index| index + 1);
+                self.search_history(before);
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.search_history(self.history.len());
+            }
+            // Enter runs the match straight away, like in a shell
+            KeyCode::Enter => {
+                self.accept_search();
+                self.submit_input();
+            }
+            // Anything else keeps the match to edit it
+            _ => self.accept_search(),
+        }
+    }
+
+    // Text pasted into the terminal (bracketed paste)
+    // It arrives as ONE event, so a newline in it can't submit the input
+    // halfway, and a big paste is a single insert instead of thousands
+    fn handle_paste(&mut self, text: &str) {
+        match self.input_mode {
+            InputMode::Editing => {
+                self.field.paste(text);
+                self.history_index = None;
+            }
+            InputMode::Search => {
+                if let Some(search) = &mut self.search {
+                    search.query.push_str(&single_line(text));
+                    let before = search.found.map_or(self.history.len(), |index| index + 1);
+                    self.search_history(before);
+                }
+            }
+            // The notes keep the line breaks
+            InputMode::Notes => self.notes.paste(text),
+            InputMode::Normal => {}
+        }
+    }
+
+    // Keep the match as the input and go back to editing
+    fn accept_search(&mut self) {
+        self.search = None;
+        self.history_index = None;
+        self.input_mode = InputMode::Editing;
+    }
+
+    fn cancel_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            self.field.set_value(&search.original);
+        }
+        self.input_mode = InputMode::Editing;
+    }
+}
+
+
+// MAIN PROGRAM
+fn main() -> Result<()> {
+    // Install better error handling
+    color_eyre::install()?;
+
+    let mut app = App::new();
+
+    // `--history FILE` keeps the history between runs
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => {}
+        [flag, path] if flag == "--history" => app.load_history(PathBuf::from(path))?,
+        _ => return Err(eyre!("usage: ex04_input_prompt [--history FILE]")),
+    }
+
+    // Raw mode so keys are read instantly, and the alternate screen
+    // Bracketed paste: the terminal sends a paste as one Event::Paste
+    // instead of typing it key by key
+    // Most terminals send Ctrl-Enter as a plain Enter; the ones that speak
+    // the kitty keyboard protocol can tell them apart when asked to
+    let mut terminal = Session::start()?
+        .with_bracketed_paste()?
+        .with_keyboard_enhancement()?;
+
+    // MAIN LOOP
+    loop {
+        terminal.draw(|f| {
+            let frame = f.area();
+
+            // Split screen into 4 vertical parts
+            // (the input and the notes get an extra line for their errors)
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(11),
+                    Constraint::Length(4),
+                    Constraint::Min(6),
+                    Constraint::Length(3),
+                ])
+                .split(frame);
+
+            // HEADER TEXT
+            let header_text = vec![
+                title_text("Input Prompt Exercise", false).alignment(Alignment::Center),
+                Line::from(Span::styled("Press [i] to start typing", default_style_text(false))),
+                Line::from(Span::styled(
+                    format!("Press [m] to switch keymap (now: {:?})", app.field.keymap),
+                    default_style_text(false),
+                )),
+                Line::from(Span::styled(
+                    format!("Press [f] to try another field (now: {})", app.field_name),
+                    default_style_text(false),
+                )),
+                Line::from(Span::styled("[Up]/[Down] recall earlier inputs, [Ctrl-R] searches them", default_style_text(false))),
+                Line::from(Span::styled("[Shift]+arrows select, [Ctrl-C]/[Ctrl-X]/[Ctrl-V] copy, cut, paste", default_style_text(false))),
+                Line::from(Span::styled("[Tab] completes words in the Path and Language fields", default_style_text(false))),
+                Line::from(Span::styled("Press [n] to write notes, [Ctrl-Enter] or [Ctrl-S] submits them", default_style_text(false))),
+                Line::from(Span::styled("Press [q] to quit", default_style_text(false))),
+            ];
+
+            let mode_label = match app.input_mode {
+                InputMode::Normal | InputMode::Notes => "",
+                InputMode::Editing if app.field.vi_command => "[COMMAND]",
+                InputMode::Editing if app.field.completing() => "[COMPLETE]",
+                InputMode::Editing | InputMode::Search => "[INSERT]",
+            };
+
+            // While searching the label shows the query, like bash does
+            let prefix = match &app.search {
+                Some(search) if search.failed => format!("(failed reverse-i-search)`{}': ", search.query),
+                Some(search) => format!("(reverse-i-search)`{}': ", search.query),
+                None => format!("{mode_label} {}: ", app.field_name),
+            };
+
+            let last = app.last_submitted.as_deref().unwrap_or("None");
+
+            let submit_text = Line::from(vec![
+                Span::styled("Last Submitted: ", default_style_text(false)),
+                Span::raw(last),
+            ]);
+
+            // DRAW PANELS
+            let header_panel = Paragraph::new(header_text)
+                .block(Block::default().borders(Borders::ALL).padding(Padding::new(4, 4, 0, 0)));
+
+            // Errors (like a history file that can't be written) go in the title
+            let mut submit_block = Block::default().borders(Borders::ALL);
+            if let Some(message) = &app.message {
+                submit_block = submit_block.title(Span::styled(message.as_str(), Style::default().fg(Color::Red)));
+            }
+            let submit_panel = Paragraph::new(submit_text).block(submit_block);
+
+            f.render_widget(header_panel, layout[0]);
+            f.render_widget(submit_panel, layout[3]);
+
+            // The field draws itself, and shows the cursor only when typing
+            let typing = matches!(app.input_mode, InputMode::Editing | InputMode::Search);
+            app.field.render(f, layout[1], &prefix, typing);
+
+            // So does the notes box, with the mode and its length in the title
+            let writing = matches!(app.input_mode, InputMode::Notes);
+            let notes_mode = match app.input_mode {
+                InputMode::Notes if app.notes.editor.vi_command => "[COMMAND] ",
+                InputMode::Notes => "[INSERT] ",
+                _ => "",
+            };
+            let length = app.notes.value().graphemes(true).count();
+            let notes_title = format!(" {notes_mode}Notes ({length}/500) ");
+            app.notes.render(f, layout[2], &notes_title, writing);
+        })?;
+
+        // READ KEYBOARD INPUT (and pasted text)
+        let event = event::read()?;
+        if let Event::Paste(text) = &event {
+            app.handle_paste(text);
+        }
+        if let Event::Key(key) = event {
+            match app.input_mode {
+                InputMode::Normal => match key.code {
+                    KeyCode::Char('i') => app.input_mode = InputMode::Editing,
+                    KeyCode::Char('m') => app.toggle_keymap(),
+                    KeyCode::Char('f') => app.next_field(),
+                    KeyCode::Char('n') => app.input_mode = InputMode::Notes,
+                    KeyCode::Char('q') => break,
+                    _ => {}
+                },
+                InputMode::Editing if key.kind == KeyEventKind::Press => app.handle_editing_key(key),
+                InputMode::Search if key.kind == KeyEventKind::Press => app.handle_search_key(key),
+                InputMode::Notes if key.kind == KeyEventKind::Press => app.handle_notes_key(key),
+                _ => {}
+            }
+        }
+
+        // Copied text also goes to the terminal's clipboard, so it can be
+        // pasted in other programs (works over SSH too)
+        if let Some(text) = app.field.host_clipboard.take().or_else(|| app.notes.editor.host_clipboard.take()) {
+            execute!(terminal.backend_mut(), Print(osc52(&text)))?;
+        }
+    }
+
+    // CLEANUP TERMINAL
+    terminal.finish()?;
+
+    Ok(())
+}
+
+
+// The OSC 52 escape sequence that asks the terminal to put `text` on
+// the system clipboard ("c"); terminals that don't know it ignore it
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", BASE64.encode(text))
+}
+
+
+// TESTS
+// Run with: cargo test --bin ex04_input_prompt
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Press a key while editing, in whichever mode the app is in
+    fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+        let key = KeyEvent::new(code, modifiers);
+        match app.input_mode {
+            InputMode::Search => app.handle_search_key(key),
+            InputMode::Notes => app.handle_notes_key(key),
+            _ => app.handle_editing_key(key),
+        }
+    }
+
+    // Press a plain key (no Ctrl or Alt) for each char of `keys`
+    fn press_keys(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            press(app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    // The input with a '|' where the cursor is
+    fn with_cursor(app: &App) -> String {
+        let mut shown = app.field.input.clone();
+        shown.insert(app.field.byte_index(), '|');
+        shown
+    }
+
+    // An app that is typing `text`
+    fn typing(text: &str) -> App {
+        let mut app = App::new();
+        app.input_mode = InputMode::Editing;
+        app.field.insert_text(text);
+        app
+    }
+
+    #[test]
+    fn emacs_esc_cancels_the_input() {
+        let mut app = typing("draft");
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(matches!(app.input_mode, InputMode::Normal));
+        assert_eq!(app.field.input, "");
+    }
+
+    #[test]
+    fn vi_esc_twice_cancels_the_input() {
+        let mut app = typing("abc");
+        app.toggle_keymap();
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(matches!(app.input_mode, InputMode::Editing));
+        assert!(app.field.vi_command);
+
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(matches!(app.input_mode, InputMode::Normal));
+        assert!(!app.field.vi_command);
+    }
+
+    // An app that has already submitted `entries`, now typing again
+    fn with_history(entries: &[&str]) -> App {
+        let mut app = App::new();
+        for entry in entries {
+            app.field.insert_text(entry);
+            app.submit_input();
+        }
+        app.input_mode = InputMode::Editing;
+        app
+    }
+
+    #[test]
+    fn up_and_down_browse_the_history_and_keep_the_draft() {
+        let mut app = with_history(&["first", "second"]);
+        app.field.insert_text("draft");
+
+        press(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&app), "second|");
+        press(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&app), "first|");
+
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&app), "second|");
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&app), "draft|");
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&app), "draft|");
+    }
+
+    #[test]
+    fn vi_k_and_j_browse_the_history_in_command_mode() {
+        let mut app = with_history(&["first", "second"]);
+        app.toggle_keymap();
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+        press_keys(&mut app, "kk");
+        assert_eq!(with_cursor(&app), "firs|t");
+        press_keys(&mut app, "j");
+        assert_eq!(with_cursor(&app), "secon|d");
+    }
+
+    #[test]
+    fn history_skips_repeats_and_forgets_the_oldest() {
+        let mut app = with_history(&["same", "same"]);
+        assert_eq!(app.history, ["same"]);
+
+        for i in 0..HISTORY_SIZE {
+            app.field.insert_text(&i.to_string());
+            app.submit_input();
+        }
+        assert_eq!(app.history.len(), HISTORY_SIZE);
+        assert_eq!(app.history[0], "0");
+    }
+
+    #[test]
+    fn ctrl_r_finds_newer_matches_first() {
+        let mut app = with_history(&["git status", "cargo test", "git commit", "ls"]);
+        app.field.insert_text("typed");
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert!(matches!(app.input_mode, InputMode::Search));
+
+        press_keys(&mut app, "git");
+        assert_eq!(with_cursor(&app), "|git commit");
+
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&app), "|git status");
+
+        // no older match: the search fails but keeps showing the last one
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert!(app.search.as_ref().unwrap().failed);
+        assert_eq!(app.field.input, "git status");
+    }
+
+    #[test]
+    fn ctrl_r_narrows_the_search_as_you_type() {
+        let mut app = with_history(&["cargo build", "cargo test", "echo test"]);
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+
+        press_keys(&mut app, "t");
+        assert_eq!(app.field.input, "echo test");
+        press_keys(&mut app, "es");
+        assert_eq!(with_cursor(&app), "echo |test");
+
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        }
+        press_keys(&mut app, "ca");
+        assert_eq!(with_cursor(&app), "|cargo test");
+    }
+
+    #[test]
+    fn esc_cancels_the_search() {
+        let mut app = with_history(&["old"]);
+        app.field.insert_text("new");
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        press_keys(&mut app, "o");
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(matches!(app.input_mode, InputMode::Editing));
+        assert_eq!(with_cursor(&app), "new|");
+    }
+
+    #[test]
+    fn other_keys_keep_the_match_for_editing() {
+        let mut app = with_history(&["hello world"]);
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        press_keys(&mut app, "world");
+        press(&mut app, KeyCode::End, KeyModifiers::NONE);
+
+        assert!(matches!(app.input_mode, InputMode::Editing));
+        assert_eq!(with_cursor(&app), "hello |world");
+        press(&mut app, KeyCode::End, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&app), "hello world|");
+    }
+
+    #[test]
+    fn enter_in_search_submits_the_match() {
+        let mut app = with_history(&["again", "other"]);
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        press_keys(&mut app, "ag");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        assert_eq!(app.last_submitted.as_deref(), Some("again"));
+        assert_eq!(app.history, ["again", "other", "again"]);
+    }
+
+    #[test]
+    fn history_is_saved_and_loaded_again() {
+        let path = std::env::temp_dir().join(format!("ex04_history_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut app = App::new();
+        app.load_history(path.clone()).unwrap();
+        app.field.insert_text("one");
+        app.submit_input();
+        app.field.insert_text("two");
+        app.submit_input();
+
+        let mut again = App::new();
+        again.load_history(path.clone()).unwrap();
+        assert_eq!(again.history, ["one", "two"]);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn osc52_encodes_the_text_in_base64() {
+        assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+    }
+
+    #[test]
+    fn paste_inserts_at_the_cursor_without_submitting() {
+        let mut app = typing("[]");
+        app.field.move_cursor_left();
+        app.handle_paste("first line\nsecond line\n");
+
+        assert_eq!(with_cursor(&app), "[first line second line|]");
+        assert!(matches!(app.input_mode, InputMode::Editing));
+        assert_eq!(app.last_submitted, None);
+    }
+
+    #[test]
+    fn paste_extends_the_history_search() {
+        let mut app = with_history(&["deploy --prod", "deploy --dry-run"]);
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        app.handle_paste("--prod");
+        assert_eq!(app.field.input, "deploy --prod");
+    }
+
+    #[test]
+    fn paste_is_ignored_when_not_typing() {
+        let mut app = App::new();
+        app.handle_paste("text");
+        assert_eq!(app.field.input, "");
+    }
+
+    // Switch to the example field called `name`
+    fn switch_to(app: &mut App, name: &str) {
+        while app.field_name != name {
+            app.next_field();
+        }
+        app.input_mode = InputMode::Editing;
+    }
+
+    #[test]
+    fn invalid_input_is_refused_and_keeps_focus() {
+        let mut app = App::new();
+        switch_to(&mut app, "Age");
+        press_keys(&mut app, "200");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(matches!(app.input_mode, InputMode::Editing));
+        assert_eq!(app.field.input, "200");
+        assert_eq!(app.field.error().as_deref(), Some("A number from 0 to 150"));
+        assert_eq!(app.last_submitted, None);
+
+        press(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(matches!(app.input_mode, InputMode::Normal));
+        assert_eq!(app.last_submitted.as_deref(), Some("20"));
+    }
+
+    #[test]
+    fn empty_required_field_is_refused() {
+        let mut app = App::new();
+        switch_to(&mut app, "Name");
+        assert_eq!(app.field.error(), None);
+
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(matches!(app.input_mode, InputMode::Editing));
+        assert_eq!(app.field.error().as_deref(), Some("Can't be empty"));
+    }
+
+    #[test]
+    fn template_field_submits_the_formatted_value() {
+        let mut app = App::new();
+        switch_to(&mut app, "Time");
+        press_keys(&mut app, "1430");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.last_submitted.as_deref(), Some("14:30"));
+        assert_eq!(app.history, ["14:30"]);
+    }
+
+    #[test]
+    fn passwords_stay_out_of_the_history() {
+        let mut app = with_history(&["earlier"]);
+        switch_to(&mut app, "Password");
+        press_keys(&mut app, "hunter2");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        assert_eq!(app.last_submitted.as_deref(), Some("•••••••"));
+        assert_eq!(app.history, ["earlier"]);
+
+        // and Up doesn't bring anything into a password field
+        app.input_mode = InputMode::Editing;
+        press(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(app.field.input, "");
+    }
+
+    #[test]
+    fn enter_takes_the_completion_before_submitting() {
+        let mut app = with_history(&["earlier"]);
+        switch_to(&mut app, "Language");
+        press_keys(&mut app, "ja");
+        assert!(app.field.completing());
+
+        // Down moves through the dropdown instead of the history
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&app), "JavaScript|");
+        assert!(matches!(app.input_mode, InputMode::Editing));
+
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.last_submitted.as_deref(), Some("JavaScript"));
+    }
+
+    #[test]
+    fn esc_closes_the_completion_before_cancelling() {
+        let mut app = App::new();
+        switch_to(&mut app, "Language");
+        press(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        assert!(app.field.completing());
+
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.field.completing());
+        assert!(matches!(app.input_mode, InputMode::Editing));
+    }
+
+    #[test]
+    fn switching_fields_keeps_the_keymap() {
+        let mut app = App::new();
+        app.toggle_keymap();
+        app.next_field();
+        assert_eq!(app.field_name, "Name");
+        assert_eq!(app.field.keymap, Keymap::Vi);
+    }
+
+    #[test]
+    fn notes_keep_enter_and_submit_with_ctrl_s() {
+        let mut app = App::new();
+        app.input_mode = InputMode::Notes;
+        press_keys(&mut app, "buy");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        app.handle_paste("milk\r\neggs");
+        assert!(matches!(app.input_mode, InputMode::Notes));
+
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(app.last_submitted.as_deref(), Some("buy ⏎ milk ⏎ eggs"));
+        assert!(matches!(app.input_mode, InputMode::Normal));
+        assert!(app.history.is_empty());
+    }
+
+    #[test]
+    fn esc_leaves_the_notes_but_keeps_them() {
+        let mut app = App::new();
+        app.input_mode = InputMode::Notes;
+        press_keys(&mut app, "draft");
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(matches!(app.input_mode, InputMode::Normal));
+        assert_eq!(app.notes.value(), "draft");
+    }
+}
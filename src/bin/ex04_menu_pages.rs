@@ -1,14 +1,11 @@
 use color_eyre::Result;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use crossterm::{event, execute};
-use ratatui::backend::CrosstermBackend;
+use crossterm::event;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
-use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
-use ratatui::Terminal;
-use std::io;
+use rust_tui_lab::style::{menu_style, title_text};
+use rust_tui_lab::terminal::Session;
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum Page {
@@ -23,7 +20,7 @@ struct AppPage {
 
 impl AppPage {
     fn new() -> Self {
-        let mut current_page = Page::Home;
+        let current_page = Page::Home;
 
         Self { current_page }
     }
@@ -32,13 +29,8 @@ impl AppPage {
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    enable_raw_mode()?;
-
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode and the alternate screen, until the session is finished
+    let mut terminal = Session::start()?;
 
     let mut app_page = AppPage::new();
 
@@ -63,34 +55,20 @@ fn main() -> Result<()> {
             ])
                 .split(vertical_layout[1]);
 
-            let header_title = Line::from(
-                Span::styled(
-                    "Rust TUI Lab – Menu Pages",
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD)
-                )
-            ).alignment(Alignment::Center);
+            let header_title = title_text("Rust TUI Lab – Menu Pages", false).alignment(Alignment::Center);
 
 
             let menu_text = vec![
-                Line::from(
-                    Span::styled(
-                        "MENU",
-                        Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD)
-                    )
-                ).alignment(Alignment::Center),
+                title_text("MENU", false).alignment(Alignment::Center),
                 Line::from(""),
                 Line::from(
-                    Span::styled("Home", menu_style(Page::Home, app_page.current_page))
+                    Span::styled("Home", menu_style(app_page.current_page == Page::Home))
                 ).alignment(Alignment::Center),
                 Line::from(
-                    Span::styled("Help", menu_style(Page::Help, app_page.current_page))
+                    Span::styled("Help", menu_style(app_page.current_page == Page::Help))
                 ).alignment(Alignment::Center),
                 Line::from(
-                    Span::styled("About", menu_style(Page::About, app_page.current_page))
+                    Span::styled("About", menu_style(app_page.current_page == Page::About))
                 ).alignment(Alignment::Center),
                 Line::from(""),
             ];
@@ -130,14 +108,7 @@ fn main() -> Result<()> {
             };
 
             let mut content_lines = vec![
-                Line::from(
-                    Span::styled(
-                        "CONTENT",
-                        Style::default()
-                            .fg(Color::White)
-                            .add_modifier(Modifier::BOLD),
-                    )
-                ).alignment(Alignment::Center),
+                title_text("CONTENT", false).alignment(Alignment::Center),
                 Line::from(""),
             ];
 
@@ -186,35 +157,22 @@ fn main() -> Result<()> {
 
         })?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
-                    KeyCode::Char('h')  => app_page.current_page = Page::Home,
-                    KeyCode::Char('?') => app_page.current_page = Page::Help,
-                    KeyCode::Char('a') => app_page.current_page = Page::About,
-                    _ => {}
-                }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('h')  => app_page.current_page = Page::Home,
+                KeyCode::Char('?') => app_page.current_page = Page::Help,
+                KeyCode::Char('a') => app_page.current_page = Page::About,
+                _ => {}
             }
         }
 
     }
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    // Restore terminal
+    terminal.finish()?;
 
     Ok(())
 
 }
-
-fn menu_style(page: Page, current: Page) -> Style {
-    if page == current {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::White)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::White)
-    }
-}
@@ -0,0 +1,43 @@
+use chrono::{Days, NaiveDate};
+
+use crate::task::Task;
+
+// Completed, non-recurring tasks dated more than `age_days` before
+// `today`. An age that reaches past the first date chrono knows archives
+// nothing.
+pub fn is_archivable(task: &Task, today: NaiveDate, age_days: u32) -> bool {
+    let Some(cutoff) = today.checked_sub_days(Days::new(age_days.into())) else {
+        return false;
+    };
+    task.done && task.recurrence.is_none() && task.date < cutoff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Recurrence;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn done(day: NaiveDate) -> Task {
+        Task { done: true, ..Task::new(day, "", "done") }
+    }
+
+    #[test]
+    fn archives_done_tasks_older_than_the_age() {
+        let today = date(2024, 5, 31);
+
+        assert!(is_archivable(&done(date(2024, 4, 30)), today, 30));
+        assert!(!is_archivable(&done(date(2024, 5, 1)), today, 30));
+        assert!(!is_archivable(&Task::new(date(2024, 1, 1), "", "open"), today, 30));
+        assert!(!is_archivable(&Task { recurrence: Some(Recurrence::Daily), ..done(date(2024, 1, 1)) }, today, 30));
+        assert!(is_archivable(&done(date(2024, 5, 30)), today, 0));
+    }
+
+    #[test]
+    fn a_huge_age_archives_nothing() {
+        assert!(!is_archivable(&done(NaiveDate::MIN), date(2024, 5, 31), u32::MAX));
+    }
+}
@@ -0,0 +1,234 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+
+use crate::store::{Journal, Store};
+use crate::task::Task;
+
+const BACKUP_DIR: &str = "backups";
+
+// One backup in the list. A backup that can't be read stays in the list
+// with the reason, so the others can still be restored.
+pub struct Backup {
+    pub modified: Option<DateTime<Local>>,
+    pub contents: std::result::Result<Contents, String>,
+}
+
+pub struct Contents {
+    pub tasks: Vec<Task>,
+    pub journal: Journal,
+    // `None` for backups taken before the archive was backed up too.
+    pub archive: Option<Vec<Task>>,
+}
+
+// What restoring a backup would do to one task.
+pub enum Change {
+    Added(Task),
+    Removed(Task),
+    Changed(Task),
+}
+
+pub fn backup_dir(store: &Store) -> PathBuf {
+    store.dir().join(BACKUP_DIR)
+}
+
+// Copies the store and archive files as they are on disk (encrypted or
+// not) to `backups/tasks-<timestamp>.jsonl` and
+// `backups/archive-<timestamp>.jsonl`, and drops the oldest pairs beyond
+// `keep`. A missing archive is backed up as an empty one, so restoring
+// the pair brings back exactly what was there.
+pub fn create(store: &Store, keep: usize) -> Result<()> {
+    if keep == 0 || !store.path().exists() {
+        return Ok(());
+    }
+
+    let dir = backup_dir(store);
+    fs::create_dir_all(&dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+
+    let path = dir.join(format!("tasks-{}.jsonl", Local::now().format("%Y%m%d-%H%M%S%.9f")));
+    let archive = archive_path_for(&path);
+    if store.archive_path().exists() {
+        fs::copy(store.archive_path(), &archive).wrap_err_with(|| format!("writing {}", archive.display()))?;
+    } else {
+        fs::write(&archive, "").wrap_err_with(|| format!("writing {}", archive.display()))?;
+    }
+    // The tasks file goes last: it is what makes the pair show up in the
+    // list.
+    fs::copy(store.path(), &path).wrap_err_with(|| format!("writing {}", path.display()))?;
+
+    for old in backup_paths(store)?.into_iter().skip(keep) {
+        fs::remove_file(&old).wrap_err_with(|| format!("removing {}", old.display()))?;
+        let archive = archive_path_for(&old);
+        match fs::remove_file(&archive) {
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                return Err(err).wrap_err_with(|| format!("removing {}", archive.display()));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Every backup, newest first. Only a backup directory that can't be read
+// at all is an error.
+pub fn list(store: &Store) -> Result<Vec<Backup>> {
+    Ok(backup_paths(store)?.into_iter().map(|path| read(store, &path)).collect())
+}
+
+fn read(store: &Store, path: &Path) -> Backup {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok().map(DateTime::from);
+    let contents = read_contents(store, path).map_err(|err| format!("{err:#}"));
+    Backup { modified, contents }
+}
+
+fn read_contents(store: &Store, path: &Path) -> Result<Contents> {
+    let (tasks, journal) = store.load_from(path)?;
+    let archive = archive_path_for(path);
+    let archive = if archive.exists() { Some(store.load_from(&archive)?.0) } else { None };
+    Ok(Contents { tasks, journal, archive })
+}
+
+// `backups/tasks-<timestamp>.jsonl` → `backups/archive-<timestamp>.jsonl`.
+fn archive_path_for(path: &Path) -> PathBuf {
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    path.with_file_name(name.replacen("tasks-", "archive-", 1))
+}
+
+// Tasks that restoring `backup` over `current` would bring back, remove
+// or change.
+pub fn diff(current: &[Task], backup: &[Task]) -> Vec<Change> {
+    let mut changes = vec![];
+
+    for task in backup {
+        match current.iter().find(|t| t.id == task.id) {
+            None => changes.push(Change::Added(task.clone())),
+            Some(now) if now != task => changes.push(Change::Changed(task.clone())),
+            Some(_) => {}
+        }
+    }
+    for task in current.iter().filter(|t| !backup.iter().any(|b| b.id == t.id)) {
+        changes.push(Change::Removed(task.clone()));
+    }
+
+    changes
+}
+
+fn backup_paths(store: &Store) -> Result<Vec<PathBuf>> {
+    let dir = backup_dir(store);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err).wrap_err_with(|| format!("reading {}", dir.display())),
+    };
+
+    // The timestamp in the name sorts the same as the time it was taken.
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with("tasks-")))
+        .collect();
+    paths.sort();
+    paths.reverse();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    // A fresh store directory under the system temp dir.
+    fn temp_store(name: &str) -> Store {
+        let dir = std::env::temp_dir().join(format!("ex05-backup-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Store::new(dir)
+    }
+
+    fn task(text: &str) -> Task {
+        Task::new(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), "", text)
+    }
+
+    fn texts(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.text.as_str()).collect()
+    }
+
+    #[test]
+    fn backs_up_the_tasks_and_archive_together() {
+        let store = temp_store("pair");
+        store.save(&[task("current")], &Journal::new()).unwrap();
+        store.save_archive(&[task("archived")]).unwrap();
+
+        create(&store, 10).unwrap();
+        let backups = list(&store).unwrap();
+
+        assert_eq!(backups.len(), 1);
+        let contents = backups[0].contents.as_ref().unwrap();
+        assert_eq!(texts(&contents.tasks), ["current"]);
+        assert_eq!(texts(contents.archive.as_deref().unwrap()), ["archived"]);
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn a_missing_archive_is_backed_up_as_an_empty_one() {
+        let store = temp_store("empty-archive");
+        store.save(&[task("current")], &Journal::new()).unwrap();
+
+        create(&store, 10).unwrap();
+        let backups = list(&store).unwrap();
+
+        assert_eq!(backups[0].contents.as_ref().unwrap().archive, Some(vec![]));
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn an_unreadable_backup_is_listed_next_to_the_readable_ones() {
+        let store = temp_store("unreadable");
+        store.save(&[task("current")], &Journal::new()).unwrap();
+        create(&store, 10).unwrap();
+        fs::write(backup_dir(&store).join("tasks-00000000-000000.jsonl"), "not json\n").unwrap();
+
+        let backups = list(&store).unwrap();
+
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].contents.is_ok());
+        assert!(backups[1].contents.as_ref().is_err_and(|err| err.contains("line 1")));
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn older_backups_without_an_archive_leave_it_alone() {
+        let store = temp_store("legacy");
+        fs::create_dir_all(backup_dir(&store)).unwrap();
+        let text = crate::store::encode(&[task("old")], &Journal::new()).unwrap();
+        fs::write(backup_dir(&store).join("tasks-20240501-120000.jsonl"), text).unwrap();
+
+        let backups = list(&store).unwrap();
+
+        assert_eq!(backups[0].contents.as_ref().unwrap().archive, None);
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn pruning_removes_both_files_of_a_pair() {
+        let store = temp_store("prune");
+        store.save(&[task("current")], &Journal::new()).unwrap();
+
+        for _ in 0..3 {
+            create(&store, 2).unwrap();
+        }
+
+        let names: Vec<String> = fs::read_dir(backup_dir(&store))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names.len(), 4);
+        assert_eq!(names.iter().filter(|name| name.starts_with("archive-")).count(), 2);
+        assert_eq!(list(&store).unwrap().len(), 2);
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+
+use crate::task::Task;
+
+pub enum BulkOp {
+    ToggleDone,
+    Delete,
+    Reschedule(NaiveDate),
+    Retag { add: Vec<String>, remove: Vec<String> },
+    MoveToProject(String),
+}
+
+// Applies `op` to every task whose id is in `ids` and returns how many
+// were affected. Toggling marks all of them done unless all already are.
+pub fn apply(tasks: &mut Vec<Task>, ids: &BTreeSet<String>, op: &BulkOp) -> usize {
+    let count = tasks.iter().filter(|t| ids.contains(&t.id)).count();
+
+    match op {
+        BulkOp::Delete => tasks.retain(|t| !ids.contains(&t.id)),
+        BulkOp::ToggleDone => {
+            let done = !tasks.iter().filter(|t| ids.contains(&t.id)).all(|t| t.done);
+            for task in tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+                task.done = done;
+            }
+        }
+        BulkOp::Reschedule(date) => {
+            for task in tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+                task.date = *date;
+            }
+        }
+        BulkOp::Retag { add, remove } => {
+            for task in tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+                task.tags.retain(|tag| !remove.contains(tag));
+                for tag in add {
+                    if !task.tags.contains(tag) {
+                        task.tags.push(tag.clone());
+                    }
+                }
+            }
+        }
+        BulkOp::MoveToProject(project) => {
+            for task in tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+                task.project = project.clone();
+            }
+        }
+    }
+
+    count
+}
+
+// "#a +b -c" adds a and b and removes c.
+pub fn parse_retag(input: &str) -> BulkOp {
+    let mut add = vec![];
+    let mut remove = vec![];
+
+    for word in input.split_whitespace() {
+        match word.strip_prefix('-') {
+            Some(tag) => remove.push(tag.trim_start_matches('#').to_string()),
+            None => add.push(word.trim_start_matches(['+', '#']).to_string()),
+        }
+    }
+
+    add.retain(|tag| !tag.is_empty());
+    remove.retain(|tag| !tag.is_empty());
+    BulkOp::Retag { add, remove }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retag(input: &str) -> (Vec<String>, Vec<String>) {
+        match parse_retag(input) {
+            BulkOp::Retag { add, remove } => (add, remove),
+            _ => unreachable!(),
+        }
+    }
+
+    fn tasks(tags: &[&[&str]]) -> Vec<Task> {
+        tags.iter()
+            .map(|tags| Task {
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                ..Task::new(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), "", "task")
+            })
+            .collect()
+    }
+
+    fn ids(tasks: &[Task]) -> BTreeSet<String> {
+        tasks.iter().map(|t| t.id.clone()).collect()
+    }
+
+    #[test]
+    fn parses_retag_input() {
+        assert_eq!(retag("#a +b -c"), (vec!["a".into(), "b".into()], vec!["c".into()]));
+        assert_eq!(retag("plain -#old"), (vec!["plain".into()], vec!["old".into()]));
+        assert_eq!(retag("  #a   #b  "), (vec!["a".into(), "b".into()], vec![]));
+        assert_eq!(retag("# + - -#"), (vec![], vec![]));
+        assert_eq!(retag(""), (vec![], vec![]));
+    }
+
+    #[test]
+    fn retag_adds_once_and_removes() {
+        let mut tasks = tasks(&[&["a", "c"], &["b"], &["c"]]);
+        let selected = ids(&tasks[..2]);
+
+        let count = apply(&mut tasks, &selected, &parse_retag("#a +b -c"));
+
+        assert_eq!(count, 2);
+        assert_eq!(tasks[0].tags, ["a", "b"]);
+        assert_eq!(tasks[1].tags, ["b", "a"]);
+        assert_eq!(tasks[2].tags, ["c"]);
+    }
+
+    #[test]
+    fn toggle_marks_all_done_unless_all_already_are() {
+        let mut tasks = tasks(&[&[], &[], &[]]);
+        tasks[0].done = true;
+        let selected = ids(&tasks[..2]);
+
+        apply(&mut tasks, &selected, &BulkOp::ToggleDone);
+        assert_eq!(tasks.iter().map(|t| t.done).collect::<Vec<_>>(), [true, true, false]);
+
+        apply(&mut tasks, &selected, &BulkOp::ToggleDone);
+        assert_eq!(tasks.iter().map(|t| t.done).collect::<Vec<_>>(), [false, false, false]);
+    }
+
+    #[test]
+    fn delete_reschedule_and_move_touch_only_the_selection() {
+        let mut tasks = tasks(&[&[], &[], &[]]);
+        let kept = tasks[2].id.clone();
+        let selected = ids(&tasks[..2]);
+        let day = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+
+        apply(&mut tasks, &selected, &BulkOp::Reschedule(day));
+        apply(&mut tasks, &selected, &BulkOp::MoveToProject("lab".into()));
+        assert_eq!(tasks.iter().map(|t| (t.date == day, t.project.as_str())).collect::<Vec<_>>(), [(true, "lab"), (true, "lab"), (false, "")]);
+
+        assert_eq!(apply(&mut tasks, &selected, &BulkOp::Delete), 2);
+        assert_eq!(ids(&tasks), BTreeSet::from([kept]));
+    }
+}
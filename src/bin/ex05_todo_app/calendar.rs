@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+
+// Holidays from any number of files plus the weekdays that are worked,
+// e.g. a country's public holidays combined with a company's closures.
+pub struct Calendar {
+    holidays: BTreeMap<NaiveDate, Vec<String>>,
+    working_days: Vec<Weekday>,
+    // "every weekday" tasks don't happen on holidays.
+    pub skip_holidays: bool,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self {
+            holidays: BTreeMap::new(),
+            working_days: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+            skip_holidays: false,
+        }
+    }
+}
+
+impl Calendar {
+    // Reads every file in `paths`, relative ones from `dir`; a date listed
+    // in several files keeps all its names.
+    pub fn load(dir: &Path, paths: &[PathBuf], working_days: Vec<Weekday>, skip_holidays: bool) -> Result<Self> {
+        let mut calendar = Self { working_days, skip_holidays, ..Self::default() };
+
+        for path in paths {
+            let path = dir.join(path);
+            let text = fs::read_to_string(&path).wrap_err_with(|| format!("reading {}", path.display()))?;
+            let entries = parse(&path, &text).wrap_err_with(|| format!("reading holidays from {}", path.display()))?;
+
+            for (date, name) in entries {
+                let names = calendar.holidays.entry(date).or_default();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+
+        Ok(calendar)
+    }
+
+    pub fn holidays_on(&self, day: NaiveDate) -> &[String] {
+        self.holidays.get(&day).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_day_off(&self, day: NaiveDate) -> bool {
+        !self.working_days.contains(&day.weekday())
+    }
+
+    // Whether "every weekday" tasks happen on `day`.
+    pub fn has_weekday_tasks(&self, day: NaiveDate) -> bool {
+        self.working_days.contains(&day.weekday()) && !(self.skip_holidays && self.holidays.contains_key(&day))
+    }
+}
+
+// `date: name` pairs from a `.toml`, `.yaml`/`.yml` or `.csv` file:
+//
+//     2026-12-25 = "Christmas Day"      # TOML
+//     2026-12-25: Christmas Day         # YAML
+//     2026-12-25,Christmas Day          # CSV, an optional `date,name` header
+fn parse(path: &Path, text: &str) -> Result<Vec<(NaiveDate, String)>> {
+    let entries: Vec<(String, String)> = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str::<BTreeMap<String, String>>(text)?.into_iter().collect(),
+        Some("yaml" | "yml") => serde_yaml::from_str::<BTreeMap<String, String>>(text)?.into_iter().collect(),
+        Some("csv") => text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && *line != "date,name")
+            .map(|line| {
+                let (date, name) = line.split_once(',').ok_or_else(|| eyre!("expected date,name in \"{line}\""))?;
+                Ok((date.trim().to_string(), name.trim().trim_matches('"').to_string()))
+            })
+            .collect::<Result<_>>()?,
+        _ => return Err(eyre!("unknown format; use .toml, .yaml or .csv")),
+    };
+
+    entries
+        .into_iter()
+        .map(|(date, name)| {
+            let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|_| eyre!("\"{date}\" is not a date like 2026-12-25"))?;
+            Ok((date, name))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn christmas() -> Vec<(NaiveDate, String)> {
+        vec![(date(2026, 12, 25), "Christmas Day".into()), (date(2026, 12, 26), "Boxing Day".into())]
+    }
+
+    #[test]
+    fn parses_toml() {
+        let text = "2026-12-25 = \"Christmas Day\"\n# closed\n2026-12-26 = \"Boxing Day\"\n";
+        assert_eq!(parse(Path::new("uk.toml"), text).unwrap(), christmas());
+    }
+
+    #[test]
+    fn parses_yaml() {
+        let text = "2026-12-25: Christmas Day\n2026-12-26: \"Boxing Day\"\n";
+        assert_eq!(parse(Path::new("uk.yaml"), text).unwrap(), christmas());
+        assert_eq!(parse(Path::new("uk.yml"), text).unwrap(), christmas());
+    }
+
+    #[test]
+    fn parses_csv_with_header_comments_and_quotes() {
+        let text = "date,name\n# closed\n\n2026-12-25, \"Christmas Day\"\n2026-12-26,Boxing Day\n";
+        assert_eq!(parse(Path::new("uk.csv"), text).unwrap(), christmas());
+    }
+
+    #[test]
+    fn rejects_bad_dates_lines_and_formats() {
+        let err = parse(Path::new("uk.toml"), "25.12.2026 = \"Christmas Day\"").unwrap_err();
+        assert!(err.to_string().contains("25.12.2026"));
+        assert!(parse(Path::new("uk.csv"), "2026-12-25 Christmas Day").is_err());
+        assert!(parse(Path::new("uk.toml"), "2026-12-25 = ").is_err());
+        assert!(parse(Path::new("uk.json"), "{}").is_err());
+    }
+
+    #[test]
+    fn load_combines_files_and_keeps_every_name() {
+        let dir = std::env::temp_dir().join(format!("ex05-calendar-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("country.toml"), "2026-12-25 = \"Christmas Day\"\n").unwrap();
+        fs::write(dir.join("company.csv"), "2026-12-25,Office closed\n2026-12-25,Christmas Day\n2026-12-24,Office closed\n").unwrap();
+
+        let working_days = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        let paths = [PathBuf::from("country.toml"), PathBuf::from("company.csv")];
+        let calendar = Calendar::load(&dir, &paths, working_days, true).unwrap();
+
+        assert_eq!(calendar.holidays_on(date(2026, 12, 25)), ["Christmas Day", "Office closed"]);
+        assert_eq!(calendar.holidays_on(date(2026, 12, 24)), ["Office closed"]);
+        assert!(calendar.holidays_on(date(2026, 12, 23)).is_empty());
+        // Christmas 2026 is a Friday: a working day, but a holiday.
+        assert!(!calendar.is_day_off(date(2026, 12, 25)));
+        assert!(!calendar.has_weekday_tasks(date(2026, 12, 25)));
+        assert!(calendar.has_weekday_tasks(date(2026, 12, 23)));
+        assert!(calendar.is_day_off(date(2026, 12, 26)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
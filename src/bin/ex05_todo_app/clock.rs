@@ -0,0 +1,119 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+
+pub const TODAY_ENV: &str = "EX05_TODAY";
+pub const TZ_ENV: &str = "EX05_TZ";
+
+// The planner's "now": the zone every time is shown in and, for testing,
+// a fixed day that stands in for today.
+#[derive(Clone, Copy)]
+pub struct Clock {
+    pub zone: Tz,
+    today: Option<NaiveDate>,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self { zone: system_zone(), today: None }
+    }
+}
+
+impl Clock {
+    // `--tz`, else `$EX05_TZ`, else `time_zone` from the config, else the
+    // system zone; `--today`, else `$EX05_TODAY`, else the real date.
+    pub fn resolve(zone: Option<Tz>, today: Option<NaiveDate>, configured: Option<Tz>) -> Result<Self> {
+        let zone = match zone {
+            Some(zone) => Some(zone),
+            None => env(TZ_ENV, parse_zone)?,
+        };
+        let today = match today {
+            Some(today) => Some(today),
+            None => env(TODAY_ENV, parse_today)?,
+        };
+
+        Ok(Self { zone: zone.or(configured).unwrap_or_else(system_zone), today })
+    }
+
+    // With a fixed today, the current time of day on that date.
+    pub fn now(&self) -> DateTime<Tz> {
+        let now = Utc::now().with_timezone(&self.zone);
+        match self.today {
+            Some(today) => today
+                .and_time(now.time())
+                .and_local_timezone(self.zone)
+                .earliest()
+                .unwrap_or(now),
+            None => now,
+        }
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+pub fn parse_zone(name: &str) -> Result<Tz> {
+    name.parse().map_err(|_| eyre!("unknown time zone \"{name}\" (use a name like Europe/Berlin)"))
+}
+
+pub fn parse_today(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| eyre!("\"{date}\" is not a date like 2026-03-01"))
+}
+
+// `$TZ` when it names a zone, then the zone the system is set to.
+fn system_zone() -> Tz {
+    std::env::var("TZ")
+        .ok()
+        .and_then(|name| name.trim_start_matches(':').parse().ok())
+        .or_else(|| iana_time_zone::get_timezone().ok()?.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+fn env<T>(name: &str, parse: fn(&str) -> Result<T>) -> Result<Option<T>> {
+    match std::env::var(name) {
+        Ok(value) => parse(&value).map(Some).map_err(|err| eyre!("${name}: {err}")),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::{America::New_York, Asia::Tokyo, Europe::Berlin};
+
+    #[test]
+    fn explicit_values_win_over_the_config() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let clock = Clock::resolve(Some(Tokyo), Some(today), Some(Berlin)).unwrap();
+
+        assert_eq!(clock.zone, Tokyo);
+        assert_eq!(clock.today(), today);
+    }
+
+    #[test]
+    fn a_fixed_today_keeps_the_time_of_day_in_the_zone() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let clock = Clock { zone: New_York, today: Some(today) };
+
+        let before = Utc::now().with_timezone(&New_York);
+        let now = clock.now();
+        let after = Utc::now().with_timezone(&New_York);
+
+        assert_eq!(now.date_naive(), today);
+        assert_eq!(now.timezone(), New_York);
+        // Midnight between the readings is the one case the order breaks.
+        if before.date_naive() == after.date_naive() {
+            assert!(before.time() <= now.time() && now.time() <= after.time());
+        }
+    }
+
+    #[test]
+    fn parses_zone_names_and_dates() {
+        assert_eq!(parse_zone("Europe/Berlin").unwrap(), Berlin);
+        assert!(parse_zone("Mars/Olympus").unwrap_err().to_string().contains("Mars/Olympus"));
+        assert_eq!(parse_today("2026-03-01").unwrap(), NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert!(parse_today("01.03.2026").is_err());
+    }
+}
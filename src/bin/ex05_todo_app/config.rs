@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::Weekday::{self, Fri, Mon, Thu, Tue, Wed};
+use chrono_tz::Tz;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::Deserialize;
+
+use crate::i18n::{self, Language};
+
+pub const CONFIG_ENV: &str = "EX05_CONFIG";
+const CONFIG_FILE: &str = "config.toml";
+
+// Display settings from `config.toml`; every key is optional.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub language: Language,
+    // A chrono strftime pattern, e.g. "%Y-%m-%d".
+    pub date_format: String,
+    // The first column of the week and month views, and the day "next
+    // week" lands on.
+    pub week_start: Weekday,
+    // An IANA name like "Asia/Jakarta"; the system zone when unset.
+    pub time_zone: Option<Tz>,
+    // New tasks keep their wall-clock time in every zone instead of
+    // being pinned to the zone they were planned in.
+    pub floating_times: bool,
+    // Holiday files (.toml, .yaml or .csv), relative to the config file;
+    // all of them are combined.
+    pub holidays: Vec<PathBuf>,
+    // The weekdays "every weekday" tasks happen on; the others are days off.
+    pub working_days: Vec<Weekday>,
+    // "every weekday" tasks skip holidays too.
+    pub skip_holidays: bool,
+    // How many days old a completed task must be before `A` archives it.
+    pub archive_after_days: u32,
+    // How many backups are kept; 0 turns backups off.
+    pub backups: usize,
+    // Replaces single UI strings by key, on top of the language.
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            language: Language::English,
+            date_format: "%d-%m-%Y".into(),
+            week_start: Mon,
+            time_zone: None,
+            floating_times: false,
+            holidays: vec![],
+            working_days: vec![Mon, Tue, Wed, Thu, Fri],
+            skip_holidays: false,
+            archive_after_days: 30,
+            backups: 10,
+            labels: BTreeMap::new(),
+        }
+    }
+}
+
+// `$EX05_CONFIG`, falling back to `config.toml` in the data directory.
+pub fn path(data_dir: &Path) -> PathBuf {
+    std::env::var_os(CONFIG_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_dir.join(CONFIG_FILE))
+}
+
+// The defaults when there is no config file; a file with mistakes is an
+// error rather than half applied.
+pub fn load(data_dir: &Path) -> Result<Config> {
+    let path = path(data_dir);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Config::default()),
+        Err(err) => return Err(err).wrap_err_with(|| format!("reading {}", path.display())),
+    };
+
+    let config: Config = toml::from_str(&text).wrap_err_with(|| format!("parsing {}", path.display()))?;
+
+    if StrftimeItems::new(&config.date_format).any(|item| matches!(item, Item::Error)) {
+        return Err(eyre!("{}: invalid date_format \"{}\"", path.display(), config.date_format));
+    }
+    if let Some(key) = config.labels.keys().find(|key| !i18n::is_key(key)) {
+        return Err(eyre!("{}: unknown label \"{key}\"", path.display()));
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday::Sun;
+
+    // Runs `check` on a data directory holding `config` as config.toml
+    // (none for `None`).
+    fn with_config(name: &str, config: Option<&str>, check: impl FnOnce(Result<Config>)) {
+        let dir = std::env::temp_dir().join(format!("ex05-config-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        if let Some(config) = config {
+            fs::write(dir.join(CONFIG_FILE), config).unwrap();
+        }
+        check(load(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn no_file_means_the_defaults() {
+        with_config("none", None, |config| {
+            let config = config.unwrap();
+            assert_eq!(config.language, Language::English);
+            assert_eq!(config.date_format, "%d-%m-%Y");
+            assert_eq!((config.week_start, config.archive_after_days, config.backups), (Mon, 30, 10));
+        });
+    }
+
+    #[test]
+    fn reads_every_key() {
+        let text = r#"
+            language = "id"
+            date_format = "%Y-%m-%d"
+            week_start = "sunday"
+            time_zone = "Asia/Jakarta"
+            floating_times = true
+            holidays = ["id-2026.toml"]
+            working_days = ["mon", "tue"]
+            skip_holidays = true
+            archive_after_days = 60
+            backups = 0
+
+            [labels]
+            "day.today" = "Now"
+        "#;
+        with_config("full", Some(text), |config| {
+            let config = config.unwrap();
+            assert_eq!(config.language, Language::Indonesian);
+            assert_eq!(config.date_format, "%Y-%m-%d");
+            assert_eq!(config.week_start, Sun);
+            assert_eq!(config.time_zone, Some(chrono_tz::Asia::Jakarta));
+            assert!(config.floating_times && config.skip_holidays);
+            assert_eq!(config.holidays, [PathBuf::from("id-2026.toml")]);
+            assert_eq!(config.working_days, [Mon, Tue]);
+            assert_eq!((config.archive_after_days, config.backups), (60, 0));
+            assert_eq!(config.labels["day.today"], "Now");
+        });
+    }
+
+    #[test]
+    fn mistakes_are_errors_with_the_reason() {
+        let cases = [
+            ("format", r#"date_format = "%Y-%Q""#, "invalid date_format \"%Y-%Q\""),
+            ("label", "[labels]\n\"day.tomorow\" = \"Later\"", "unknown label \"day.tomorow\""),
+            ("key", "colour = \"red\"", "parsing"),
+            ("zone", r#"time_zone = "Mars/Olympus""#, "parsing"),
+        ];
+        for (name, text, reason) in cases {
+            with_config(name, Some(text), |config| {
+                let err = config.err().unwrap_or_else(|| panic!("{name}: expected an error"));
+                assert!(err.to_string().contains(reason), "{name}: {err}");
+            });
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+
+const MAGIC: &str = "ex05-encrypted v1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+// Key for the encrypted store format: a header line, the Argon2id salt,
+// then nonce and XChaCha20-Poly1305 ciphertext, both base64. The key is
+// derived once per unlock and reused for every save; the passphrase
+// itself is never kept.
+#[derive(Clone)]
+pub struct Cipher {
+    salt: [u8; SALT_LEN],
+    key: Key,
+    // Keys for files written under another salt (e.g. the archive saved
+    // before a re-encryption), derived while the passphrase was at hand.
+    other_keys: Vec<([u8; SALT_LEN], Key)>,
+    // Nothing, sealed with `key`: a passphrase matches when the key it
+    // derives opens this, which leaves the comparison to the AEAD tag check.
+    check: Vec<u8>,
+}
+
+pub fn is_encrypted(text: &str) -> bool {
+    text.starts_with(MAGIC)
+}
+
+impl Cipher {
+    // A fresh salt, for encrypting a store for the first time or under a
+    // new passphrase.
+    pub fn new(passphrase: &str) -> Result<Self> {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self::with_salt(passphrase, salt)
+    }
+
+    // Derives the keys of the encrypted files and checks them against the
+    // files, so a wrong passphrase is reported before anything is loaded.
+    // The first file's key is the one new saves use.
+    pub fn unlock(passphrase: &str, texts: &[&str]) -> Result<Self> {
+        let (first, rest) = texts.split_first().ok_or_else(|| eyre!("not an encrypted store"))?;
+        let (salt, _) = split(first)?;
+        let mut cipher = Self::with_salt(passphrase, salt)?;
+        cipher.decrypt(first)?;
+
+        for text in rest {
+            let (salt, _) = split(text)?;
+            if cipher.key_for(salt).is_none() {
+                let key = derive_key(passphrase, &salt)?;
+                cipher.other_keys.push((salt, key));
+                cipher.decrypt(text)?;
+            }
+        }
+        Ok(cipher)
+    }
+
+    pub fn matches(&self, passphrase: &str) -> bool {
+        derive_key(passphrase, &self.salt).is_ok_and(|key| open(&key, &self.check).is_ok())
+    }
+
+    pub fn encrypt(&self, plain: &str) -> Result<String> {
+        let payload = seal(&self.key, plain.as_bytes())?;
+        Ok(format!("{MAGIC}\n{}\n{}\n", STANDARD.encode(self.salt), STANDARD.encode(payload)))
+    }
+
+    pub fn decrypt(&self, text: &str) -> Result<String> {
+        let (salt, payload) = split(text)?;
+
+        // A file under a salt nobody derived a key for was encrypted
+        // after this unlock, with a passphrase we never saw.
+        let key = self
+            .key_for(salt)
+            .ok_or_else(|| eyre!("encrypted with another passphrase; unlock with that one to read it"))?;
+        let plain = open(key, &payload)?;
+
+        String::from_utf8(plain).wrap_err("decrypted store is not text")
+    }
+
+    fn key_for(&self, salt: [u8; SALT_LEN]) -> Option<&Key> {
+        if salt == self.salt {
+            return Some(&self.key);
+        }
+        self.other_keys.iter().find(|(other, _)| *other == salt).map(|(_, key)| key)
+    }
+
+    fn with_salt(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<Self> {
+        let key = derive_key(passphrase, &salt)?;
+        let check = seal(&key, b"")?;
+        Ok(Self { salt, key, other_keys: vec![], check })
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8; SALT_LEN]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| eyre!("deriving key: {err}"))?;
+    Ok(key)
+}
+
+// A random nonce followed by the ciphertext.
+fn seal(key: &Key, plain: &[u8]) -> Result<Vec<u8>> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = XChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plain)
+        .map_err(|_| eyre!("encryption failed"))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend(sealed);
+    Ok(payload)
+}
+
+fn open(key: &Key, payload: &[u8]) -> Result<Vec<u8>> {
+    if payload.len() < NONCE_LEN {
+        return Err(eyre!("encrypted store is truncated"));
+    }
+    let (nonce, sealed) = payload.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key)
+        .decrypt(XNonce::from_slice(nonce), sealed)
+        .map_err(|_| eyre!("wrong passphrase (or the store was tampered with)"))
+}
+
+fn split(text: &str) -> Result<([u8; SALT_LEN], Vec<u8>)> {
+    let mut lines = text.lines();
+    if lines.next() != Some(MAGIC) {
+        return Err(eyre!("not an encrypted store"));
+    }
+
+    let salt = STANDARD
+        .decode(lines.next().unwrap_or_default())
+        .ok()
+        .and_then(|salt| salt.try_into().ok())
+        .ok_or_else(|| eyre!("encrypted store has a damaged header"))?;
+    let payload = STANDARD
+        .decode(lines.next().unwrap_or_default())
+        .wrap_err("encrypted store is damaged")?;
+
+    Ok((salt, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_and_passphrase_check() {
+        let cipher = Cipher::new("correct horse").unwrap();
+        let text = cipher.encrypt("{\"kind\":\"task\"}\n").unwrap();
+        assert!(is_encrypted(&text));
+        assert_eq!(cipher.decrypt(&text).unwrap(), "{\"kind\":\"task\"}\n");
+
+        assert!(cipher.matches("correct horse"));
+        assert!(!cipher.matches("correct horse "));
+        assert!(!cipher.matches(""));
+    }
+
+    #[test]
+    fn unlock_checks_the_passphrase_against_the_file() {
+        let text = Cipher::new("pass").unwrap().encrypt("tasks").unwrap();
+        assert!(Cipher::unlock("wrong", &[&text]).is_err());
+
+        let cipher = Cipher::unlock("pass", &[&text]).unwrap();
+        assert_eq!(cipher.decrypt(&text).unwrap(), "tasks");
+        assert!(cipher.matches("pass"));
+    }
+
+    #[test]
+    fn files_under_another_salt_need_their_key_derived_at_unlock() {
+        let tasks = Cipher::new("pass").unwrap().encrypt("tasks").unwrap();
+        let archive = Cipher::new("pass").unwrap().encrypt("archive").unwrap();
+
+        let cipher = Cipher::unlock("pass", &[&tasks, &archive]).unwrap();
+        assert_eq!(cipher.decrypt(&archive).unwrap(), "archive");
+
+        let later = Cipher::new("pass").unwrap().encrypt("later").unwrap();
+        assert!(cipher.decrypt(&later).is_err());
+    }
+
+    #[test]
+    fn damaged_files_are_errors() {
+        let cipher = Cipher::new("pass").unwrap();
+        let text = cipher.encrypt("tasks").unwrap();
+        let mut lines: Vec<&str> = text.lines().collect();
+        lines[2] = "AAAA";
+        assert!(cipher.decrypt(&lines.join("\n")).is_err());
+        assert!(cipher.decrypt("tasks").is_err());
+    }
+}
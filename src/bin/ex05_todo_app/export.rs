@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+
+use crate::store::Journal;
+use crate::task::Task;
+
+// Markdown outline of the planner: one section per day with its tasks as
+// a checklist, followed by that day's journal entry.
+pub fn to_markdown(tasks: &[Task], journal: &Journal) -> String {
+    let days: BTreeSet<_> = tasks
+        .iter()
+        .map(|t| t.date)
+        .chain(journal.iter().filter(|(_, text)| !text.trim().is_empty()).map(|(date, _)| *date))
+        .collect();
+
+    let mut out = String::from("# Task Planner\n");
+
+    for day in days {
+        out.push_str(&format!("\n## {}\n\n", day.format("%Y-%m-%d (%A)")));
+
+        for task in tasks.iter().filter(|t| t.date == day) {
+            let check = if task.done { "x" } else { " " };
+            let time = if task.time.is_empty() { String::new() } else { format!("{} ", task.time) };
+            let tags: String = task.tags.iter().map(|tag| format!(" #{tag}")).collect();
+            let repeat = task.recurrence.map(|r| format!(" ({})", r.label())).unwrap_or_default();
+            out.push_str(&format!("- [{check}] {time}{}{tags}{repeat}\n", task.text));
+        }
+
+        if let Some(entry) = journal.get(&day).filter(|text| !text.trim().is_empty()) {
+            out.push_str("\n### Journal\n\n");
+            out.push_str(entry.trim_end());
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Recurrence;
+    use chrono::NaiveDate;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    #[test]
+    fn writes_each_day_with_its_tasks_and_journal() {
+        let mut milk = Task::new(date(1), "", "Buy milk");
+        milk.done = true;
+        milk.tags = vec!["home".into()];
+        milk.recurrence = Some(Recurrence::Daily);
+        let docs = Task::new(date(2), "09:00", "Write docs");
+
+        let mut journal = Journal::new();
+        journal.insert(date(2), "Went well.\nSlept badly.\n\n".into());
+        // A blank entry adds no day.
+        journal.insert(date(3), "   ".into());
+
+        let expected = "\
+# Task Planner
+
+## 2024-05-01 (Wednesday)
+
+- [x] Buy milk #home (every day)
+
+## 2024-05-02 (Thursday)
+
+- [ ] 09:00 Write docs
+
+### Journal
+
+Went well.
+Slept badly.
+";
+        assert_eq!(to_markdown(&[docs, milk], &journal), expected);
+    }
+
+    #[test]
+    fn an_empty_planner_has_only_the_title() {
+        assert_eq!(to_markdown(&[], &Journal::new()), "# Task Planner\n");
+    }
+}
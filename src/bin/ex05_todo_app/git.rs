@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+
+use crate::store::Snapshot;
+use crate::task::Task;
+
+pub const REMOTE_ENV: &str = "EX05_GIT_REMOTE";
+const DEFAULT_REMOTE: &str = "origin";
+const IGNORED: &str = "store.lock\n*.tmp\nplanner.md\nbackups/\n";
+
+// The data directory as a git repository. It is used only when the
+// directory already is one (`git init` in it turns the feature on); the
+// remote is `$EX05_GIT_REMOTE`, falling back to `origin`.
+#[derive(Clone)]
+pub struct Repo {
+    dir: PathBuf,
+    remote: String,
+    anonymous: bool,
+}
+
+// Where the local branch stands relative to the fetched remote one.
+pub enum Divergence {
+    UpToDate,
+    Ahead,
+    Behind,
+    Diverged { base: Option<String> },
+    NoRemoteBranch,
+}
+
+impl Repo {
+    pub fn open(dir: &Path) -> Option<Self> {
+        if !dir.join(".git").exists() {
+            return None;
+        }
+
+        let remote = std::env::var(REMOTE_ENV).unwrap_or_else(|_| DEFAULT_REMOTE.to_string());
+        let mut repo = Self { dir: dir.to_path_buf(), remote, anonymous: false };
+        repo.anonymous = repo.git(&["config", "user.email"]).is_err();
+        Some(repo)
+    }
+
+    pub fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    // Commits the store files if they changed; returns whether a commit
+    // was made.
+    pub fn commit(&self, message: &str) -> Result<bool> {
+        let ignore = self.dir.join(".gitignore");
+        if !ignore.exists() {
+            fs::write(&ignore, IGNORED).wrap_err_with(|| format!("writing {}", ignore.display()))?;
+        }
+
+        // A merge in progress is committed even when our files won.
+        let merging = self.dir.join(".git").join("MERGE_HEAD").exists();
+
+        self.git(&["add", "--all"])?;
+        if !merging && self.git(&["diff", "--cached", "--quiet"]).is_ok() {
+            return Ok(false);
+        }
+
+        self.git(&["commit", "--quiet", "-m", message])?;
+        Ok(true)
+    }
+
+    // The remote-tracking branch that sync merges with, e.g. `origin/main`.
+    pub fn upstream(&self) -> Result<String> {
+        Ok(format!("{}/{}", self.remote, self.branch()?))
+    }
+
+    pub fn fetch(&self) -> Result<()> {
+        self.git(&["fetch", "--quiet", &self.remote]).map(|_| ())
+    }
+
+    // Compares the local branch with the last fetched remote one, without
+    // touching the network.
+    pub fn divergence(&self) -> Result<Divergence> {
+        let upstream = self.upstream()?;
+        if self.git(&["rev-parse", "--verify", "--quiet", &upstream]).is_err() {
+            return Ok(Divergence::NoRemoteBranch);
+        }
+
+        // A fresh `git init` has no commit yet and simply takes the remote.
+        let Ok(head) = self.git(&["rev-parse", "--verify", "--quiet", "HEAD"]) else {
+            return Ok(Divergence::Behind);
+        };
+        let theirs = self.git(&["rev-parse", &upstream])?;
+        let base = self.git(&["merge-base", "HEAD", &upstream]).ok();
+
+        Ok(match base {
+            _ if head == theirs => Divergence::UpToDate,
+            Some(base) if base == theirs => Divergence::Ahead,
+            Some(base) if base == head => Divergence::Behind,
+            base => Divergence::Diverged { base },
+        })
+    }
+
+    pub fn fast_forward(&self) -> Result<()> {
+        self.git(&["merge", "--quiet", "--ff-only", &self.upstream()?]).map(|_| ())
+    }
+
+    // Starts a merge commit with the upstream branch that keeps our files;
+    // the caller writes the task-by-task merge result before `commit`.
+    pub fn begin_merge(&self) -> Result<()> {
+        let upstream = self.upstream()?;
+        self.git(&["merge", "--quiet", "--no-ff", "--no-commit", "--allow-unrelated-histories", "-s", "ours", &upstream])
+            .map(|_| ())
+    }
+
+    // Contents of a store file at `rev`, empty when it did not exist yet.
+    // A bad revision or a failing `git show` is an error, not an empty
+    // store that would drop every task from the merge.
+    pub fn show(&self, rev: &str, file: &Path) -> Result<String> {
+        let name = file.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let object = format!("{rev}:{name}");
+
+        self.git(&["rev-parse", "--verify", &format!("{rev}^{{commit}}")])?;
+        if self.git(&["cat-file", "-e", &object]).is_err() {
+            return Ok(String::new());
+        }
+        self.git(&["show", &object])
+    }
+
+    pub fn push(&self) -> Result<()> {
+        self.git(&["push", "--quiet", &self.remote, &self.branch()?]).map(|_| ())
+    }
+
+    fn branch(&self) -> Result<String> {
+        self.git(&["symbolic-ref", "--short", "HEAD"])
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.dir);
+
+        // Without a configured identity git refuses to commit; the planner
+        // still keeps its history under a placeholder name.
+        if self.anonymous {
+            command.args(["-c", "user.name=ex05 planner", "-c", "user.email=ex05@localhost"]);
+        }
+
+        let output = command
+            .args(args)
+            .output()
+            .wrap_err("running git")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(eyre!("git {}: {}", args[0], stderr.trim()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+// "Complete \"Write docs\"", or the first change and a count with every
+// change listed in the body.
+pub fn commit_message(before: &Snapshot, after: &Snapshot) -> String {
+    let find = |tasks: &[Task], id: &str| tasks.iter().find(|t| t.id == id).cloned();
+    let mut changes = vec![];
+
+    for task in after.tasks {
+        match find(before.tasks, &task.id) {
+            None if find(before.archive, &task.id).is_some() => changes.push(format!("Restore \"{}\"", task.text)),
+            None => changes.push(format!("Add \"{}\"", task.text)),
+            Some(old) if old.done != task.done && task.done => changes.push(format!("Complete \"{}\"", task.text)),
+            Some(old) if old.done != task.done => changes.push(format!("Reopen \"{}\"", task.text)),
+            Some(old) if old != *task => changes.push(format!("Edit \"{}\"", task.text)),
+            Some(_) => {}
+        }
+    }
+
+    for task in before.tasks.iter().filter(|t| find(after.tasks, &t.id).is_none()) {
+        match find(after.archive, &task.id) {
+            Some(_) => changes.push(format!("Archive \"{}\"", task.text)),
+            None => changes.push(format!("Delete \"{}\"", task.text)),
+        }
+    }
+
+    for task in before.archive.iter().filter(|t| find(after.archive, &t.id).is_none() && find(after.tasks, &t.id).is_none()) {
+        changes.push(format!("Purge \"{}\"", task.text));
+    }
+
+    for (date, text) in after.journal {
+        if before.journal.get(date) != Some(text) {
+            changes.push(format!("Update journal for {date}"));
+        }
+    }
+    for date in before.journal.keys().filter(|date| !after.journal.contains_key(date)) {
+        changes.push(format!("Remove journal for {date}"));
+    }
+
+    match changes.len() {
+        0 => "Update planner".into(),
+        1 => changes.remove(0),
+        n => format!("{} and {} more\n\n{}", changes[0], n - 1, changes.join("\n")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn show_is_empty_only_for_a_missing_file() {
+        let dir = std::env::temp_dir().join(format!("ex05-show-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let status = Command::new("git").args(["init", "--quiet"]).arg(&dir).status().unwrap();
+        assert!(status.success());
+
+        let repo = Repo { anonymous: true, ..Repo::open(&dir).unwrap() };
+        fs::write(dir.join("tasks.jsonl"), "first\n").unwrap();
+        assert!(repo.commit("first").unwrap());
+
+        assert_eq!(repo.show("HEAD", &dir.join("tasks.jsonl")).unwrap(), "first");
+        assert_eq!(repo.show("HEAD", &dir.join("archive.jsonl")).unwrap(), "");
+        assert!(repo.show("no-such-rev", &dir.join("tasks.jsonl")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
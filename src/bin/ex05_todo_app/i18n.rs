@@ -0,0 +1,547 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+use chrono::Weekday;
+use serde::Deserialize;
+
+use crate::task::{Priority, Recurrence};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "id")]
+    Indonesian,
+}
+
+// Every UI string of the planner by key. `{}` marks where `format` puts
+// its arguments, in order.
+const ENGLISH: &[(&str, &str)] = &[
+    // Day view
+    ("planner.title", "TASK PLANNER"),
+    ("day.today", "Today"),
+    ("day.yesterday", "Yesterday"),
+    ("day.tomorrow", "Tomorrow"),
+    ("day.selected", "Selected Day"),
+    ("day.previous", "◀ Previous "),
+    ("day.next", "Next ▶"),
+    ("day.hint_list", "Press n to add new task, a for quick add"),
+    ("day.hint_timeline", "Press Enter on a free slot to add a task there"),
+    ("day.journal", " Journal "),
+    ("day.conflict", "⚠ conflict"),
+    ("day.day_off", "day off"),
+    ("timeline.now", "── now {} "),
+    ("timeline.free", "▸ free · Enter to add at {}"),
+    // Footer keys
+    ("key.new", "New"),
+    ("key.quick_add", "Quick add"),
+    ("key.timeline", "Timeline"),
+    ("key.journal", "Journal"),
+    ("key.archive", "Archive"),
+    ("key.free_slot", "Free slot"),
+    ("key.list", "List"),
+    ("key.change_day", "Change day"),
+    ("key.change_week", "Change week"),
+    ("key.week", "Week"),
+    ("key.month", "Month"),
+    ("key.search", "Search"),
+    ("key.history", "History"),
+    ("key.help", "Help"),
+    ("key.quit", "Quit"),
+    ("key.next", "Next"),
+    ("key.save", "Save"),
+    ("key.cancel", "Cancel"),
+    ("key.new_line", "New line"),
+    ("key.save_close", "Save & close"),
+    ("key.select", "Select"),
+    ("key.go_to_day", "Go to day"),
+    ("key.back_to_planner", "Back to planner"),
+    ("key.back", "Back"),
+    ("key.mark", "Mark"),
+    ("key.include_archived", "Include archived"),
+    ("key.hide_archived", "Hide archived"),
+    ("key.keep_mine", "Keep mine"),
+    ("key.take_theirs", "Take theirs"),
+    ("key.later", "Later"),
+    ("key.apply", "Apply"),
+    ("key.unlock", "Unlock"),
+    ("key.archive_now", "Archive now"),
+    ("key.restore", "Restore"),
+    ("key.purge", "Purge"),
+    ("key.purge_all", "Purge all"),
+    ("key.undo", "Undo"),
+    ("marked", "{} marked"),
+    // Task fields
+    ("field.date", "Date"),
+    ("field.time", "Time"),
+    ("field.title", "Title"),
+    ("field.duration", "Duration"),
+    ("field.tags", "Tags"),
+    ("field.priority", "Priority"),
+    ("field.repeat", "Repeat"),
+    ("field.task", "Task"),
+    ("field.done", "Done"),
+    ("field.project", "Project"),
+    ("field.due", "Due"),
+    ("field.notes", "Notes"),
+    ("field.zone", "Zone"),
+    ("zone.floating", "floating"),
+    ("field.passphrase", "Passphrase"),
+    ("field.current", "Current"),
+    ("field.new", "New"),
+    ("field.confirm", "Confirm"),
+    ("minutes", "{} min"),
+    ("yes", "yes"),
+    ("no", "no"),
+    ("task_count", "{} task(s)"),
+    ("priority.low", "low"),
+    ("priority.normal", "normal"),
+    ("priority.high", "high"),
+    ("repeat.daily", "every day"),
+    ("repeat.weekdays", "every weekday"),
+    ("repeat.weekly", "every week"),
+    ("repeat.monthly", "every month"),
+    ("repeat.on", "every {}"),
+    ("weekday.mon", "Monday"),
+    ("weekday.tue", "Tuesday"),
+    ("weekday.wed", "Wednesday"),
+    ("weekday.thu", "Thursday"),
+    ("weekday.fri", "Friday"),
+    ("weekday.sat", "Saturday"),
+    ("weekday.sun", "Sunday"),
+    ("month.jan", "January"),
+    ("month.feb", "February"),
+    ("month.mar", "March"),
+    ("month.apr", "April"),
+    ("month.may", "May"),
+    ("month.jun", "June"),
+    ("month.jul", "July"),
+    ("month.aug", "August"),
+    ("month.sep", "September"),
+    ("month.oct", "October"),
+    ("month.nov", "November"),
+    ("month.dec", "December"),
+    // Pages
+    ("input.title", "NEW TASKS PLANNER"),
+    ("input.subtitle", "Enter task details below"),
+    ("quick_add.title", "QUICK ADD"),
+    ("quick_add.example", "e.g. tomorrow 14:00 review PR #work !high"),
+    ("quick_add.unparsed", "Highlighted words could not be understood and stay in the title"),
+    ("journal.title", "JOURNAL"),
+    ("search.title", "SEARCH"),
+    ("search.subtitle", "Tasks and journal entries"),
+    ("week.title", "WEEK"),
+    ("month.title", "MONTH"),
+    ("history.title", "HISTORY"),
+    ("history.subtitle", "Completed & past tasks"),
+    ("history.subtitle_archived", "Completed & past tasks, including archived"),
+    ("history.archived", "  (archived)"),
+    ("conflict.title", "CONFLICT · 1 of {}"),
+    ("conflict.subtitle", "This task was changed here and in {}"),
+    ("conflict.journal_subtitle", "The journal for {} was changed here and in {}"),
+    ("conflict.deleted", "(deleted)"),
+    ("conflict.this_window", "This window"),
+    ("conflict.another_window", "another window"),
+    ("prompt.title", "{} · {} task(s)"),
+    ("prompt.reschedule", "RESCHEDULE"),
+    ("prompt.reschedule_hint", "tomorrow, next monday, in 3 days, 2026-03-01"),
+    ("prompt.retag", "RETAG"),
+    ("prompt.retag_hint", "#tag or +tag adds, -tag removes"),
+    ("prompt.project", "MOVE TO PROJECT"),
+    ("prompt.project_hint", "project name, empty to clear"),
+    ("unlock.title", "UNLOCK PLANNER"),
+    ("unlock.subtitle", "{} is encrypted"),
+    ("passphrase.title", "PASSPHRASE"),
+    ("passphrase.hint_encrypted", "Leave the new passphrase empty to store tasks unencrypted"),
+    ("passphrase.hint_plain", "Tasks are stored unencrypted; choose a passphrase to encrypt them"),
+    ("archive.title", "ARCHIVE"),
+    ("archive.subtitle", "{} task(s) · done tasks older than {} day(s) are archived with A"),
+    ("archive.confirm_one", "Permanently delete the selected task? y to confirm, any other key to cancel"),
+    ("archive.confirm_all", "Permanently delete all {} archived task(s)? y to confirm, any other key to cancel"),
+    ("archive.file", "Archive file: {}"),
+    ("backups.title", "BACKUPS"),
+    ("backups.subtitle", "{} backup(s) in {} · the last {} saves are kept"),
+    ("backups.none", "No backups yet"),
+    ("backups.restoring", "Restoring would"),
+    ("backups.bring_back", "+ bring back"),
+    ("backups.remove", "- remove"),
+    ("backups.change", "~ change"),
+    ("backups.journal", "~ journal     {} day(s) differ"),
+    ("backups.archive", "~ archive     {} task(s) differ"),
+    ("backups.unreadable", "unreadable"),
+    ("backups.same", "nothing: it matches the planner"),
+    ("help.title", "HELP"),
+    ("help.change_day", "Change day"),
+    ("help.move_selection", "Move selection"),
+    ("help.toggle_done", "Toggle done"),
+    ("help.mark", "Mark task, V marks a range"),
+    ("help.bulk", "Delete, r reschedule, # retag, p move to project"),
+    ("help.bulk_targets", "(marked tasks, or the selected one)"),
+    ("help.undo", "Undo"),
+    ("help.reorder", "Move task up/down (also Shift+↑/↓)"),
+    ("help.new", "New task"),
+    ("help.timeline", "Switch between list and timeline"),
+    ("help.free_slot", "Move to a free slot (timeline)"),
+    ("help.journal", "Journal for the day"),
+    ("help.search", "Search tasks and journal"),
+    ("help.export", "Export to Markdown"),
+    ("help.sync", "Sync with the git remote"),
+    ("help.conflicts", "Resolve the conflicts left for later"),
+    ("help.encrypt", "Encrypt the store or change its passphrase"),
+    ("help.backups", "Backups (Enter restores)"),
+    ("help.archive", "Archive (r restore, p purge)"),
+    ("help.add_at_slot", "Add a task at the free slot (timeline)"),
+    ("help.quick_add", "Quick add (e.g. \"tomorrow 14:00 review PR #work !high\")"),
+    ("help.history", "History"),
+    ("help.week", "Week, with holidays"),
+    ("help.month", "Month, with holidays"),
+    ("help.quit", "Quit"),
+    // Status messages
+    ("status.save_failed", "Save failed: {}"),
+    ("status.not_git", "{} is not a git repository; git init there to enable sync"),
+    ("status.sync_failed", "Sync failed: {}"),
+    ("status.up_to_date", "Already up to date"),
+    ("status.pushed", "Pushed to {}"),
+    ("status.pulled", "Pulled changes from {}"),
+    ("status.merged", "Merged with {} and pushed"),
+    ("status.resolve_to_sync", "Resolve the conflicts to finish the sync"),
+    ("status.conflicts_left", "{} conflict(s) left for later, C resolves them"),
+    ("status.loaded_external", "Loaded changes from another window"),
+    ("status.cant_unlock", "Can't unlock: {}"),
+    ("status.passphrase_wrong", "The current passphrase is wrong"),
+    ("status.passphrase_mismatch", "The new passphrases don't match"),
+    ("status.decrypted", "The store is no longer encrypted"),
+    ("status.encrypted", "The store is encrypted with the new passphrase"),
+    ("status.cant_encrypt", "Can't encrypt: {}"),
+    ("status.passphrase_needed", "Enter a passphrase to encrypt the store"),
+    ("status.exported", "Exported to {}"),
+    ("status.export_failed", "Export failed: {}"),
+    ("status.ordered_by_time", "Tasks with a time are ordered by it"),
+    ("status.archived_unmarkable", "Archived tasks can't be marked"),
+    ("status.undone", "Undone"),
+    ("status.nothing_to_undo", "Nothing to undo"),
+    ("status.bulk_updated", "Updated {} task(s) · u to undo"),
+    ("status.bulk_deleted", "Deleted {} task(s) · u to undo"),
+    ("status.bulk_rescheduled", "Rescheduled {} task(s) · u to undo"),
+    ("status.bulk_retagged", "Retagged {} task(s) · u to undo"),
+    ("status.bulk_moved", "Moved {} task(s) · u to undo"),
+    ("status.not_a_date", "Not a date: \"{}\""),
+    ("status.archived", "Archived {} task(s) completed more than {} day(s) ago"),
+    ("status.restored", "Restored \"{}\""),
+    ("status.purged", "Purged \"{}\""),
+    ("status.purged_all", "Purged {} archived task(s)"),
+    ("status.purge_cancelled", "Purge cancelled"),
+    ("status.backups_failed", "Reading backups failed: {}"),
+    ("status.backup_restored", "Restored the backup from {} · u to undo"),
+    ("status.backup_unreadable", "This backup can't be read: {}"),
+];
+
+const INDONESIAN: &[(&str, &str)] = &[
+    // Day view
+    ("planner.title", "PERENCANA TUGAS"),
+    ("day.today", "Hari ini"),
+    ("day.yesterday", "Kemarin"),
+    ("day.tomorrow", "Besok"),
+    ("day.selected", "Hari terpilih"),
+    ("day.previous", "◀ Sebelumnya "),
+    ("day.next", "Berikutnya ▶"),
+    ("day.hint_list", "Tekan n untuk tugas baru, a untuk tambah cepat"),
+    ("day.hint_timeline", "Tekan Enter di slot kosong untuk menambah tugas di sana"),
+    ("day.journal", " Jurnal "),
+    ("day.conflict", "⚠ bentrok"),
+    ("day.day_off", "hari libur"),
+    ("timeline.now", "── sekarang {} "),
+    ("timeline.free", "▸ kosong · Enter untuk menambah pada {}"),
+    // Footer keys
+    ("key.new", "Baru"),
+    ("key.quick_add", "Tambah cepat"),
+    ("key.timeline", "Linimasa"),
+    ("key.journal", "Jurnal"),
+    ("key.archive", "Arsip"),
+    ("key.free_slot", "Slot kosong"),
+    ("key.list", "Daftar"),
+    ("key.change_day", "Ganti hari"),
+    ("key.change_week", "Ganti minggu"),
+    ("key.week", "Minggu"),
+    ("key.month", "Bulan"),
+    ("key.search", "Cari"),
+    ("key.history", "Riwayat"),
+    ("key.help", "Bantuan"),
+    ("key.quit", "Keluar"),
+    ("key.next", "Berikutnya"),
+    ("key.save", "Simpan"),
+    ("key.cancel", "Batal"),
+    ("key.new_line", "Baris baru"),
+    ("key.save_close", "Simpan & tutup"),
+    ("key.select", "Pilih"),
+    ("key.go_to_day", "Ke hari itu"),
+    ("key.back_to_planner", "Kembali ke perencana"),
+    ("key.back", "Kembali"),
+    ("key.mark", "Tandai"),
+    ("key.include_archived", "Sertakan arsip"),
+    ("key.hide_archived", "Sembunyikan arsip"),
+    ("key.keep_mine", "Pakai milik saya"),
+    ("key.take_theirs", "Pakai yang lain"),
+    ("key.later", "Nanti"),
+    ("key.apply", "Terapkan"),
+    ("key.unlock", "Buka"),
+    ("key.archive_now", "Arsipkan sekarang"),
+    ("key.restore", "Pulihkan"),
+    ("key.purge", "Hapus permanen"),
+    ("key.purge_all", "Hapus semua"),
+    ("key.undo", "Batalkan"),
+    ("marked", "{} ditandai"),
+    // Task fields
+    ("field.date", "Tanggal"),
+    ("field.time", "Jam"),
+    ("field.title", "Judul"),
+    ("field.duration", "Durasi"),
+    ("field.tags", "Tag"),
+    ("field.priority", "Prioritas"),
+    ("field.repeat", "Ulangi"),
+    ("field.task", "Tugas"),
+    ("field.done", "Selesai"),
+    ("field.project", "Proyek"),
+    ("field.due", "Tenggat"),
+    ("field.notes", "Catatan"),
+    ("field.zone", "Zona"),
+    ("zone.floating", "mengambang"),
+    ("field.passphrase", "Frasa sandi"),
+    ("field.current", "Saat ini"),
+    ("field.new", "Baru"),
+    ("field.confirm", "Konfirmasi"),
+    ("minutes", "{} mnt"),
+    ("yes", "ya"),
+    ("no", "tidak"),
+    ("task_count", "{} tugas"),
+    ("priority.low", "rendah"),
+    ("priority.normal", "normal"),
+    ("priority.high", "tinggi"),
+    ("repeat.daily", "setiap hari"),
+    ("repeat.weekdays", "setiap hari kerja"),
+    ("repeat.weekly", "setiap minggu"),
+    ("repeat.monthly", "setiap bulan"),
+    ("repeat.on", "setiap {}"),
+    ("weekday.mon", "Senin"),
+    ("weekday.tue", "Selasa"),
+    ("weekday.wed", "Rabu"),
+    ("weekday.thu", "Kamis"),
+    ("weekday.fri", "Jumat"),
+    ("weekday.sat", "Sabtu"),
+    ("weekday.sun", "Minggu"),
+    ("month.jan", "Januari"),
+    ("month.feb", "Februari"),
+    ("month.mar", "Maret"),
+    ("month.apr", "April"),
+    ("month.may", "Mei"),
+    ("month.jun", "Juni"),
+    ("month.jul", "Juli"),
+    ("month.aug", "Agustus"),
+    ("month.sep", "September"),
+    ("month.oct", "Oktober"),
+    ("month.nov", "November"),
+    ("month.dec", "Desember"),
+    // Pages
+    ("input.title", "TUGAS BARU"),
+    ("input.subtitle", "Isi detail tugas di bawah ini"),
+    ("quick_add.title", "TAMBAH CEPAT"),
+    ("quick_add.example", "mis. tomorrow 14:00 review PR #work !high"),
+    ("quick_add.unparsed", "Kata yang disorot tidak dipahami dan tetap menjadi bagian judul"),
+    ("journal.title", "JURNAL"),
+    ("search.title", "CARI"),
+    ("search.subtitle", "Tugas dan catatan jurnal"),
+    ("week.title", "MINGGU"),
+    ("month.title", "BULAN"),
+    ("history.title", "RIWAYAT"),
+    ("history.subtitle", "Tugas selesai & yang sudah lewat"),
+    ("history.subtitle_archived", "Tugas selesai & yang sudah lewat, termasuk arsip"),
+    ("history.archived", "  (diarsipkan)"),
+    ("conflict.title", "KONFLIK · 1 dari {}"),
+    ("conflict.subtitle", "Tugas ini diubah di sini dan di {}"),
+    ("conflict.journal_subtitle", "Jurnal {} diubah di sini dan di {}"),
+    ("conflict.deleted", "(dihapus)"),
+    ("conflict.this_window", "Jendela ini"),
+    ("conflict.another_window", "jendela lain"),
+    ("prompt.title", "{} · {} tugas"),
+    ("prompt.reschedule", "JADWALKAN ULANG"),
+    ("prompt.reschedule_hint", "tomorrow, next monday, in 3 days, 2026-03-01"),
+    ("prompt.retag", "UBAH TAG"),
+    ("prompt.retag_hint", "#tag atau +tag menambah, -tag menghapus"),
+    ("prompt.project", "PINDAH KE PROYEK"),
+    ("prompt.project_hint", "nama proyek, kosongkan untuk menghapus"),
+    ("unlock.title", "BUKA PERENCANA"),
+    ("unlock.subtitle", "{} terenkripsi"),
+    ("passphrase.title", "FRASA SANDI"),
+    ("passphrase.hint_encrypted", "Kosongkan frasa sandi baru untuk menyimpan tugas tanpa enkripsi"),
+    ("passphrase.hint_plain", "Tugas disimpan tanpa enkripsi; pilih frasa sandi untuk mengenkripsinya"),
+    ("archive.title", "ARSIP"),
+    ("archive.subtitle", "{} tugas · tugas selesai yang lebih lama dari {} hari diarsipkan dengan A"),
+    ("archive.confirm_one", "Hapus permanen tugas terpilih? y untuk konfirmasi, tombol lain untuk batal"),
+    ("archive.confirm_all", "Hapus permanen semua {} tugas arsip? y untuk konfirmasi, tombol lain untuk batal"),
+    ("archive.file", "Berkas arsip: {}"),
+    ("backups.title", "CADANGAN"),
+    ("backups.subtitle", "{} cadangan di {} · {} penyimpanan terakhir disimpan"),
+    ("backups.none", "Belum ada cadangan"),
+    ("backups.restoring", "Memulihkan akan"),
+    ("backups.bring_back", "+ kembalikan"),
+    ("backups.remove", "- hapus"),
+    ("backups.change", "~ ubah"),
+    ("backups.journal", "~ jurnal      {} hari berbeda"),
+    ("backups.archive", "~ arsip       {} tugas berbeda"),
+    ("backups.unreadable", "tidak terbaca"),
+    ("backups.same", "tidak ada: sama dengan perencana"),
+    ("help.title", "BANTUAN"),
+    ("help.change_day", "Ganti hari"),
+    ("help.move_selection", "Pindahkan pilihan"),
+    ("help.toggle_done", "Tandai selesai/belum"),
+    ("help.mark", "Tandai tugas, V menandai rentang"),
+    ("help.bulk", "Hapus, r jadwalkan ulang, # ubah tag, p pindah ke proyek"),
+    ("help.bulk_targets", "(tugas yang ditandai, atau yang terpilih)"),
+    ("help.undo", "Batalkan"),
+    ("help.reorder", "Geser tugas ke atas/bawah (juga Shift+↑/↓)"),
+    ("help.new", "Tugas baru"),
+    ("help.timeline", "Beralih antara daftar dan linimasa"),
+    ("help.free_slot", "Pindah ke slot kosong (linimasa)"),
+    ("help.journal", "Jurnal hari ini"),
+    ("help.search", "Cari tugas dan jurnal"),
+    ("help.export", "Ekspor ke Markdown"),
+    ("help.sync", "Sinkronkan dengan remote git"),
+    ("help.conflicts", "Selesaikan konflik yang ditunda"),
+    ("help.encrypt", "Enkripsi penyimpanan atau ganti frasa sandinya"),
+    ("help.backups", "Cadangan (Enter memulihkan)"),
+    ("help.archive", "Arsip (r pulihkan, p hapus permanen)"),
+    ("help.add_at_slot", "Tambah tugas di slot kosong (linimasa)"),
+    ("help.quick_add", "Tambah cepat (mis. \"tomorrow 14:00 review PR #work !high\")"),
+    ("help.history", "Riwayat"),
+    ("help.week", "Minggu, dengan hari libur"),
+    ("help.month", "Bulan, dengan hari libur"),
+    ("help.quit", "Keluar"),
+    // Status messages
+    ("status.save_failed", "Gagal menyimpan: {}"),
+    ("status.not_git", "{} bukan repositori git; jalankan git init di sana untuk sinkronisasi"),
+    ("status.sync_failed", "Sinkronisasi gagal: {}"),
+    ("status.up_to_date", "Sudah yang terbaru"),
+    ("status.pushed", "Dikirim ke {}"),
+    ("status.pulled", "Perubahan diambil dari {}"),
+    ("status.merged", "Digabung dengan {} dan dikirim"),
+    ("status.resolve_to_sync", "Selesaikan konflik untuk menuntaskan sinkronisasi"),
+    ("status.conflicts_left", "{} konflik ditunda, C untuk menyelesaikannya"),
+    ("status.loaded_external", "Perubahan dari jendela lain dimuat"),
+    ("status.cant_unlock", "Tidak bisa membuka: {}"),
+    ("status.passphrase_wrong", "Frasa sandi saat ini salah"),
+    ("status.passphrase_mismatch", "Frasa sandi baru tidak cocok"),
+    ("status.decrypted", "Penyimpanan tidak lagi terenkripsi"),
+    ("status.encrypted", "Penyimpanan dienkripsi dengan frasa sandi baru"),
+    ("status.cant_encrypt", "Tidak bisa mengenkripsi: {}"),
+    ("status.passphrase_needed", "Masukkan frasa sandi untuk mengenkripsi penyimpanan"),
+    ("status.exported", "Diekspor ke {}"),
+    ("status.export_failed", "Ekspor gagal: {}"),
+    ("status.ordered_by_time", "Tugas berjam diurutkan menurut jamnya"),
+    ("status.archived_unmarkable", "Tugas arsip tidak bisa ditandai"),
+    ("status.undone", "Dibatalkan"),
+    ("status.nothing_to_undo", "Tidak ada yang bisa dibatalkan"),
+    ("status.bulk_updated", "{} tugas diperbarui · u untuk membatalkan"),
+    ("status.bulk_deleted", "{} tugas dihapus · u untuk membatalkan"),
+    ("status.bulk_rescheduled", "{} tugas dijadwalkan ulang · u untuk membatalkan"),
+    ("status.bulk_retagged", "Tag {} tugas diubah · u untuk membatalkan"),
+    ("status.bulk_moved", "{} tugas dipindahkan · u untuk membatalkan"),
+    ("status.not_a_date", "Bukan tanggal: \"{}\""),
+    ("status.archived", "{} tugas yang selesai lebih dari {} hari lalu diarsipkan"),
+    ("status.restored", "\"{}\" dipulihkan"),
+    ("status.purged", "\"{}\" dihapus permanen"),
+    ("status.purged_all", "{} tugas arsip dihapus permanen"),
+    ("status.purge_cancelled", "Penghapusan dibatalkan"),
+    ("status.backups_failed", "Gagal membaca cadangan: {}"),
+    ("status.backup_restored", "Cadangan dari {} dipulihkan · u untuk membatalkan"),
+    ("status.backup_unreadable", "Cadangan ini tidak bisa dibaca: {}"),
+];
+
+pub fn is_key(key: &str) -> bool {
+    ENGLISH.iter().any(|(known, _)| *known == key)
+}
+
+// The UI strings for one language, with the labels from the config file
+// laid over them.
+pub struct Texts {
+    entries: HashMap<String, String>,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self::new(Language::English, &BTreeMap::new())
+    }
+}
+
+impl Texts {
+    pub fn new(language: Language, labels: &BTreeMap<String, String>) -> Self {
+        let translation = match language {
+            Language::English => ENGLISH,
+            Language::Indonesian => INDONESIAN,
+        };
+
+        let entries = ENGLISH
+            .iter()
+            .chain(translation)
+            .map(|(key, text)| (key.to_string(), text.to_string()))
+            .chain(labels.iter().map(|(key, text)| (key.clone(), text.clone())))
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.entries.get(key).map_or(key, String::as_str)
+    }
+
+    pub fn format(&self, key: &str, args: &[&dyn Display]) -> String {
+        let mut parts = self.get(key).split("{}");
+        let mut out = parts.next().unwrap_or_default().to_string();
+        for (i, part) in parts.enumerate() {
+            if let Some(arg) = args.get(i) {
+                out.push_str(&arg.to_string());
+            }
+            out.push_str(part);
+        }
+        out
+    }
+
+    pub fn weekday(&self, day: Weekday) -> &str {
+        self.get(match day {
+            Weekday::Mon => "weekday.mon",
+            Weekday::Tue => "weekday.tue",
+            Weekday::Wed => "weekday.wed",
+            Weekday::Thu => "weekday.thu",
+            Weekday::Fri => "weekday.fri",
+            Weekday::Sat => "weekday.sat",
+            Weekday::Sun => "weekday.sun",
+        })
+    }
+
+    // `month` counts from 1, as chrono's `Datelike::month` does.
+    pub fn month(&self, month: u32) -> &str {
+        const KEYS: [&str; 12] = [
+            "month.jan", "month.feb", "month.mar", "month.apr", "month.may", "month.jun",
+            "month.jul", "month.aug", "month.sep", "month.oct", "month.nov", "month.dec",
+        ];
+        KEYS.get(month.wrapping_sub(1) as usize).map_or("", |key| self.get(key))
+    }
+
+    pub fn priority(&self, priority: Priority) -> &str {
+        self.get(match priority {
+            Priority::Low => "priority.low",
+            Priority::Normal => "priority.normal",
+            Priority::High => "priority.high",
+        })
+    }
+
+    pub fn recurrence(&self, recurrence: Recurrence) -> String {
+        match recurrence {
+            Recurrence::Daily => self.get("repeat.daily").into(),
+            Recurrence::Weekdays => self.get("repeat.weekdays").into(),
+            Recurrence::Weekly => self.get("repeat.weekly").into(),
+            Recurrence::Monthly => self.get("repeat.monthly").into(),
+            Recurrence::On(day) => self.format("repeat.on", &[&self.weekday(day).to_lowercase()]),
+        }
+    }
+}
@@ -0,0 +1,3365 @@
+mod archive;
+mod backup;
+mod bulk;
+mod calendar;
+mod clock;
+mod config;
+mod crypt;
+mod export;
+mod git;
+mod i18n;
+mod merge;
+mod overview;
+mod quick_add;
+mod search;
+mod store;
+mod task;
+mod taskwarrior;
+mod timeline;
+
+use chrono::{Datelike, Duration, NaiveDate, Timelike, Weekday};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+};
+use crossterm::event;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::layout::Position;
+use ratatui::Frame;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::io::{Read, Write};
+use std::{fs, io};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use rust_tui_lab::complete::{self, Popup, WordList};
+use rust_tui_lab::field::TextField;
+use rust_tui_lab::layout::centered_rect;
+use rust_tui_lab::mask::Mask;
+use rust_tui_lab::style::{default_style_text, panel_block_with_padding_borders, title_text};
+use rust_tui_lab::terminal::Session;
+use rust_tui_lab::textarea::TextArea;
+
+use git::{Divergence, Repo};
+use backup::{Backup, Change};
+use bulk::BulkOp;
+use calendar::Calendar;
+use clock::Clock;
+use config::Config;
+use crypt::Cipher;
+use i18n::Texts;
+use merge::Conflict;
+use quick_add::TokenKind;
+use search::HitKind;
+use store::{Journal, Snapshot, Stamp, Store};
+use task::Task;
+use timeline::TimeBlock;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Page{
+    Day,
+    Input,
+    QuickAdd,
+    Journal,
+    Search,
+    History,
+    Week,
+    Month,
+    Archive,
+    Prompt,
+    Conflict,
+    Unlock,
+    Passphrase,
+    Backups,
+    Help
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PromptKind {
+    Reschedule,
+    Retag,
+    Project,
+}
+
+const UNDO_LIMIT: usize = 100;
+
+// What `u` goes back to: the tasks and the archive together, since
+// archiving and restoring move tasks between them, and the journal, which
+// a backup restore or a resolved journal conflict replaces.
+struct UndoStep {
+    tasks: Vec<Task>,
+    archive: Vec<Task>,
+    journal: Journal,
+}
+// How often the store is checked for writes from another window.
+const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+enum Purge {
+    Selected,
+    All,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DayView {
+    List,
+    Timeline,
+}
+
+struct  InputBuffer {
+    date: NaiveDate,
+    time: String,
+    text: String,
+    focus: usize,
+}
+
+struct App {
+    page: Page,
+    day_offset: i32,
+    tasks: Vec<Task>,
+    list_state: ListState,
+    input_buffer: InputBuffer,
+    quick_add: TextField,
+    day_view: DayView,
+    timeline_cursor: usize,
+    timeline_scroll: usize,
+    journal: Journal,
+    // The day open in the journal editor.
+    journal_day: NaiveDate,
+    journal_editor: TextArea,
+    search_input: TextField,
+    search_state: ListState,
+    store: Store,
+    dirty: bool,
+    status: Option<String>,
+    archive: Vec<Task>,
+    archive_dirty: bool,
+    archive_state: ListState,
+    pending_purge: Option<Purge>,
+    history_include_archived: bool,
+    history_state: ListState,
+    marked: BTreeSet<String>,
+    visual_anchor: Option<usize>,
+    undo_stack: Vec<UndoStep>,
+    prompt: TextField,
+    prompt_kind: PromptKind,
+    prompt_return: Page,
+    // Tag suggestions under the quick add or retag input.
+    completion: Option<Popup>,
+    base_tasks: Vec<Task>,
+    base_journal: Journal,
+    base_archive: Vec<Task>,
+    stamp: Stamp,
+    conflicts: Vec<Conflict>,
+    conflict_return: Page,
+    conflict_source: String,
+    repo: Option<Repo>,
+    pending_push: bool,
+    unlock_input: TextField,
+    // Current, new and confirmation fields of the passphrase popup.
+    passphrase_inputs: [TextField; 3],
+    passphrase_focus: usize,
+    backups: Vec<Backup>,
+    backup_state: ListState,
+    config: Config,
+    texts: Texts,
+    clock: Clock,
+    calendar: Calendar,
+}
+
+impl App {
+    fn new() -> Self {
+        let clock = Clock::default();
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        Self {
+            page: Page::Day,
+            day_offset: 0,
+            tasks: vec![],
+            list_state,
+            input_buffer: InputBuffer {
+                date: clock.today(),
+                time: "09:00".into(),
+                text: String::new(),
+                focus: 0,
+            },
+            quick_add: TextField::new(),
+            day_view: DayView::List,
+            timeline_cursor: timeline::slot_for(9 * 60).unwrap_or(0),
+            timeline_scroll: 0,
+            journal: Journal::new(),
+            journal_day: NaiveDate::default(),
+            journal_editor: TextArea::new(),
+            search_input: TextField::new(),
+            search_state: ListState::default(),
+            store: Store::from_env(),
+            dirty: false,
+            status: None,
+            archive: vec![],
+            archive_dirty: false,
+            archive_state: ListState::default(),
+            pending_purge: None,
+            history_include_archived: false,
+            history_state: ListState::default(),
+            marked: BTreeSet::new(),
+            visual_anchor: None,
+            undo_stack: vec![],
+            prompt: TextField::new(),
+            prompt_kind: PromptKind::Reschedule,
+            prompt_return: Page::Day,
+            completion: None,
+            base_tasks: vec![],
+            base_journal: Journal::new(),
+            base_archive: vec![],
+            stamp: Stamp::default(),
+            conflicts: vec![],
+            conflict_return: Page::Day,
+            conflict_source: String::new(),
+            repo: None,
+            pending_push: false,
+            unlock_input: TextField::new().mask(Mask::Password),
+            passphrase_inputs: std::array::from_fn(|_| TextField::new().mask(Mask::Password)),
+            passphrase_focus: 0,
+            backups: vec![],
+            backup_state: ListState::default(),
+            config: Config::default(),
+            texts: Texts::default(),
+            clock,
+            calendar: Calendar::default(),
+        }
+    }
+
+    fn set_config(&mut self, config: Config) {
+        self.texts = Texts::new(config.language, &config.labels);
+        self.config = config;
+    }
+
+    fn tr(&self, key: &str) -> String {
+        self.texts.get(key).to_string()
+    }
+
+    fn tr_args(&self, key: &str, args: &[&dyn Display]) -> String {
+        self.texts.format(key, args)
+    }
+
+    fn format_date(&self, date: NaiveDate) -> String {
+        date.format(&self.config.date_format).to_string()
+    }
+
+    fn load(&mut self) -> Result<()> {
+        let _lock = self.store.lock()?;
+        self.repo = Repo::open(self.store.dir());
+        self.read_store()
+    }
+
+    // Replaces everything in memory with the files; the caller holds the lock.
+    fn read_store(&mut self) -> Result<()> {
+        (self.tasks, self.journal) = self.store.load()?;
+        self.archive = self.store.load_archive()?;
+        self.base_tasks = self.tasks.clone();
+        self.base_journal = self.journal.clone();
+        self.base_archive = self.archive.clone();
+        self.stamp = self.store.stamp();
+        Ok(())
+    }
+
+    // Writes the store after any change and picks up writes from other
+    // windows; failures are shown instead of tearing down the UI so
+    // nothing typed so far is lost.
+    fn save_if_dirty(&mut self) {
+        // Nothing is loaded until an encrypted store is unlocked.
+        if self.page == Page::Unlock {
+            return;
+        }
+
+        if let Err(err) = self.sync_store() {
+            self.status = Some(self.tr_args("status.save_failed", &[&err]));
+        }
+    }
+
+    fn sync_store(&mut self) -> Result<()> {
+        let _lock = self.store.lock()?;
+        self.sync_locked()
+    }
+
+    // `sync_store` for a caller that already holds the store lock.
+    fn sync_locked(&mut self) -> Result<()> {
+        if self.store.stamp() != self.stamp {
+            self.merge_external()?;
+        }
+
+        let message = (self.repo.is_some() && (self.dirty || self.archive_dirty)).then(|| {
+            git::commit_message(
+                &Snapshot { tasks: &self.base_tasks, archive: &self.base_archive, journal: &self.base_journal },
+                &Snapshot { tasks: &self.tasks, archive: &self.archive, journal: &self.journal },
+            )
+        });
+
+        // The backup is taken before either file is written, so the
+        // tasks and archive in it belong together.
+        if self.dirty || self.archive_dirty {
+            backup::create(&self.store, self.config.backups)?;
+        }
+
+        // The archive goes first: a task moved there must never exist
+        // only in memory.
+        if self.archive_dirty {
+            self.store.save_archive(&self.archive)?;
+            self.base_archive = self.archive.clone();
+            self.archive_dirty = false;
+        }
+
+        if self.dirty {
+            self.store.save(&self.tasks, &self.journal)?;
+            self.base_tasks = self.tasks.clone();
+            self.base_journal = self.journal.clone();
+            self.dirty = false;
+        }
+
+        if let (Some(repo), Some(message)) = (&self.repo, message) {
+            repo.commit(&message)?;
+        }
+
+        self.stamp = self.store.stamp();
+        Ok(())
+    }
+
+    // Pulls the remote into the store, merging task by task, and pushes
+    // the result. Tasks edited on both sides go to the conflict screen and
+    // the push waits until they are resolved.
+    fn sync_remote(&mut self) {
+        let Some(repo) = self.repo.clone() else {
+            self.status = Some(self.tr_args("status.not_git", &[&self.store.dir().display()]));
+            return;
+        };
+
+        let result = self.pull(&repo);
+        self.status = Some(match result {
+            Ok(status) => status,
+            Err(err) => self.tr_args("status.sync_failed", &[&err]),
+        });
+    }
+
+    // The network steps run without the store lock so other windows can
+    // keep saving meanwhile; the lock covers only the merge and the write.
+    fn pull(&mut self, repo: &Repo) -> Result<String> {
+        repo.fetch()?;
+
+        let lock = self.store.lock()?;
+        // Another window may have saved and committed during the fetch.
+        self.sync_locked()?;
+
+        let base = match repo.divergence()? {
+            Divergence::UpToDate => return Ok(self.tr("status.up_to_date")),
+            Divergence::Ahead | Divergence::NoRemoteBranch => {
+                drop(lock);
+                repo.push()?;
+                return Ok(self.tr_args("status.pushed", &[&repo.remote()]));
+            }
+            Divergence::Behind => {
+                repo.fast_forward()?;
+                self.read_store()?;
+                self.undo_stack.clear();
+                return Ok(self.tr_args("status.pulled", &[&repo.remote()]));
+            }
+            Divergence::Diverged { base } => base,
+        };
+
+        let upstream = repo.upstream()?;
+        let read = |rev: Option<&str>, path: &std::path::Path| -> Result<(Vec<Task>, Journal)> {
+            match rev {
+                Some(rev) => self.store.decode_file(&repo.show(rev, path)?),
+                None => Ok((vec![], Journal::new())),
+            }
+        };
+        let (base_tasks, base_journal) = read(base.as_deref(), &self.store.path())?;
+        let (their_tasks, their_journal) = read(Some(&upstream), &self.store.path())?;
+        let (base_archive, _) = read(base.as_deref(), &self.store.archive_path())?;
+        let (their_archive, _) = read(Some(&upstream), &self.store.archive_path())?;
+
+        repo.begin_merge()?;
+        let merged = merge::merge_store(
+            &Snapshot { tasks: &base_tasks, archive: &base_archive, journal: &base_journal },
+            &Snapshot { tasks: &self.tasks, archive: &self.archive, journal: &self.journal },
+            &Snapshot { tasks: &their_tasks, archive: &their_archive, journal: &their_journal },
+        );
+        self.tasks = merged.tasks;
+        self.journal = merged.journal;
+        self.archive = merged.archive;
+
+        self.store.save_archive(&self.archive)?;
+        self.store.save(&self.tasks, &self.journal)?;
+        repo.commit(&format!("Merge {upstream}"))?;
+
+        self.base_tasks = self.tasks.clone();
+        self.base_journal = self.journal.clone();
+        self.base_archive = self.archive.clone();
+        self.stamp = self.store.stamp();
+        self.undo_stack.clear();
+        drop(lock);
+
+        if merged.conflicts.is_empty() {
+            repo.push()?;
+            return Ok(self.tr_args("status.merged", &[&repo.remote()]));
+        }
+
+        self.show_conflicts(merged.conflicts, repo.remote().to_string());
+        self.pending_push = true;
+        Ok(self.tr("status.resolve_to_sync"))
+    }
+
+    fn show_conflicts(&mut self, conflicts: Vec<Conflict>, source: String) {
+        if self.page != Page::Conflict {
+            self.conflict_return = self.page;
+            self.page = Page::Conflict;
+        }
+        self.conflicts.extend(conflicts);
+        self.conflict_source = source;
+    }
+
+    // Folds a write from another window into memory, keeping local
+    // changes that have not been saved yet.
+    fn merge_external(&mut self) -> Result<()> {
+        let (theirs, their_journal) = self.store.load()?;
+        let their_archive = self.store.load_archive()?;
+
+        let merged = merge::merge_store(
+            &Snapshot { tasks: &self.base_tasks, archive: &self.base_archive, journal: &self.base_journal },
+            &Snapshot { tasks: &self.tasks, archive: &self.archive, journal: &self.journal },
+            &Snapshot { tasks: &theirs, archive: &their_archive, journal: &their_journal },
+        );
+
+        self.tasks = merged.tasks;
+        self.journal = merged.journal;
+        self.archive = merged.archive;
+        self.dirty = self.tasks != theirs || self.journal != their_journal;
+        self.archive_dirty = self.archive != their_archive;
+        self.base_tasks = theirs;
+        self.base_journal = their_journal;
+        self.base_archive = their_archive;
+
+        // Undoing past the reload would silently drop the other window's work.
+        self.undo_stack.clear();
+        self.status = Some(self.tr("status.loaded_external"));
+
+        if !merged.conflicts.is_empty() {
+            self.show_conflicts(merged.conflicts, self.tr("conflict.another_window"));
+        }
+
+        Ok(())
+    }
+
+    fn submit_unlock(&mut self) {
+        let result = self.store.unlock(&self.unlock_input.input).and_then(|_| self.load());
+        self.unlock_input.clear();
+
+        match result {
+            Ok(()) => self.page = Page::Day,
+            Err(err) => self.status = Some(self.tr_args("status.cant_unlock", &[&err])),
+        }
+    }
+
+    fn handle_unlock_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.submit_unlock(),
+            KeyCode::Char(c) => self.unlock_input.enter_char(c),
+            KeyCode::Backspace => self.unlock_input.delete_char(),
+            KeyCode::Left => self.unlock_input.move_cursor_left(),
+            KeyCode::Right => self.unlock_input.move_cursor_right(),
+            _ => {}
+        }
+    }
+
+    fn open_passphrase(&mut self) {
+        self.passphrase_inputs.iter_mut().for_each(TextField::clear);
+        // A plain store has no current passphrase to ask for.
+        self.passphrase_focus = if self.store.is_unlocked() { 0 } else { 1 };
+        self.page = Page::Passphrase;
+    }
+
+    // Sets, changes or (with an empty new passphrase) removes encryption;
+    // the next save rewrites every file in the new format.
+    fn submit_passphrase(&mut self) {
+        let [current, new, confirm] = &self.passphrase_inputs;
+
+        if let Some(cipher) = self.store.cipher()
+            && !cipher.matches(&current.input) {
+            self.status = Some(self.tr("status.passphrase_wrong"));
+            self.passphrase_focus = 0;
+            return;
+        }
+        if new.input != confirm.input {
+            self.status = Some(self.tr("status.passphrase_mismatch"));
+            self.passphrase_focus = 2;
+            return;
+        }
+
+        let (cipher, message) = if new.input.is_empty() {
+            (None, "status.decrypted")
+        } else {
+            match Cipher::new(&new.input) {
+                Ok(cipher) => (Some(cipher), "status.encrypted"),
+                Err(err) => {
+                    self.status = Some(self.tr_args("status.cant_encrypt", &[&err]));
+                    return;
+                }
+            }
+        };
+
+        if cipher.is_none() && !self.store.is_unlocked() {
+            self.status = Some(self.tr("status.passphrase_needed"));
+            return;
+        }
+
+        self.store.set_cipher(cipher);
+        self.dirty = true;
+        self.archive_dirty = true;
+        self.status = Some(self.tr(message));
+        self.page = Page::Day;
+    }
+
+    fn handle_passphrase_key(&mut self, code: KeyCode) {
+        let first = if self.store.is_unlocked() { 0 } else { 1 };
+        let input = &mut self.passphrase_inputs[self.passphrase_focus];
+
+        match code {
+            KeyCode::Enter => self.submit_passphrase(),
+            KeyCode::Tab | KeyCode::Down => self.passphrase_focus = (self.passphrase_focus + 1).min(2),
+            KeyCode::BackTab | KeyCode::Up => self.passphrase_focus = self.passphrase_focus.saturating_sub(1).max(first),
+            KeyCode::Char(c) => input.enter_char(c),
+            KeyCode::Backspace => input.delete_char(),
+            KeyCode::Left => input.move_cursor_left(),
+            KeyCode::Right => input.move_cursor_right(),
+            KeyCode::Esc => self.page = Page::Day,
+            _ => {}
+        }
+    }
+
+    fn resolve_conflict(&mut self, take_theirs: bool) {
+        if self.conflicts.is_empty() {
+            return;
+        }
+
+        match self.conflicts.remove(0) {
+            Conflict::Task { theirs, .. } if take_theirs => {
+                self.record_undo();
+                match self.tasks.iter_mut().find(|t| t.id == theirs.id) {
+                    Some(task) => *task = *theirs,
+                    None => self.tasks.push(*theirs),
+                }
+                self.dirty = true;
+            }
+            Conflict::Task { .. } => {}
+            // The merge keeps an edit over a delete whichever side made it,
+            // so both choices write the entry.
+            Conflict::Journal { day, mine, theirs } => {
+                let text = if take_theirs { theirs } else { mine };
+                if self.journal.get(&day) != text.as_ref() {
+                    self.record_undo();
+                    match text {
+                        Some(text) => self.journal.insert(day, text),
+                        None => self.journal.remove(&day),
+                    };
+                    self.dirty = true;
+                }
+            }
+        }
+
+        if self.conflicts.is_empty() {
+            self.page = self.conflict_return;
+            if self.pending_push {
+                self.pending_push = false;
+                self.sync_remote();
+            }
+        }
+    }
+
+    fn handle_conflict_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('m') => self.resolve_conflict(false),
+            KeyCode::Char('t') => self.resolve_conflict(true),
+            KeyCode::Esc => self.leave_conflicts(),
+            _ => {}
+        }
+    }
+
+    // Sets the remaining conflicts aside; a pending push keeps waiting
+    // for them until `C` brings them back and the last one is resolved.
+    fn leave_conflicts(&mut self) {
+        self.page = self.conflict_return;
+        self.status = Some(self.tr_args("status.conflicts_left", &[&self.conflicts.len()]));
+    }
+
+    fn reopen_conflicts(&mut self) {
+        if !self.conflicts.is_empty() {
+            self.conflict_return = self.page;
+            self.page = Page::Conflict;
+        }
+    }
+
+    fn export_markdown(&mut self) {
+        let path = self.store.dir().join("planner.md");
+        let result = fs::create_dir_all(self.store.dir())
+            .and_then(|_| fs::write(&path, export::to_markdown(&self.tasks, &self.journal)));
+
+        self.status = Some(match result {
+            Ok(()) => self.tr_args("status.exported", &[&path.display()]),
+            Err(err) => self.tr_args("status.export_failed", &[&err]),
+        });
+    }
+
+    fn day_task_indices(&self) -> Vec<usize> {
+        self.task_indices_on(self.selected_day())
+    }
+
+    // Timed tasks first by time, untimed ones last; ties keep the manual order.
+    fn task_indices_on(&self, day: NaiveDate) -> Vec<usize> {
+        let zone = self.clock.zone;
+        let mut indices: Vec<usize> = (0..self.tasks.len()).filter(|i| self.tasks[*i].occurs_on(day, zone, &self.calendar)).collect();
+        indices.sort_by_key(|i| {
+            let time = self.tasks[*i].time_on(day, zone, &self.calendar);
+            (time.is_empty(), time, self.tasks[*i].order)
+        });
+        indices
+    }
+
+    fn next_order(&self, task: &Task) -> u32 {
+        self.tasks
+            .iter()
+            .filter(|t| t.date == task.date && t.time == task.time)
+            .map(|t| t.order + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Swaps the selected task with its neighbour when both share a time;
+    // the selection moves along with it.
+    fn move_task(&mut self, step: isize) {
+        let indices = self.day_task_indices();
+        let Some(selected) = self.list_state.selected().filter(|i| *i < indices.len()) else {
+            return;
+        };
+        let Some(target) = selected.checked_add_signed(step).filter(|i| *i < indices.len()) else {
+            return;
+        };
+
+        let (day, zone) = (self.selected_day(), self.clock.zone);
+        let time = self.tasks[indices[selected]].time_on(day, zone, &self.calendar);
+        if self.tasks[indices[target]].time_on(day, zone, &self.calendar) != time {
+            self.status = Some(self.tr("status.ordered_by_time"));
+            return;
+        }
+
+        self.record_undo();
+        let group: Vec<usize> = indices.iter().copied().filter(|i| self.tasks[*i].time_on(day, zone, &self.calendar) == time).collect();
+        for (order, i) in group.into_iter().enumerate() {
+            self.tasks[i].order = order as u32;
+        }
+        let order = self.tasks[indices[selected]].order;
+        self.tasks[indices[selected]].order = self.tasks[indices[target]].order;
+        self.tasks[indices[target]].order = order;
+
+        self.list_state.select(Some(target));
+        self.dirty = true;
+    }
+
+    fn day_items(&self) -> Vec<ListItem<'static>> {
+        let blocks = self.day_blocks();
+        let marked = self.effective_marks();
+        let day = self.selected_day();
+
+        self.day_task_indices()
+            .into_iter()
+            .map(|i| (i, &self.tasks[i]))
+            .map(|(i, t)| {
+                let mark = if marked.contains(&t.id) { "●" } else { " " };
+                let prefix = if t.done { "✓" } else { " " };
+                let tags: String = t.tags.iter().map(|tag| format!(" #{tag}")).collect();
+                let project = if t.project.is_empty() { String::new() } else { format!(" @{}", t.project) };
+                let conflict = if blocks.iter().any(|b| b.task_index == i && b.conflict) { format!("  {}", self.texts.get("day.conflict")) } else { String::new() };
+                let time = t.time_on(day, self.clock.zone, &self.calendar);
+                let item = ListItem::new(format!("{}{} {:5}  {}{}{}{}", mark, prefix, time, t.text, project, tags, conflict));
+                if marked.contains(&t.id) {
+                    item.style(Style::default().fg(Color::Cyan))
+                } else {
+                    item
+                }
+            })
+            .collect()
+    }
+
+    fn day_blocks(&self) -> Vec<TimeBlock> {
+        let day = self.selected_day();
+        let zone = self.clock.zone;
+        timeline::layout_blocks(self.tasks.iter().enumerate().filter(|(_, t)| t.occurs_on(day, zone, &self.calendar)), day, zone, &self.calendar)
+    }
+
+    // Clamped to the calendar, however far the offset was moved.
+    fn selected_day(&self) -> NaiveDate {
+        let end = if self.day_offset < 0 { NaiveDate::MIN } else { NaiveDate::MAX };
+        self.clock.today().checked_add_signed(Duration::days(self.day_offset.into())).unwrap_or(end)
+    }
+
+    fn open_quick_add(&mut self) {
+        self.quick_add.clear();
+        self.completion = None;
+        self.page = Page::QuickAdd;
+    }
+
+    fn open_quick_add_at_cursor(&mut self) {
+        if !timeline::is_free(&self.day_blocks(), self.timeline_cursor) {
+            return;
+        }
+
+        self.open_quick_add();
+        let time = timeline::format_minutes(timeline::slot_start(self.timeline_cursor));
+        for c in format!("{time} ").chars() {
+            self.quick_add.enter_char(c);
+        }
+    }
+
+    fn toggle_day_view(&mut self) {
+        self.day_view = match self.day_view {
+            DayView::List => DayView::Timeline,
+            DayView::Timeline => DayView::List,
+        };
+        self.snap_timeline_cursor();
+    }
+
+    fn change_day(&mut self, delta: i32) {
+        self.day_offset = self.day_offset.saturating_add(delta);
+        self.list_state.select(Some(0));
+        self.snap_timeline_cursor();
+    }
+
+    // The Week and Month pages move the selected day; Enter opens it.
+    fn handle_overview_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Left => self.change_day(-1),
+            KeyCode::Right => self.change_day(1),
+            KeyCode::Up => self.change_day(-7),
+            KeyCode::Down => self.change_day(7),
+            KeyCode::Enter => self.page = Page::Day,
+            KeyCode::Char('w') => self.page = Page::Week,
+            KeyCode::Char('m') => self.page = Page::Month,
+            _ => return false,
+        }
+        true
+    }
+
+    // Ids of the rows in the Day or History list, with whether the row
+    // is an archived task (those can be browsed but not changed).
+    fn list_rows(&self) -> Vec<(String, bool)> {
+        match self.page {
+            Page::History => self.history_entries()
+                .into_iter()
+                .map(|(t, archived)| (t.id.clone(), archived))
+                .collect(),
+            _ => self.day_task_indices()
+                .into_iter()
+                .map(|i| (self.tasks[i].id.clone(), false))
+                .collect(),
+        }
+    }
+
+    fn list_state_mut(&mut self) -> &mut ListState {
+        match self.page {
+            Page::History => &mut self.history_state,
+            _ => &mut self.list_state,
+        }
+    }
+
+    fn move_selection(&mut self, step: isize) {
+        let count = self.list_rows().len();
+        if count == 0 {
+            return;
+        }
+        let state = self.list_state_mut();
+        let current = state.selected().unwrap_or(0).min(count - 1);
+        state.select(Some(current.saturating_add_signed(step).min(count - 1)));
+    }
+
+    fn toggle_mark(&mut self) {
+        let rows = self.list_rows();
+        let selected = self.list_state_mut().selected();
+        match selected.and_then(|i| rows.get(i)) {
+            Some((_, true)) => self.status = Some(self.tr("status.archived_unmarkable")),
+            Some((id, false)) if !self.marked.remove(id) => {
+                self.marked.insert(id.clone());
+            }
+            _ => {}
+        }
+    }
+
+    // `V` starts a range at the cursor; the second `V` keeps it marked.
+    fn toggle_visual(&mut self) {
+        match self.visual_anchor {
+            Some(_) => {
+                self.marked = self.effective_marks();
+                self.visual_anchor = None;
+            }
+            None => self.visual_anchor = self.list_state_mut().selected(),
+        }
+    }
+
+    fn clear_marks(&mut self) -> bool {
+        let had_marks = !self.marked.is_empty() || self.visual_anchor.is_some();
+        self.marked.clear();
+        self.visual_anchor = None;
+        had_marks
+    }
+
+    fn effective_marks(&self) -> BTreeSet<String> {
+        let mut marked = self.marked.clone();
+        let selected = match self.page {
+            Page::History => self.history_state.selected(),
+            _ => self.list_state.selected(),
+        };
+
+        if let (Some(anchor), Some(cursor)) = (self.visual_anchor, selected) {
+            let rows = self.list_rows();
+            for (id, archived) in rows.iter().skip(anchor.min(cursor)).take(anchor.abs_diff(cursor) + 1) {
+                if !archived {
+                    marked.insert(id.clone());
+                }
+            }
+        }
+        marked
+    }
+
+    // Marked tasks, or the task under the cursor when nothing is marked.
+    fn bulk_targets(&mut self) -> BTreeSet<String> {
+        let marked = self.effective_marks();
+        if !marked.is_empty() {
+            return marked;
+        }
+
+        let rows = self.list_rows();
+        let selected = self.list_state_mut().selected();
+        selected
+            .and_then(|i| rows.get(i))
+            .filter(|(_, archived)| !archived)
+            .map(|(id, _)| id.clone())
+            .into_iter()
+            .collect()
+    }
+
+    fn record_undo(&mut self) {
+        self.undo_stack.push(UndoStep {
+            tasks: self.tasks.clone(),
+            archive: self.archive.clone(),
+            journal: self.journal.clone(),
+        });
+        if self.undo_stack.len() > UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    fn undo(&mut self) {
+        match self.undo_stack.pop() {
+            Some(step) => {
+                self.tasks = step.tasks;
+                self.journal = step.journal;
+                if self.archive != step.archive {
+                    self.archive = step.archive;
+                    self.archive_dirty = true;
+                    let selected = self.archive_state.selected().unwrap_or(0);
+                    self.archive_state.select(Some(selected));
+                    self.clamp_archive_selection();
+                }
+                self.dirty = true;
+                self.status = Some(self.tr("status.undone"));
+            }
+            None => self.status = Some(self.tr("status.nothing_to_undo")),
+        }
+    }
+
+    // Runs one operation over all targets as a single undo step.
+    fn apply_bulk(&mut self, op: BulkOp) {
+        let targets = self.bulk_targets();
+        if targets.is_empty() {
+            return;
+        }
+
+        self.record_undo();
+        let count = bulk::apply(&mut self.tasks, &targets, &op);
+        self.clear_marks();
+        self.dirty = true;
+
+        if count > 1 || matches!(op, BulkOp::Delete) {
+            let message = match op {
+                BulkOp::ToggleDone => "status.bulk_updated",
+                BulkOp::Delete => "status.bulk_deleted",
+                BulkOp::Reschedule(_) => "status.bulk_rescheduled",
+                BulkOp::Retag { .. } => "status.bulk_retagged",
+                BulkOp::MoveToProject(_) => "status.bulk_moved",
+            };
+            self.status = Some(self.tr_args(message, &[&count]));
+        }
+
+        let count = self.list_rows().len();
+        let state = self.list_state_mut();
+        state.select(state.selected().map(|i| i.min(count.saturating_sub(1))));
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        if self.bulk_targets().is_empty() {
+            return;
+        }
+        self.prompt.clear();
+        self.completion = None;
+        self.prompt_kind = kind;
+        self.prompt_return = self.page;
+        self.page = Page::Prompt;
+    }
+
+    fn submit_prompt(&mut self) {
+        let input = self.prompt.input.trim().to_string();
+        let op = match self.prompt_kind {
+            PromptKind::Reschedule => match quick_add::parse_date_phrase(&input, self.clock.today(), self.config.week_start) {
+                Some(date) => BulkOp::Reschedule(date),
+                None => {
+                    self.status = Some(self.tr_args("status.not_a_date", &[&input]));
+                    return;
+                }
+            },
+            PromptKind::Retag => bulk::parse_retag(&input),
+            PromptKind::Project => BulkOp::MoveToProject(input.trim_start_matches('@').to_string()),
+        };
+
+        self.page = self.prompt_return;
+        self.apply_bulk(op);
+    }
+
+    fn handle_prompt_key(&mut self, code: KeyCode) {
+        if self.handle_completion_key(code) {
+            return;
+        }
+        match code {
+            KeyCode::Enter => self.submit_prompt(),
+            KeyCode::Char(c) => {
+                self.prompt.enter_char(c);
+                self.update_completion(false);
+            }
+            KeyCode::Backspace => {
+                self.prompt.delete_char();
+                self.update_completion(false);
+            }
+            KeyCode::Left => self.prompt.move_cursor_left(),
+            KeyCode::Right => self.prompt.move_cursor_right(),
+            KeyCode::Esc => self.page = self.prompt_return,
+            _ => {}
+        }
+    }
+
+    // The input that completes tags on the current page.
+    fn completion_input(&mut self) -> Option<&mut TextField> {
+        match self.page {
+            Page::QuickAdd => Some(&mut self.quick_add),
+            Page::Prompt if self.prompt_kind == PromptKind::Retag => Some(&mut self.prompt),
+            _ => None,
+        }
+    }
+
+    // Every tag in use, archived tasks included.
+    fn known_tags(&self) -> WordList {
+        let tags: BTreeSet<&str> = self.tasks.iter().chain(&self.archive).flat_map(|t| t.tags.iter().map(String::as_str)).collect();
+        WordList::new(tags)
+    }
+
+    // Suggests tags for the word before the cursor. Quick add only
+    // completes words starting with '#', the rest is the task itself; Tab
+    // (`open`) asks even when nothing was typed yet.
+    fn update_completion(&mut self, open: bool) {
+        let tags = self.known_tags();
+        let quick_add = self.page == Page::QuickAdd;
+        let Some(input) = self.completion_input() else {
+            return;
+        };
+
+        let cursor = input.byte_index();
+        let word = &input.input[complete::word_start(&input.input, cursor)..cursor];
+        let wanted = if quick_add { word.starts_with('#') } else { open || !word.is_empty() };
+        self.completion = if wanted { Popup::open(&tags, &input.input, cursor) } else { None };
+    }
+
+    // Tab opens the suggestions; while they show, Tab/Down and
+    // Shift-Tab/Up move, Enter takes one and Esc closes them.
+    fn handle_completion_key(&mut self, code: KeyCode) -> bool {
+        let Some(popup) = &mut self.completion else {
+            if code == KeyCode::Tab && self.completion_input().is_some() {
+                self.update_completion(true);
+                return true;
+            }
+            return false;
+        };
+
+        match code {
+            KeyCode::Tab | KeyCode::Down => popup.next(),
+            KeyCode::BackTab | KeyCode::Up => popup.previous(),
+            KeyCode::Esc => self.completion = None,
+            KeyCode::Enter => {
+                let popup = self.completion.take();
+                if let (Some(popup), Some(input)) = (popup, self.completion_input()) {
+                    let cursor = input.byte_index();
+                    let end = popup.accept(&mut input.input, cursor);
+                    input.move_cursor_to(input.input[..end].graphemes(true).count(), false);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    // Selection keys shared by the Day list and the History list.
+    fn handle_list_key(&mut self, key: KeyEvent) -> bool {
+        let reorder = self.page == Page::Day;
+        match key.code {
+            KeyCode::Up if reorder && key.modifiers.contains(KeyModifiers::SHIFT) => self.move_task(-1),
+            KeyCode::Down if reorder && key.modifiers.contains(KeyModifiers::SHIFT) => self.move_task(1),
+            KeyCode::Char('K') if reorder => self.move_task(-1),
+            KeyCode::Char('J') if reorder => self.move_task(1),
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('V') => self.toggle_visual(),
+            KeyCode::Enter => self.apply_bulk(BulkOp::ToggleDone),
+            KeyCode::Char('d') => self.apply_bulk(BulkOp::Delete),
+            KeyCode::Char('r') => self.open_prompt(PromptKind::Reschedule),
+            KeyCode::Char('#') => self.open_prompt(PromptKind::Retag),
+            KeyCode::Char('p') => self.open_prompt(PromptKind::Project),
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Esc => return self.clear_marks(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn marked_hint(&self) -> Vec<Span<'static>> {
+        let count = self.effective_marks().len();
+        if count == 0 {
+            return vec![];
+        }
+        vec![Span::styled(
+            format!("   {}", self.tr_args("marked", &[&count])),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )]
+    }
+
+    fn open_archive(&mut self) {
+        self.pending_purge = None;
+        self.archive_state.select((!self.archive.is_empty()).then_some(0));
+        self.page = Page::Archive;
+    }
+
+    // Moves completed tasks older than the configured age to the archive.
+    fn archive_old_tasks(&mut self) {
+        let today = self.clock.today();
+        let age = self.config.archive_after_days;
+
+        if self.tasks.iter().any(|t| archive::is_archivable(t, today, age)) {
+            self.record_undo();
+        }
+        let (old, keep): (Vec<Task>, Vec<Task>) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition(|t| archive::is_archivable(t, today, age));
+        self.tasks = keep;
+
+        self.status = Some(self.tr_args("status.archived", &[&old.len(), &age]));
+        if !old.is_empty() {
+            self.archive.extend(old);
+            self.archive.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.time.cmp(&b.time)));
+            self.archive_dirty = true;
+            self.dirty = true;
+            self.archive_state.select(Some(0));
+        }
+    }
+
+    fn restore_from_archive(&mut self) {
+        if let Some(i) = self.archive_state.selected().filter(|i| *i < self.archive.len()) {
+            self.record_undo();
+            let task = self.archive.remove(i);
+            self.status = Some(self.tr_args("status.restored", &[&task.text]));
+            self.tasks.push(task);
+            self.clamp_archive_selection();
+            self.archive_dirty = true;
+            self.dirty = true;
+        }
+    }
+
+    fn purge(&mut self, target: Purge) {
+        match target {
+            Purge::Selected => {
+                if let Some(i) = self.archive_state.selected().filter(|i| *i < self.archive.len()) {
+                    self.record_undo();
+                    let task = self.archive.remove(i);
+                    self.status = Some(self.tr_args("status.purged", &[&task.text]));
+                }
+            }
+            Purge::All => {
+                if !self.archive.is_empty() {
+                    self.record_undo();
+                }
+                self.status = Some(self.tr_args("status.purged_all", &[&self.archive.len()]));
+                self.archive.clear();
+            }
+        }
+        self.clamp_archive_selection();
+        self.archive_dirty = true;
+    }
+
+    fn clamp_archive_selection(&mut self) {
+        let selected = self.archive_state.selected().map(|i| i.min(self.archive.len().saturating_sub(1)));
+        self.archive_state.select(selected.filter(|_| !self.archive.is_empty()));
+    }
+
+    // Returns false for keys the archive page leaves to the global bindings.
+    fn handle_archive_key(&mut self, code: KeyCode) -> bool {
+        if let Some(target) = self.pending_purge.take() {
+            if code == KeyCode::Char('y') {
+                self.purge(target);
+            } else {
+                self.status = Some(self.tr("status.purge_cancelled"));
+            }
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('A') => self.archive_old_tasks(),
+            KeyCode::Char('r') => self.restore_from_archive(),
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('p') if self.archive_state.selected().is_some() => {
+                self.pending_purge = Some(Purge::Selected);
+            }
+            KeyCode::Char('P') if !self.archive.is_empty() => self.pending_purge = Some(Purge::All),
+            KeyCode::Down if !self.archive.is_empty() => {
+                let next = self.archive_state.selected().map_or(0, |i| (i + 1).min(self.archive.len() - 1));
+                self.archive_state.select(Some(next));
+            }
+            KeyCode::Up => {
+                let previous = self.archive_state.selected().map(|i| i.saturating_sub(1));
+                self.archive_state.select(previous);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn open_backups(&mut self) {
+        match backup::list(&self.store) {
+            Ok(backups) => self.backups = backups,
+            Err(err) => {
+                self.status = Some(self.tr_args("status.backups_failed", &[&err]));
+                return;
+            }
+        }
+        self.backup_state.select((!self.backups.is_empty()).then_some(0));
+        self.page = Page::Backups;
+    }
+
+    fn restore_backup(&mut self) {
+        let Some(backup) = self.backup_state.selected().and_then(|i| self.backups.get(i)) else {
+            return;
+        };
+
+        let contents = match &backup.contents {
+            Ok(contents) => (contents.tasks.clone(), contents.journal.clone(), contents.archive.clone()),
+            Err(err) => {
+                self.status = Some(self.tr_args("status.backup_unreadable", &[err]));
+                return;
+            }
+        };
+        let (tasks, journal, archive) = contents;
+        let taken = self.backup_taken(backup);
+
+        self.record_undo();
+        self.tasks = tasks;
+        self.journal = journal;
+        self.dirty = true;
+        // Older backups hold only the tasks file; they leave the archive
+        // as it is.
+        if let Some(archive) = archive {
+            self.archive = archive;
+            self.archive_dirty = true;
+            self.archive_state.select((!self.archive.is_empty()).then_some(0));
+        }
+        self.status = Some(self.tr_args("status.backup_restored", &[&taken]));
+        self.page = Page::Day;
+    }
+
+    fn backup_taken(&self, backup: &Backup) -> String {
+        match backup.modified {
+            Some(modified) => modified.format(&format!("{} %H:%M:%S", self.config.date_format)).to_string(),
+            None => "?".to_string(),
+        }
+    }
+
+    fn handle_backups_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Enter => self.restore_backup(),
+            KeyCode::Down if !self.backups.is_empty() => {
+                let next = self.backup_state.selected().map_or(0, |i| (i + 1).min(self.backups.len() - 1));
+                self.backup_state.select(Some(next));
+            }
+            KeyCode::Up => {
+                let previous = self.backup_state.selected().map(|i| i.saturating_sub(1));
+                self.backup_state.select(previous);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn move_timeline_cursor(&mut self, step: isize) {
+        self.timeline_cursor = timeline::next_free_slot(&self.day_blocks(), self.timeline_cursor, step);
+    }
+
+    // Keeps the timeline cursor off booked slots after the day changes.
+    fn snap_timeline_cursor(&mut self) {
+        let blocks = self.day_blocks();
+        if !timeline::is_free(&blocks, self.timeline_cursor) {
+            let down = timeline::next_free_slot(&blocks, self.timeline_cursor, 1);
+            self.timeline_cursor = if down != self.timeline_cursor {
+                down
+            } else {
+                timeline::next_free_slot(&blocks, self.timeline_cursor, -1)
+            };
+        }
+    }
+
+    fn commit_quick_add(&mut self) {
+        let today = self.clock.today();
+        let parsed = quick_add::parse(&self.quick_add.input, today, self.selected_day(), self.config.week_start);
+
+        if parsed.task.text.is_empty() {
+            return;
+        }
+
+        self.day_offset = (parsed.task.date - today).num_days() as i32;
+        self.record_undo();
+        let mut task = parsed.task;
+        task.zone = (!self.config.floating_times).then_some(self.clock.zone);
+        task.order = self.next_order(&task);
+        self.tasks.push(task);
+        self.dirty = true;
+        self.quick_add.clear();
+        self.page = Page::Day;
+    }
+
+    fn handle_quick_add_key(&mut self, code: KeyCode) {
+        if self.handle_completion_key(code) {
+            return;
+        }
+        match code {
+            KeyCode::Enter => self.commit_quick_add(),
+            KeyCode::Char(c) => {
+                self.quick_add.enter_char(c);
+                self.update_completion(false);
+            }
+            KeyCode::Backspace => {
+                self.quick_add.delete_char();
+                self.update_completion(false);
+            }
+            KeyCode::Left => self.quick_add.move_cursor_left(),
+            KeyCode::Right => self.quick_add.move_cursor_right(),
+            KeyCode::Esc => {
+                self.quick_add.clear();
+                self.page = Page::Day;
+            }
+            _ => {}
+        }
+    }
+
+    fn open_journal(&mut self) {
+        let day = self.selected_day();
+        let text = self.journal.get(&day).cloned().unwrap_or_default();
+        self.journal_day = day;
+        self.journal_editor.set_value(&text);
+        self.page = Page::Journal;
+    }
+
+    fn close_journal(&mut self) {
+        let day = self.journal_day;
+        let text = self.journal_editor.value().trim_end().to_string();
+        let previous = self.journal.get(&day).cloned().unwrap_or_default();
+
+        if text != previous {
+            if text.is_empty() {
+                self.journal.remove(&day);
+            } else {
+                self.journal.insert(day, text);
+            }
+            self.dirty = true;
+        }
+        self.page = Page::Day;
+    }
+
+    // Pasted text arrives whole (bracketed paste), so a newline in it
+    // can't submit a prompt halfway through.
+    fn handle_paste(&mut self, text: &str) {
+        self.completion = None;
+        match self.page {
+            Page::QuickAdd => self.quick_add.paste(text),
+            Page::Search => {
+                self.search_input.paste(text);
+                self.search_state.select(None);
+            }
+            Page::Prompt => self.prompt.paste(text),
+            Page::Unlock => self.unlock_input.paste(text),
+            Page::Passphrase => self.passphrase_inputs[self.passphrase_focus].paste(text),
+            Page::Journal => self.journal_editor.paste(text),
+            _ => {}
+        }
+    }
+
+    // The text area takes every editing key; Esc (or its submit key)
+    // saves the entry and closes the editor.
+    fn handle_journal_key(&mut self, key: KeyEvent) {
+        if self.journal_editor.is_submit_key(key) || !self.journal_editor.handle_key(key) {
+            self.close_journal();
+        }
+    }
+
+    fn open_search(&mut self) {
+        self.search_state.select(None);
+        self.page = Page::Search;
+    }
+
+    fn handle_search_key(&mut self, code: KeyCode) {
+        let hits = search::search(&self.tasks, &self.journal, &self.search_input.input);
+
+        match code {
+            KeyCode::Esc => self.page = Page::Day,
+            KeyCode::Enter => {
+                if let Some(hit) = self.search_state.selected().and_then(|i| hits.get(i)) {
+                    self.day_offset = (hit.date - self.clock.today()).num_days() as i32;
+                    self.page = Page::Day;
+                }
+            }
+            KeyCode::Down if !hits.is_empty() => {
+                let next = self.search_state.selected().map_or(0, |i| (i + 1).min(hits.len() - 1));
+                self.search_state.select(Some(next));
+            }
+            KeyCode::Up => {
+                let previous = self.search_state.selected().and_then(|i| i.checked_sub(1));
+                self.search_state.select(previous);
+            }
+            KeyCode::Char(c) => {
+                self.search_input.enter_char(c);
+                self.search_state.select(None);
+            }
+            KeyCode::Backspace => {
+                self.search_input.delete_char();
+                self.search_state.select(None);
+            }
+            KeyCode::Left => self.search_input.move_cursor_left(),
+            KeyCode::Right => self.search_input.move_cursor_right(),
+            _ => {}
+        }
+    }
+
+
+    fn render_day_view(&mut self, frame: &mut Frame, dim: bool) {
+        let container = frame.area();
+
+        let style = if dim {
+            Style::default().fg(Color::DarkGray)
+        }else {
+            Style::default()
+        };
+
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),   // header
+                Constraint::Min(0),      // content
+                Constraint::Length(2),   // action
+                Constraint::Length(2),   // footer
+            ])
+            .split(container);
+
+        let date = self.selected_day();
+        let formatted_date = self.format_date(date);
+
+        let label = match self.day_offset {
+            0 => "day.today",
+            -1 => "day.yesterday",
+            1 => "day.tomorrow",
+            _ => "day.selected",
+        };
+
+        let holidays = self.calendar.holidays_on(date);
+        let day_kind = if !holidays.is_empty() {
+            Span::styled(format!("★ {}   ", holidays.join(" · ")), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        } else if self.calendar.is_day_off(date) {
+            Span::styled(format!("{}   ", self.texts.get("day.day_off")), default_style_text(true))
+        } else {
+            Span::raw("")
+        };
+
+        let header_line = Line::from(vec![
+            Span::raw(self.tr("day.previous")),
+            Span::styled(
+                format!("{} · {} · {}   ", self.texts.get(label), formatted_date, self.clock.zone),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            day_kind,
+            Span::raw(self.tr("day.next")),
+        ]);
+
+        let header_text = vec![
+            title_text(self.texts.get("planner.title"), dim),
+            header_line,
+        ];
+
+
+        let action_hint = match self.day_view {
+            DayView::List => self.tr("day.hint_list"),
+            DayView::Timeline => self.tr("day.hint_timeline"),
+        };
+
+        let action_text = match &self.status {
+            Some(status) => Line::from(Span::styled(status.clone(), Style::default().fg(Color::Yellow))),
+            None => Line::from(Span::styled(action_hint, default_style_text(dim))),
+        };
+
+        let view_hints = match self.day_view {
+            DayView::List => vec![
+                Span::raw("   n "),
+                Span::styled(self.tr("key.new"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   a "),
+                Span::styled(self.tr("key.quick_add"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   t "),
+                Span::styled(self.tr("key.timeline"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   j "),
+                Span::styled(self.tr("key.journal"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   A "),
+                Span::styled(self.tr("key.archive"), Style::default().add_modifier(Modifier::BOLD)),
+            ],
+            DayView::Timeline => vec![
+                Span::raw("   ↑/↓ "),
+                Span::styled(self.tr("key.free_slot"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   t "),
+                Span::styled(self.tr("key.list"), Style::default().add_modifier(Modifier::BOLD)),
+            ],
+        };
+
+        let mut footer_spans = vec![
+            Span::raw("←/→ "),
+            Span::styled(self.tr("key.change_day"), Style::default().add_modifier(Modifier::BOLD)),
+        ];
+        footer_spans.extend(view_hints);
+        footer_spans.extend([
+            Span::raw("   / "),
+            Span::styled(self.tr("key.search"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   h "),
+            Span::styled(self.tr("key.history"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   ? "),
+            Span::styled(self.tr("key.help"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   q "),
+            Span::styled(self.tr("key.quit"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+        footer_spans.extend(self.marked_hint());
+        let footer_text = Line::from(footer_spans);
+
+
+        let header_panel = Paragraph::new(header_text)
+            .style(style)
+            .block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            );
+
+        let content_block = panel_block_with_padding_borders(7, 0, 2, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM);
+
+        let action_panel = Paragraph::new(action_text)
+            .style(style)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let footer_panel = Paragraph::new(footer_text)
+            .style(style)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+
+
+        frame.render_widget(header_panel, vertical_layout[0]);
+        match self.day_view {
+            DayView::List => {
+                let entry = self.journal.get(&date).cloned().unwrap_or_default();
+                let journal_height = if entry.is_empty() { 0 } else { entry.lines().count().min(6) as u16 + 2 };
+
+                let [list_area, journal_area] = Layout::vertical([
+                    Constraint::Min(0),
+                    Constraint::Length(journal_height),
+                ]).areas(vertical_layout[1]);
+
+                let content_panel = List::new(self.day_items())
+                    .style(style)
+                    .highlight_symbol("▸ ")
+                    .block(content_block);
+                frame.render_stateful_widget(content_panel, list_area,  &mut self.list_state );
+
+                if !entry.is_empty() {
+                    let journal_panel = Paragraph::new(entry)
+                        .style(style)
+                        .wrap(Wrap { trim: false })
+                        .block(
+                            panel_block_with_padding_borders(7, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+                                .title(Span::styled(self.tr("day.journal"), default_style_text(true)))
+                        );
+                    frame.render_widget(journal_panel, journal_area);
+                }
+            }
+            DayView::Timeline => {
+                let timeline_block = panel_block_with_padding_borders(2, 1, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+                    .style(style);
+                let timeline_area = timeline_block.inner(vertical_layout[1]);
+                frame.render_widget(timeline_block, vertical_layout[1]);
+                self.render_timeline(frame, timeline_area, dim);
+            }
+        }
+        frame.render_widget(action_panel, vertical_layout[2]);
+        frame.render_widget(footer_panel, vertical_layout[3]);
+
+    }
+
+
+
+    fn render_timeline(&mut self, frame: &mut Frame, area: Rect, dim: bool) {
+        let rows = area.height as usize;
+        if rows == 0 {
+            return;
+        }
+
+        let cursor = self.timeline_cursor;
+        if cursor < self.timeline_scroll {
+            self.timeline_scroll = cursor;
+        } else if cursor >= self.timeline_scroll + rows {
+            self.timeline_scroll = cursor + 1 - rows;
+        }
+        self.timeline_scroll = self.timeline_scroll.min(timeline::slot_count().saturating_sub(rows));
+        let scroll = self.timeline_scroll;
+
+        let label_width = 7;
+        let [labels_area, grid_area] = Layout::horizontal([
+            Constraint::Length(label_width),
+            Constraint::Min(0),
+        ]).areas(area);
+
+        let now = self.clock.now();
+        let now_slot = (self.day_offset == 0)
+            .then(|| now.hour() * 60 + now.minute())
+            .and_then(timeline::slot_for);
+
+        let blocks = self.day_blocks();
+        let cursor_free = timeline::is_free(&blocks, cursor);
+
+        for row in 0..rows.min(timeline::slot_count() - scroll) {
+            let slot = scroll + row;
+            let minutes = timeline::slot_start(slot);
+            let label_area = Rect { y: area.y + row as u16, height: 1, ..labels_area };
+            let grid_row = Rect { y: area.y + row as u16, height: 1, ..grid_area };
+
+            let label = if minutes.is_multiple_of(60) { timeline::format_minutes(minutes) } else { "   ·".into() };
+            let label_style = if Some(slot) == now_slot {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                default_style_text(true)
+            };
+            frame.render_widget(Paragraph::new(Span::styled(label, label_style)), label_area);
+
+            if Some(slot) == now_slot {
+                let line = format!("{}{}", self.tr_args("timeline.now", &[&now.format("%H:%M")]), "─".repeat(grid_row.width as usize));
+                frame.render_widget(Paragraph::new(Span::styled(line, Style::default().fg(Color::Red))), grid_row);
+            }
+
+            if slot == cursor && cursor_free && !dim {
+                let hint = self.tr_args("timeline.free", &[&timeline::format_minutes(minutes)]);
+                frame.render_widget(
+                    Paragraph::new(Span::styled(hint, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+                    grid_row,
+                );
+            }
+        }
+
+        for block in &blocks {
+            let first = block.first_slot().max(scroll);
+            let last = block.last_slot().min(scroll + rows - 1);
+            if first > last {
+                continue;
+            }
+
+            let width = grid_area.width as usize;
+            let left = width * block.column / block.columns;
+            let right = width * (block.column + 1) / block.columns;
+            let block_area = Rect {
+                x: grid_area.x + left as u16,
+                y: area.y + (first - scroll) as u16,
+                width: (right - left).saturating_sub(1).max(1) as u16,
+                height: (last - first + 1) as u16,
+            };
+
+            let task = &self.tasks[block.task_index];
+            let background = if dim {
+                Color::DarkGray
+            } else if block.conflict {
+                Color::Red
+            } else if task.done {
+                Color::DarkGray
+            } else {
+                Color::Blue
+            };
+
+            let mut lines = vec![Line::from(format!(
+                "{}–{} {}",
+                timeline::format_minutes(block.start),
+                timeline::format_minutes(block.end),
+                task.text,
+            ))];
+            if block.conflict {
+                lines.push(Line::from(self.tr("day.conflict")));
+            }
+
+            frame.render_widget(Clear, block_area);
+            frame.render_widget(
+                Paragraph::new(lines).style(Style::default().bg(background).fg(Color::White)),
+                block_area,
+            );
+        }
+    }
+
+    fn render_input_view(&mut self, frame: &mut Frame) {
+
+        self.render_day_view(frame, true);
+
+        let popup_area = centered_rect(45, 80, frame.area());
+
+        frame.render_widget(Clear, popup_area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),  // Header "NEW TASK"
+                Constraint::Min(0),     // Form Fields
+                Constraint::Length(3),  // Action Buttons
+            ])
+            .margin(1) // Margin dalam popup
+            .split(popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+
+
+        frame.render_widget(popup_block, popup_area);
+
+        let header_text = vec![
+            Line::from(""),
+            title_text(self.texts.get("input.title"), false),
+            Line::from(""),
+            Line::from(
+                Span::styled(
+                    self.tr("input.subtitle"), default_style_text(false),
+                )
+            )
+        ];
+
+        let header_panel_form = Paragraph::new(header_text)
+            .style(default_style_text(false))
+            .alignment(Alignment::Center);
+
+        frame.render_widget(header_panel_form, layout[0]);
+
+        let form_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+            ])
+            .split(layout[1]);
+
+        let fields = [
+            (self.tr("field.date"), self.format_date(self.input_buffer.date)),
+            (self.tr("field.time"), self.input_buffer.time.clone()),
+            (self.tr("field.title"), self.input_buffer.text.clone()),
+        ];
+
+        for (i, (label, value)) in fields.into_iter().enumerate() {
+            let border_style = if self.input_buffer.focus == i {
+                Style::default().fg(Color::Yellow)
+            } else {
+                default_style_text(false)
+            };
+
+            let field_panel = Paragraph::new(value)
+                .style(default_style_text(false))
+                .block(Block::default().borders(Borders::ALL).border_style(border_style).title(label));
+
+            frame.render_widget(field_panel, form_layout[i]);
+        }
+
+        let action_text = Line::from(vec![
+            Span::raw("Tab "),
+            Span::styled(self.tr("key.next"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Enter "),
+            Span::styled(self.tr("key.save"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.cancel"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        frame.render_widget(Paragraph::new(action_text).alignment(Alignment::Center), layout[2]);
+    }
+
+    fn render_quick_add_view(&mut self, frame: &mut Frame) {
+
+        self.render_day_view(frame, true);
+
+        let popup_area = centered_rect(45, 80, frame.area());
+
+        frame.render_widget(Clear, popup_area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Header
+                Constraint::Length(3),  // Input
+                Constraint::Min(0),     // Preview
+                Constraint::Length(1),  // Actions
+            ])
+            .margin(1)
+            .split(popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+
+        frame.render_widget(popup_block, popup_area);
+
+        let header_text = vec![
+            title_text(self.texts.get("quick_add.title"), false),
+            Line::from(Span::styled(
+                self.tr("quick_add.example"), default_style_text(false)
+            )),
+        ];
+
+        frame.render_widget(Paragraph::new(header_text).alignment(Alignment::Center), layout[0]);
+
+        let today = self.clock.today();
+        let parsed = quick_add::parse(&self.quick_add.input, today, self.selected_day(), self.config.week_start);
+
+        let input_spans: Vec<Span> = parsed.tokens
+            .iter()
+            .enumerate()
+            .flat_map(|(i, token)| {
+                let separator = Span::raw(if i == 0 { "" } else { " " });
+                [separator, Span::styled(token.text.clone(), token_style(token.kind))]
+            })
+            .collect();
+
+        let input_panel = Paragraph::new(Line::from(input_spans))
+            .block(Block::default().borders(Borders::ALL).title(self.tr("field.task")));
+
+        frame.render_widget(input_panel, layout[1]);
+
+        let task = &parsed.task;
+        let tags = task.tags.iter().map(|t| format!("#{t}")).collect::<Vec<_>>().join(" ");
+        let repeat = task.recurrence.map(|r| self.texts.recurrence(r)).unwrap_or_else(|| "-".into());
+        let time = if task.time.is_empty() { "-".to_string() } else { task.time.clone() };
+
+        let mut preview_text = vec![
+            preview_line(self.texts.get("field.date"), format!("{} ({})", self.format_date(task.date), self.texts.weekday(task.date.weekday()))),
+            preview_line(self.texts.get("field.time"), time),
+            preview_line(self.texts.get("field.duration"), if task.duration == 0 { "-".into() } else { self.tr_args("minutes", &[&task.duration]) }),
+            preview_line(self.texts.get("field.title"), task.text.clone()),
+            preview_line(self.texts.get("field.tags"), if tags.is_empty() { "-".into() } else { tags }),
+            preview_line(self.texts.get("field.priority"), self.texts.priority(task.priority).to_string()),
+            preview_line(self.texts.get("field.repeat"), repeat),
+        ];
+
+        if parsed.has_unparsed() {
+            preview_text.push(Line::from(""));
+            preview_text.push(Line::from(Span::styled(
+                self.tr("quick_add.unparsed"),
+                token_style(TokenKind::Unparsed),
+            )));
+        }
+
+        let preview_panel = Paragraph::new(preview_text)
+            .block(panel_block_with_padding_borders(1, 0, 1, 0, Borders::NONE));
+
+        frame.render_widget(preview_panel, layout[2]);
+
+        let action_text = Line::from(vec![
+            Span::raw("Enter "),
+            Span::styled(self.tr("key.save"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.cancel"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        frame.render_widget(Paragraph::new(action_text).alignment(Alignment::Center), layout[3]);
+
+        let cursor_x = self.quick_add.input[..self.quick_add.byte_index()].width() as u16;
+        frame.set_cursor_position(Position::new(
+            layout[1].x + 1 + cursor_x,
+            layout[1].y + 1,
+        ));
+
+        if let Some(popup) = &self.completion {
+            let word_x = self.quick_add.input[..popup.word_start].width() as u16;
+            popup.render(frame, layout[1], layout[1].x + 1 + word_x);
+        }
+    }
+
+    fn render_journal_view(&mut self, frame: &mut Frame) {
+
+        self.render_day_view(frame, true);
+
+        let popup_area = centered_rect(60, 80, frame.area());
+
+        frame.render_widget(Clear, popup_area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Min(0),     // Editor
+                Constraint::Length(1),  // Actions
+            ])
+            .margin(1)
+            .split(popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+
+        frame.render_widget(popup_block, popup_area);
+
+        let day = self.journal_day;
+        let date = format!("{}, {}", self.texts.weekday(day.weekday()), self.format_date(day));
+        let header_text = vec![
+            title_text(self.texts.get("journal.title"), false),
+            Line::from(Span::styled(date, default_style_text(false))),
+        ];
+
+        frame.render_widget(Paragraph::new(header_text).alignment(Alignment::Center), layout[0]);
+
+        self.journal_editor.render(frame, layout[1], "", true);
+
+        let action_text = Line::from(vec![
+            Span::raw("Enter "),
+            Span::styled(self.tr("key.new_line"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.save_close"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        frame.render_widget(Paragraph::new(action_text).alignment(Alignment::Center), layout[2]);
+    }
+
+    fn render_search_view(&mut self, frame: &mut Frame) {
+        let container = frame.area();
+
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(2),
+            ])
+            .split(container);
+
+        let header_text = vec![
+            title_text(self.texts.get("search.title"), false),
+            Line::from(Span::styled(
+                self.tr("search.subtitle"), default_style_text(false)
+            )),
+        ];
+
+        let footer_text =  Line::from(vec![
+            Span::raw("↑/↓ "),
+            Span::styled(self.tr("key.select"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Enter "),
+            Span::styled(self.tr("key.go_to_day"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.back_to_planner"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        let hits = search::search(&self.tasks, &self.journal, &self.search_input.input);
+        let items: Vec<ListItem> = hits
+            .iter()
+            .map(|hit| {
+                let marker = match hit.kind {
+                    HitKind::Task { done: true } => "✓",
+                    HitKind::Task { done: false } => " ",
+                    HitKind::Journal => "✎",
+                };
+                ListItem::new(format!("{}  {} {}", self.format_date(hit.date), marker, hit.text))
+            })
+            .collect();
+
+        let header_panel = Paragraph::new(header_text)
+            .block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            );
+
+        let input_panel = Paragraph::new(self.search_input.input.as_str())
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+                    .title(Span::styled(" / ", default_style_text(true)))
+            );
+
+        let content_panel = List::new(items)
+            .highlight_symbol("▸ ")
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .block(
+                panel_block_with_padding_borders(5, 0, 1, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let footer_panel = Paragraph::new(footer_text)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        frame.render_widget(header_panel, vertical_layout[0]);
+        frame.render_widget(input_panel, vertical_layout[1]);
+        frame.render_stateful_widget(content_panel, vertical_layout[2], &mut self.search_state);
+        frame.render_widget(footer_panel, vertical_layout[3]);
+
+        let cursor_x = self.search_input.input[..self.search_input.byte_index()].width() as u16;
+        frame.set_cursor_position(Position::new(
+            vertical_layout[1].x + 3 + cursor_x,
+            vertical_layout[1].y + 1,
+        ));
+    }
+
+    // Completed and past tasks, plus the archive when it is included,
+    // oldest first; the flag marks archived entries.
+    fn history_entries(&self) -> Vec<(&Task, bool)> {
+        let today = self.clock.today();
+
+        let mut entries: Vec<(&Task, bool)> = self.tasks
+            .iter()
+            .filter(|t| t.done || t.date < today)
+            .map(|t| (t, false))
+            .collect();
+        if self.history_include_archived {
+            entries.extend(self.archive.iter().map(|t| (t, true)));
+        }
+        entries.sort_by_cached_key(|(task, _)| task.localized(self.clock.zone));
+        entries
+    }
+
+    // One item per task; the first task of each day also carries the date
+    // heading so the selection maps straight onto tasks.
+    fn history_items(&self, dim: bool) -> Vec<ListItem<'static>> {
+        let marked = self.effective_marks();
+        let mut items = vec![];
+        let mut current_date = None;
+
+        for (task, archived) in self.history_entries() {
+            let (date, time) = task.localized(self.clock.zone);
+            let mut lines = vec![];
+            if current_date != Some(date) {
+                if current_date.is_some() {
+                    lines.push(Line::from(""));
+                }
+                current_date = Some(date);
+                lines.push(Line::from(Span::styled(
+                    self.format_date(date),
+                    default_style_text(dim).add_modifier(Modifier::BOLD),
+                )));
+            }
+
+            let mark = if marked.contains(&task.id) { "●" } else { " " };
+            let prefix = if task.done { "✓" } else { " " };
+            let style = if marked.contains(&task.id) {
+                Style::default().fg(Color::Cyan)
+            } else {
+                default_style_text(dim || archived)
+            };
+            let mut spans = vec![Span::styled(
+                format!(" {}{} {:5} {}", mark, prefix, time, task.text),
+                style,
+            )];
+            if archived {
+                spans.push(Span::styled(self.tr("history.archived"), default_style_text(true)));
+            }
+            lines.push(Line::from(spans));
+            items.push(ListItem::new(lines));
+        }
+
+        items
+    }
+
+    fn render_history_view(&mut self, frame: &mut Frame, dim: bool) {
+        let container = frame.area();
+
+
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(4),
+                Constraint::Min(0),
+                Constraint::Length(2),
+            ])
+            .split(container);
+
+
+        let subtitle = if self.history_include_archived {
+            self.tr("history.subtitle_archived")
+        } else {
+            self.tr("history.subtitle")
+        };
+
+        let header_text = vec![
+            title_text(self.texts.get("history.title"), dim),
+            Line::from(Span::styled(
+                subtitle, default_style_text(dim)
+            )),
+        ];
+
+        let content_items = self.history_items(dim);
+
+        let mut footer_spans = vec![
+            Span::raw("↑/↓ "),
+            Span::styled(self.tr("key.select"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Space/V "),
+            Span::styled(self.tr("key.mark"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   i "),
+            Span::styled(
+                self.tr(if self.history_include_archived { "key.hide_archived" } else { "key.include_archived" }),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("   A "),
+            Span::styled(self.tr("key.archive"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.back_to_planner"), Style::default().add_modifier(Modifier::BOLD)),
+        ];
+        footer_spans.extend(self.marked_hint());
+        let footer_text = match &self.status {
+            Some(status) => Line::from(Span::styled(status.clone(), Style::default().fg(Color::Yellow))),
+            None => Line::from(footer_spans),
+        };
+
+        let header_panel = Paragraph::new(header_text)
+            .block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            );
+
+        let content_panel = List::new(content_items)
+            .highlight_symbol("▸ ")
+            .block(
+                panel_block_with_padding_borders(7, 0, 2, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let footer_panel = Paragraph::new(footer_text)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        frame.render_widget(header_panel, vertical_layout[0]);
+        frame.render_stateful_widget(content_panel, vertical_layout[1], &mut self.history_state);
+        frame.render_widget(footer_panel, vertical_layout[2]);
+
+    }
+
+    fn render_week_view(&self, frame: &mut Frame) {
+        let container = frame.area();
+
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),
+                Constraint::Min(0),
+                Constraint::Length(2),
+            ])
+            .split(container);
+
+        let days = overview::week(self.selected_day(), self.config.week_start);
+
+        let header_text = vec![
+            title_text(self.texts.get("week.title"), false),
+            Line::from(Span::styled(
+                format!("{} – {} · {}", self.format_date(days[0]), self.format_date(days[6]), self.clock.zone),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+        ];
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 7); 7])
+            .split(vertical_layout[1]);
+
+        for (day, area) in days.into_iter().zip(columns.iter()) {
+            let mut lines = self.holiday_lines(day);
+            if lines.is_empty() && self.calendar.is_day_off(day) {
+                lines.push(Line::from(Span::styled(self.tr("day.day_off"), default_style_text(true))));
+            }
+            for i in self.task_indices_on(day) {
+                let task = &self.tasks[i];
+                let time = task.time_on(day, self.clock.zone, &self.calendar);
+                let style = if task.done { default_style_text(true) } else { default_style_text(false) };
+                let mark = if task.done { "✓ " } else { "" };
+                lines.push(Line::from(Span::styled(format!("{mark}{time} {}", task.text).trim_start().to_string(), style)));
+            }
+
+            let title = format!(" {} {} ", self.weekday_short(day.weekday()), day.day());
+            let panel = Paragraph::new(lines)
+                .wrap(Wrap { trim: true })
+                .block(self.overview_block(day, title));
+            frame.render_widget(panel, *area);
+        }
+
+        let footer_panel = Paragraph::new(self.overview_footer("key.month", "m"))
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        frame.render_widget(
+            Paragraph::new(header_text).block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            ),
+            vertical_layout[0],
+        );
+        frame.render_widget(footer_panel, vertical_layout[2]);
+    }
+
+    fn render_month_view(&self, frame: &mut Frame) {
+        let container = frame.area();
+
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(2),
+            ])
+            .split(container);
+
+        let selected = self.selected_day();
+        let weeks = overview::month(selected, self.config.week_start);
+
+        let header_text = vec![
+            title_text(self.texts.get("month.title"), false),
+            Line::from(Span::styled(
+                format!("{} {}", self.texts.month(selected.month()), selected.year()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+        ];
+
+        let weekday_columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 7); 7])
+            .split(vertical_layout[1]);
+        for (day, area) in weeks[0].iter().zip(weekday_columns.iter()) {
+            let name = Paragraph::new(self.weekday_short(day.weekday()))
+                .alignment(Alignment::Center)
+                .style(default_style_text(self.calendar.is_day_off(*day)));
+            frame.render_widget(name, *area);
+        }
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, weeks.len() as u32); weeks.len()])
+            .split(vertical_layout[2]);
+
+        for (week, row) in weeks.iter().zip(rows.iter()) {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Ratio(1, 7); 7])
+                .split(*row);
+
+            for (day, area) in week.iter().zip(cells.iter()) {
+                let mut lines = self.holiday_lines(*day);
+                let count = self.task_indices_on(*day).len();
+                if count > 0 {
+                    lines.push(Line::from(Span::styled(self.tr_args("task_count", &[&count]), default_style_text(false))));
+                }
+                if day.month() != selected.month() {
+                    lines = lines.into_iter().map(|line| line.style(default_style_text(true))).collect();
+                }
+
+                let panel = Paragraph::new(lines)
+                    .wrap(Wrap { trim: true })
+                    .block(self.overview_block(*day, format!(" {} ", day.day())));
+                frame.render_widget(panel, *area);
+            }
+        }
+
+        let footer_panel = Paragraph::new(self.overview_footer("key.week", "w"))
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        frame.render_widget(
+            Paragraph::new(header_text).block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            ),
+            vertical_layout[0],
+        );
+        frame.render_widget(footer_panel, vertical_layout[3]);
+    }
+
+    // The day's holiday names in red.
+    fn holiday_lines(&self, day: NaiveDate) -> Vec<Line<'static>> {
+        self.calendar
+            .holidays_on(day)
+            .iter()
+            .map(|name| Line::from(Span::styled(format!("★ {name}"), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))))
+            .collect()
+    }
+
+    // A day's box: yellow for the selected day, a highlighted title for
+    // today and a dim one for days off.
+    fn overview_block(&self, day: NaiveDate, title: String) -> Block<'static> {
+        let title_style = if day == self.clock.today() {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            default_style_text(self.calendar.is_day_off(day))
+        };
+        let border_style = if day == self.selected_day() {
+            Style::default().fg(Color::Yellow)
+        } else {
+            default_style_text(true)
+        };
+
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .title(Span::styled(title, title_style))
+    }
+
+    fn overview_footer(&self, other: &str, other_key: &str) -> Line<'static> {
+        if let Some(status) = &self.status {
+            return Line::from(Span::styled(status.clone(), Style::default().fg(Color::Yellow)));
+        }
+
+        Line::from(vec![
+            Span::raw("←/→ "),
+            Span::styled(self.tr("key.change_day"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   ↑/↓ "),
+            Span::styled(self.tr("key.change_week"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Enter "),
+            Span::styled(self.tr("key.go_to_day"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("   {other_key} ")),
+            Span::styled(self.tr(other), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.back_to_planner"), Style::default().add_modifier(Modifier::BOLD)),
+        ])
+    }
+
+    // The first three letters of the weekday, as column headers.
+    fn weekday_short(&self, day: Weekday) -> String {
+        self.texts.weekday(day).graphemes(true).take(3).collect()
+    }
+
+    fn render_conflict_view(&mut self, frame: &mut Frame) {
+        match self.conflict_return {
+            Page::History => self.render_history_view(frame, true),
+            _ => self.render_day_view(frame, true),
+        }
+
+        let Some(conflict) = self.conflicts.first() else {
+            return;
+        };
+
+        let popup_area = centered_rect(70, 50, frame.area());
+
+        frame.render_widget(Clear, popup_area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Min(0),     // Versions
+                Constraint::Length(1),  // Actions
+            ])
+            .margin(1)
+            .split(popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+
+        frame.render_widget(popup_block, popup_area);
+
+        let subtitle = match conflict {
+            Conflict::Task { .. } => self.tr_args("conflict.subtitle", &[&self.conflict_source]),
+            Conflict::Journal { day, .. } => {
+                self.tr_args("conflict.journal_subtitle", &[&self.format_date(*day), &self.conflict_source])
+            }
+        };
+        let header_text = vec![
+            title_text(&self.tr_args("conflict.title", &[&self.conflicts.len()]), false),
+            Line::from(Span::styled(subtitle, default_style_text(true))),
+        ];
+
+        frame.render_widget(Paragraph::new(header_text).alignment(Alignment::Center), layout[0]);
+
+        let versions = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(layout[1]);
+
+        let (mine, theirs) = match conflict {
+            Conflict::Task { mine, theirs } => (self.task_details(mine), self.task_details(theirs)),
+            Conflict::Journal { mine, theirs, .. } => (self.journal_version(mine), self.journal_version(theirs)),
+        };
+
+        for (area, title, lines) in [
+            (versions[0], self.texts.get("conflict.this_window"), mine),
+            (versions[1], self.conflict_source.as_str(), theirs),
+        ] {
+            let panel = Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(title));
+            frame.render_widget(panel, area);
+        }
+
+        let action_text = Line::from(vec![
+            Span::raw("m "),
+            Span::styled(self.tr("key.keep_mine"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   t "),
+            Span::styled(self.tr("key.take_theirs"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.later"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        frame.render_widget(Paragraph::new(action_text).alignment(Alignment::Center), layout[2]);
+    }
+
+    // One side of a journal conflict; `None` is a deleted entry.
+    fn journal_version(&self, text: &Option<String>) -> Vec<Line<'static>> {
+        match text {
+            Some(text) => text.lines().map(|line| Line::from(line.to_string())).collect(),
+            None => vec![Line::from(Span::styled(self.tr("conflict.deleted"), default_style_text(true)))],
+        }
+    }
+
+    fn render_prompt_view(&mut self, frame: &mut Frame) {
+        match self.prompt_return {
+            Page::History => self.render_history_view(frame, true),
+            _ => self.render_day_view(frame, true),
+        }
+
+        let popup_area = centered_rect(60, 30, frame.area());
+
+        frame.render_widget(Clear, popup_area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Length(3),  // Input
+                Constraint::Min(0),     // Actions
+            ])
+            .margin(1)
+            .split(popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+
+        frame.render_widget(popup_block, popup_area);
+
+        let (title, hint) = match self.prompt_kind {
+            PromptKind::Reschedule => ("prompt.reschedule", "prompt.reschedule_hint"),
+            PromptKind::Retag => ("prompt.retag", "prompt.retag_hint"),
+            PromptKind::Project => ("prompt.project", "prompt.project_hint"),
+        };
+        let count = self.bulk_targets().len();
+
+        let header_text = vec![
+            title_text(&self.tr_args("prompt.title", &[&self.texts.get(title), &count]), false),
+            Line::from(Span::styled(self.tr(hint), default_style_text(true))),
+        ];
+
+        frame.render_widget(Paragraph::new(header_text).alignment(Alignment::Center), layout[0]);
+
+        let input_panel = Paragraph::new(self.prompt.input.as_str())
+            .block(Block::default().borders(Borders::ALL));
+
+        frame.render_widget(input_panel, layout[1]);
+
+        let action_text = Line::from(vec![
+            Span::raw("Enter "),
+            Span::styled(self.tr("key.apply"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.cancel"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        frame.render_widget(Paragraph::new(action_text).alignment(Alignment::Center), layout[2]);
+
+        let cursor_x = self.prompt.input[..self.prompt.byte_index()].width() as u16;
+        frame.set_cursor_position(Position::new(
+            layout[1].x + 1 + cursor_x,
+            layout[1].y + 1,
+        ));
+
+        if let Some(popup) = &self.completion {
+            let word_x = self.prompt.input[..popup.word_start].width() as u16;
+            popup.render(frame, layout[1], layout[1].x + 1 + word_x);
+        }
+    }
+
+    fn render_unlock_view(&mut self, frame: &mut Frame) {
+        let popup_area = centered_rect(60, 40, frame.area());
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Header
+                Constraint::Length(3),  // Passphrase
+                Constraint::Length(2),  // Error
+                Constraint::Min(0),     // Actions
+            ])
+            .margin(1)
+            .split(popup_area);
+
+        frame.render_widget(Block::default().borders(Borders::ALL), popup_area);
+
+        let header_text = vec![
+            title_text(self.texts.get("unlock.title"), false),
+            Line::from(Span::styled(
+                self.tr_args("unlock.subtitle", &[&self.store.dir().display()]),
+                default_style_text(true),
+            )),
+        ];
+
+        frame.render_widget(Paragraph::new(header_text).alignment(Alignment::Center), layout[0]);
+
+        // The field's view shows one bullet per character typed, and
+        // scrolls once the passphrase is longer than the box.
+        let view = self.unlock_input.input_view(layout[1].width.saturating_sub(2) as usize);
+        let input_panel = Paragraph::new(view.text)
+            .block(Block::default().borders(Borders::ALL).title(self.tr("field.passphrase")));
+
+        frame.render_widget(input_panel, layout[1]);
+
+        if let Some(status) = &self.status {
+            let error_text = Paragraph::new(Span::styled(status.clone(), Style::default().fg(Color::Red)))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(error_text, layout[2]);
+        }
+
+        let action_text = Line::from(vec![
+            Span::raw("Enter "),
+            Span::styled(self.tr("key.unlock"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.quit"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        frame.render_widget(Paragraph::new(action_text).alignment(Alignment::Center), layout[3]);
+
+        frame.set_cursor_position(Position::new(
+            layout[1].x + 1 + view.cursor_x as u16,
+            layout[1].y + 1,
+        ));
+    }
+
+    fn render_passphrase_view(&mut self, frame: &mut Frame) {
+        self.render_day_view(frame, true);
+
+        let popup_area = centered_rect(60, 50, frame.area());
+
+        frame.render_widget(Clear, popup_area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Length(3),  // Current
+                Constraint::Length(3),  // New
+                Constraint::Length(3),  // Confirm
+                Constraint::Min(0),     // Actions
+            ])
+            .margin(1)
+            .split(popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+
+        frame.render_widget(popup_block, popup_area);
+
+        let hint = if self.store.is_unlocked() {
+            self.tr("passphrase.hint_encrypted")
+        } else {
+            self.tr("passphrase.hint_plain")
+        };
+        let header_text = vec![
+            title_text(self.texts.get("passphrase.title"), false),
+            Line::from(Span::styled(hint, default_style_text(true))),
+        ];
+
+        frame.render_widget(Paragraph::new(header_text).alignment(Alignment::Center), layout[0]);
+
+        let first = if self.store.is_unlocked() { 0 } else { 1 };
+        let mut cursor_x = 0;
+        for (i, label) in ["field.current", "field.new", "field.confirm"].into_iter().enumerate().skip(first) {
+            let border_style = if self.passphrase_focus == i {
+                Style::default().fg(Color::Yellow)
+            } else {
+                default_style_text(false)
+            };
+
+            let view = self.passphrase_inputs[i].input_view(layout[1 + i].width.saturating_sub(2) as usize);
+            if self.passphrase_focus == i {
+                cursor_x = view.cursor_x as u16;
+            }
+            let field_panel = Paragraph::new(view.text)
+                .style(default_style_text(false))
+                .block(Block::default().borders(Borders::ALL).border_style(border_style).title(self.tr(label)));
+
+            frame.render_widget(field_panel, layout[1 + i]);
+        }
+
+        let action_text = match &self.status {
+            Some(status) => Line::from(Span::styled(status.clone(), Style::default().fg(Color::Red))),
+            None => Line::from(vec![
+                Span::raw("Tab "),
+                Span::styled(self.tr("key.next"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   Enter "),
+                Span::styled(self.tr("key.save"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   Esc "),
+                Span::styled(self.tr("key.cancel"), Style::default().add_modifier(Modifier::BOLD)),
+            ]),
+        };
+
+        frame.render_widget(Paragraph::new(action_text).alignment(Alignment::Center), layout[4]);
+
+        let focused = &layout[1 + self.passphrase_focus];
+        frame.set_cursor_position(Position::new(
+            focused.x + 1 + cursor_x,
+            focused.y + 1,
+        ));
+    }
+
+    fn render_archive_view(&mut self, frame: &mut Frame) {
+        let container = frame.area();
+
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(4),
+                Constraint::Min(0),
+                Constraint::Length(2),
+                Constraint::Length(2),
+            ])
+            .split(container);
+
+        let header_text = vec![
+            title_text(self.texts.get("archive.title"), false),
+            Line::from(Span::styled(
+                self.tr_args("archive.subtitle", &[&self.archive.len(), &self.config.archive_after_days]),
+                default_style_text(false),
+            )),
+        ];
+
+        let items: Vec<ListItem> = self.archive
+            .iter()
+            .map(|t| {
+                let prefix = if t.done { "✓" } else { " " };
+                let (date, time) = t.localized(self.clock.zone);
+                ListItem::new(format!("{}  {} {:5} {}", self.format_date(date), prefix, time, t.text))
+            })
+            .collect();
+
+        let action_text = match (&self.pending_purge, &self.status) {
+            (Some(Purge::Selected), _) => Line::from(Span::styled(
+                self.tr("archive.confirm_one"),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            (Some(Purge::All), _) => Line::from(Span::styled(
+                self.tr_args("archive.confirm_all", &[&self.archive.len()]),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            (None, Some(status)) => Line::from(Span::styled(status.clone(), Style::default().fg(Color::Yellow))),
+            (None, None) => Line::from(Span::styled(
+                self.tr_args("archive.file", &[&self.store.archive_path().display()]),
+                default_style_text(true),
+            )),
+        };
+
+        let footer_text =  Line::from(vec![
+            Span::raw("A "),
+            Span::styled(self.tr("key.archive_now"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   r "),
+            Span::styled(self.tr("key.restore"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   p "),
+            Span::styled(self.tr("key.purge"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   P "),
+            Span::styled(self.tr("key.purge_all"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   u "),
+            Span::styled(self.tr("key.undo"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("   Esc "),
+            Span::styled(self.tr("key.back_to_planner"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        let header_panel = Paragraph::new(header_text)
+            .block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            );
+
+        let content_panel = List::new(items)
+            .highlight_symbol("▸ ")
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .block(
+                panel_block_with_padding_borders(5, 0, 1, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let action_panel = Paragraph::new(action_text)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let footer_panel = Paragraph::new(footer_text)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        frame.render_widget(header_panel, vertical_layout[0]);
+        frame.render_stateful_widget(content_panel, vertical_layout[1], &mut self.archive_state);
+        frame.render_widget(action_panel, vertical_layout[2]);
+        frame.render_widget(footer_panel, vertical_layout[3]);
+    }
+
+    fn render_backups_view(&mut self, frame: &mut Frame) {
+        let container = frame.area();
+
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(4),
+                Constraint::Min(0),
+                Constraint::Length(2),
+            ])
+            .split(container);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(vertical_layout[1]);
+
+        let header_text = vec![
+            title_text(self.texts.get("backups.title"), false),
+            Line::from(Span::styled(
+                self.tr_args(
+                    "backups.subtitle",
+                    &[&self.backups.len(), &backup::backup_dir(&self.store).display(), &self.config.backups],
+                ),
+                default_style_text(false),
+            )),
+        ];
+
+        let items: Vec<ListItem> = self.backups
+            .iter()
+            .map(|b| {
+                let taken = self.backup_taken(b);
+                match &b.contents {
+                    Ok(contents) => ListItem::new(format!("{}  {}", taken, self.tr_args("task_count", &[&contents.tasks.len()]))),
+                    Err(_) => ListItem::new(Line::from(vec![
+                        Span::raw(format!("{}  ", taken)),
+                        Span::styled(self.tr("backups.unreadable"), Style::default().fg(Color::Red)),
+                    ])),
+                }
+            })
+            .collect();
+
+        let preview_text = match self.backup_state.selected().and_then(|i| self.backups.get(i)) {
+            Some(backup) => self.backup_preview(backup),
+            None => vec![Line::from(Span::styled(self.tr("backups.none"), default_style_text(true)))],
+        };
+
+        let footer_text = match &self.status {
+            Some(status) => Line::from(Span::styled(status.clone(), Style::default().fg(Color::Yellow))),
+            None => Line::from(vec![
+                Span::raw("↑/↓ "),
+                Span::styled(self.tr("key.select"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   Enter "),
+                Span::styled(self.tr("key.restore"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("   Esc "),
+                Span::styled(self.tr("key.back_to_planner"), Style::default().add_modifier(Modifier::BOLD)),
+            ]),
+        };
+
+        let header_panel = Paragraph::new(header_text)
+            .block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            );
+
+        let list_panel = List::new(items)
+            .highlight_symbol("▸ ")
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .block(
+                panel_block_with_padding_borders(1, 0, 1, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let preview_panel = Paragraph::new(preview_text)
+            .wrap(Wrap { trim: false })
+            .block(
+                panel_block_with_padding_borders(2, 0, 1, 0, Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let footer_panel = Paragraph::new(footer_text)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        frame.render_widget(header_panel, vertical_layout[0]);
+        frame.render_stateful_widget(list_panel, content_layout[0], &mut self.backup_state);
+        frame.render_widget(preview_panel, content_layout[1]);
+        frame.render_widget(footer_panel, vertical_layout[2]);
+    }
+
+    // What restoring `backup` would change, task by task.
+    fn backup_preview(&self, backup: &Backup) -> Vec<Line<'static>> {
+        let backup = match &backup.contents {
+            Ok(contents) => contents,
+            Err(err) => {
+                return vec![
+                    Line::from(Span::styled(self.tr("backups.unreadable"), Style::default().fg(Color::Red))),
+                    Line::from(""),
+                    Line::from(Span::styled(err.clone(), default_style_text(true))),
+                ];
+            }
+        };
+        let changes = backup::diff(&self.tasks, &backup.tasks);
+        let archive_changes = backup.archive.as_ref().map_or(0, |archive| backup::diff(&self.archive, archive).len());
+        let journal_days = backup.journal
+            .keys()
+            .chain(self.journal.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|day| backup.journal.get(day) != self.journal.get(day))
+            .count();
+
+        let mut lines = vec![title_text(self.texts.get("backups.restoring"), false), Line::from("")];
+
+        for change in &changes {
+            let (sign, task, color) = match change {
+                Change::Added(task) => ("backups.bring_back", task, Color::Green),
+                Change::Removed(task) => ("backups.remove", task, Color::Red),
+                Change::Changed(task) => ("backups.change", task, Color::Yellow),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<13}", self.texts.get(sign)), Style::default().fg(color)),
+                Span::styled(
+                    format!("{} {:5} {}", self.format_date(task.date), task.time, task.text),
+                    default_style_text(false),
+                ),
+            ]));
+        }
+
+        if journal_days > 0 {
+            lines.push(Line::from(Span::styled(
+                self.tr_args("backups.journal", &[&journal_days]),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
+        if archive_changes > 0 {
+            lines.push(Line::from(Span::styled(
+                self.tr_args("backups.archive", &[&archive_changes]),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
+        if changes.is_empty() && journal_days == 0 && archive_changes == 0 {
+            lines.push(Line::from(Span::styled(self.tr("backups.same"), default_style_text(true))));
+        }
+
+        lines
+    }
+
+    fn task_details(&self, task: &Task) -> Vec<Line<'static>> {
+        let texts = &self.texts;
+        let due_format = format!("{} %H:%M", self.config.date_format);
+        let (date, time) = task.localized(self.clock.zone);
+        vec![
+            preview_line(texts.get("field.title"), task.text.clone()),
+            preview_line(texts.get("field.date"), self.format_date(date)),
+            preview_line(texts.get("field.time"), time),
+            preview_line(texts.get("field.zone"), task.zone.map_or_else(|| self.tr("zone.floating"), |zone| zone.to_string())),
+            preview_line(texts.get("field.done"), self.tr(if task.done { "yes" } else { "no" })),
+            preview_line(texts.get("field.tags"), task.tags.iter().map(|tag| format!("#{tag}")).collect::<Vec<_>>().join(" ")),
+            preview_line(texts.get("field.priority"), texts.priority(task.priority).into()),
+            preview_line(texts.get("field.project"), task.project.clone()),
+            preview_line(texts.get("field.repeat"), task.recurrence.map(|r| texts.recurrence(r)).unwrap_or_default()),
+            preview_line(texts.get("field.due"), task.due.map(|due| due.with_timezone(&self.clock.zone).format(&due_format).to_string()).unwrap_or_default()),
+            preview_line(texts.get("field.notes"), task.annotations.iter().map(|note| note.text.as_str()).collect::<Vec<_>>().join(" · ")),
+        ]
+    }
+
+    fn render_help_view(&self, frame: &mut Frame, dim: bool) {
+        let container = frame.area();
+
+        let vertical_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ]).split(container);
+
+        let header_text = title_text(self.texts.get("help.title"), dim);
+
+        let content_text: Vec<Line> = [
+            ("←/→", "help.change_day"),
+            ("↑/↓", "help.move_selection"),
+            ("Enter", "help.toggle_done"),
+            ("Space", "help.mark"),
+            ("d", "help.bulk"),
+            ("", "help.bulk_targets"),
+            ("u", "help.undo"),
+            ("K/J", "help.reorder"),
+            ("n", "help.new"),
+            ("t", "help.timeline"),
+            ("↑/↓", "help.free_slot"),
+            ("j", "help.journal"),
+            ("/", "help.search"),
+            ("x", "help.export"),
+            ("S", "help.sync"),
+            ("C", "help.conflicts"),
+            ("E", "help.encrypt"),
+            ("B", "help.backups"),
+            ("A", "help.archive"),
+            ("Enter", "help.add_at_slot"),
+            ("a", "help.quick_add"),
+            ("h", "help.history"),
+            ("w", "help.week"),
+            ("m", "help.month"),
+            ("q", "help.quit"),
+        ]
+        .into_iter()
+        .map(|(key, text)| Line::from(format!("{key:<7}{}", self.texts.get(text))))
+        .collect();
+
+        let footer_text =  Line::from(vec![
+            Span::raw("Esc "),
+            Span::styled(self.tr("key.back"), Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+
+        let header_panel = Paragraph::new(header_text)
+            .block(
+                panel_block_with_padding_borders(2,0,0,0, Borders::LEFT | Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+            );
+
+        let content_panel = Paragraph::new(content_text)
+            .block(
+                panel_block_with_padding_borders(7, 0, 2, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        let footer_panel = Paragraph::new(footer_text)
+            .block(
+                panel_block_with_padding_borders(2, 0, 0, 0, Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            );
+
+        frame.render_widget(header_panel, vertical_layout[0]);
+        frame.render_widget(content_panel, vertical_layout[1]);
+        frame.render_widget(footer_panel, vertical_layout[2]);
+    }
+
+}
+
+// `--import-taskwarrior FILE` merges a `task export` into the store and
+// `--export-taskwarrior FILE` writes one for `task import`, without
+// starting the UI; `-` stands for stdin or stdout.
+fn run_command(mut app: App, args: &[String]) -> Result<()> {
+    if app.store.is_encrypted() {
+        let passphrase = std::env::var(store::PASSPHRASE_ENV)
+            .map_err(|_| eyre!("the store is encrypted; set ${} to unlock it", store::PASSPHRASE_ENV))?;
+        app.store.unlock(&passphrase)?;
+    }
+    app.load()?;
+
+    match args {
+        [flag, path] if flag == "--import-taskwarrior" => {
+            let json = if path == "-" {
+                let mut json = String::new();
+                io::stdin().read_to_string(&mut json)?;
+                json
+            } else {
+                fs::read_to_string(path).wrap_err_with(|| format!("reading {path}"))?
+            };
+
+            let summary = taskwarrior::import(&mut app.tasks, &mut app.archive, &json, app.clock.zone)?;
+            app.dirty = true;
+            app.archive_dirty = summary.archive_changed;
+            app.sync_store()?;
+            eprintln!("{} added, {} updated, {} removed", summary.added, summary.updated, summary.removed);
+        }
+        [flag, path] if flag == "--export-taskwarrior" => {
+            let tasks: Vec<Task> = app.tasks.iter().chain(&app.archive).cloned().collect();
+            let json = taskwarrior::export(&tasks, app.clock.zone)?;
+            if path == "-" {
+                writeln!(io::stdout(), "{json}")?;
+            } else {
+                fs::write(path, json + "\n").wrap_err_with(|| format!("writing {path}"))?;
+                eprintln!("{} task(s) exported to {path}", tasks.len());
+            }
+        }
+        _ => return Err(eyre!("usage: ex05_todo_app [--tz ZONE] [--today YYYY-MM-DD] [--import-taskwarrior FILE | --export-taskwarrior FILE]")),
+    }
+
+    Ok(())
+}
+
+// Removes `name VALUE` from `args` and returns the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let Some(i) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if i + 1 == args.len() {
+        return Err(eyre!("{name} needs a value"));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    // `--tz` shows the planner in another zone and `--today` pins the
+    // date it treats as today, e.g. to try things out.
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let zone = take_option(&mut args, "--tz")?.map(|name| clock::parse_zone(&name)).transpose()?;
+    let today = take_option(&mut args, "--today")?.map(|date| clock::parse_today(&date)).transpose()?;
+
+    let mut app = App::new();
+    let config = config::load(app.store.dir())?;
+    app.clock = Clock::resolve(zone, today, config.time_zone)?;
+    app.calendar = Calendar::load(
+        config::path(app.store.dir()).parent().unwrap_or(app.store.dir()),
+        &config.holidays,
+        config.working_days.clone(),
+        config.skip_holidays,
+    )?;
+    app.set_config(config);
+
+    if !args.is_empty() {
+        return run_command(app, &args);
+    }
+
+    if app.store.is_encrypted() {
+        app.page = Page::Unlock;
+    } else {
+        app.load()?;
+    }
+
+    let mut terminal = Session::start()?.with_bracketed_paste()?;
+
+    loop {
+        terminal.draw(|f| {
+
+            match app.page {
+                Page::Day => app.render_day_view(f, false),
+                Page::Input => app.render_input_view(f),
+                Page::QuickAdd => app.render_quick_add_view(f),
+                Page::Journal => app.render_journal_view(f),
+                Page::Search => app.render_search_view(f),
+                Page::History => app.render_history_view(f, false),
+                Page::Week => app.render_week_view(f),
+                Page::Month => app.render_month_view(f),
+                Page::Archive => app.render_archive_view(f),
+                Page::Prompt => app.render_prompt_view(f),
+                Page::Conflict => app.render_conflict_view(f),
+                Page::Unlock => app.render_unlock_view(f),
+                Page::Passphrase => app.render_passphrase_view(f),
+                Page::Backups => app.render_backups_view(f),
+                Page::Help => app.render_help_view(f, false),
+            }
+
+        })?;
+
+        if !event::poll(WATCH_INTERVAL)? {
+            app.save_if_dirty();
+            continue;
+        }
+
+        let event = event::read()?;
+        if let Event::Paste(text) = &event {
+            app.status = None;
+            app.handle_paste(text);
+        }
+
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press {
+            app.status = None;
+
+            let handled = match app.page {
+                Page::QuickAdd => {
+                    app.handle_quick_add_key(key.code);
+                    true
+                }
+                Page::Journal => {
+                    app.handle_journal_key(key);
+                    true
+                }
+                Page::Search => {
+                    app.handle_search_key(key.code);
+                    true
+                }
+                Page::Prompt => {
+                    app.handle_prompt_key(key.code);
+                    true
+                }
+                Page::Conflict => {
+                    app.handle_conflict_key(key.code);
+                    true
+                }
+                Page::Unlock if key.code == KeyCode::Esc => break,
+                Page::Unlock => {
+                    app.handle_unlock_key(key.code);
+                    true
+                }
+                Page::Passphrase => {
+                    app.handle_passphrase_key(key.code);
+                    true
+                }
+                Page::Archive => app.handle_archive_key(key.code),
+                Page::Backups => app.handle_backups_key(key.code),
+                Page::Week | Page::Month => app.handle_overview_key(key.code),
+                Page::Day if app.day_view == DayView::List => app.handle_list_key(key),
+                Page::History => app.handle_list_key(key),
+                _ => false,
+            };
+
+            if !handled {
+                match key.code {
+                    KeyCode::Char('q')  => break,
+                    KeyCode::Char('n') => app.page = Page::Input,
+                    KeyCode::Char('a') => app.open_quick_add(),
+                    KeyCode::Char('/') => app.open_search(),
+                    KeyCode::Esc => app.page = Page::Day,
+                    KeyCode::Char('h') => {
+                        app.clear_marks();
+                        app.history_state.select(Some(0));
+                        app.page = Page::History;
+                    }
+                    KeyCode::Char('?') => app.page = Page::Help,
+                    _ => {}
+                }
+
+                if let Page::Day = app.page {
+                    match key.code {
+                        KeyCode::Left => app.change_day(-1),
+                        KeyCode::Right => app.change_day(1),
+                        KeyCode::Char('t') => app.toggle_day_view(),
+                        KeyCode::Char('j') => app.open_journal(),
+                        KeyCode::Char('x') => app.export_markdown(),
+                        KeyCode::Char('A') => app.open_archive(),
+                        KeyCode::Char('S') => app.sync_remote(),
+                        KeyCode::Char('C') => app.reopen_conflicts(),
+                        KeyCode::Char('E') => app.open_passphrase(),
+                        KeyCode::Char('B') => app.open_backups(),
+                        KeyCode::Char('w') => app.page = Page::Week,
+                        KeyCode::Char('m') => app.page = Page::Month,
+                        _ => {}
+                    }
+
+                    if app.day_view == DayView::Timeline {
+                        match key.code {
+                            KeyCode::Up => app.move_timeline_cursor(-1),
+                            KeyCode::Down => app.move_timeline_cursor(1),
+                            KeyCode::Enter => app.open_quick_add_at_cursor(),
+                            _ => {}
+                        }
+                    }
+                }
+
+                if let Page::History = app.page {
+                    match key.code {
+                        KeyCode::Char('i') => app.history_include_archived = !app.history_include_archived,
+                        KeyCode::Char('A') => app.open_archive(),
+                        _ => {}
+                    }
+                }
+            }
+
+            app.save_if_dirty();
+        }
+    }
+
+    terminal.finish()?;
+
+    Ok(())
+
+}
+
+fn preview_line(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{label:<10}"), default_style_text(true)),
+        Span::styled(value, default_style_text(false)),
+    ])
+}
+
+fn token_style(kind: TokenKind) -> Style {
+    match kind {
+        TokenKind::Text => default_style_text(false),
+        TokenKind::Date | TokenKind::Time | TokenKind::Duration => Style::default().fg(Color::Cyan),
+        TokenKind::Tag => Style::default().fg(Color::Magenta),
+        TokenKind::Priority => Style::default().fg(Color::Yellow),
+        TokenKind::Recurrence => Style::default().fg(Color::Green),
+        TokenKind::Unparsed => Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::UNDERLINED),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn old_done_task(text: &str) -> Task {
+        let mut task = Task::new(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(), "", text);
+        task.done = true;
+        task
+    }
+
+    fn texts(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.text.as_str()).collect()
+    }
+
+    #[test]
+    fn undoing_an_archive_takes_the_tasks_back_out_of_it() {
+        let mut app = App::new();
+        app.tasks = vec![old_done_task("old"), Task::new(app.clock.today(), "", "current")];
+
+        app.archive_old_tasks();
+        assert_eq!(texts(&app.tasks), ["current"]);
+        assert_eq!(texts(&app.archive), ["old"]);
+
+        app.undo();
+        assert_eq!(texts(&app.tasks), ["old", "current"]);
+        assert!(app.archive.is_empty());
+        assert!(app.archive_dirty);
+    }
+
+    #[test]
+    fn undo_after_a_restore_keeps_the_restored_task() {
+        let mut app = App::new();
+        app.tasks = vec![Task::new(app.clock.today(), "", "a")];
+        app.archive = vec![old_done_task("b")];
+        app.archive_state.select(Some(0));
+
+        // Some change, then a restore, then undo: only the restore is undone.
+        app.record_undo();
+        app.tasks[0].done = true;
+        app.restore_from_archive();
+        assert_eq!(texts(&app.tasks), ["a", "b"]);
+
+        app.undo();
+        assert_eq!(texts(&app.tasks), ["a"]);
+        assert_eq!(texts(&app.archive), ["b"]);
+        assert!(app.tasks[0].done);
+
+        app.undo();
+        assert!(!app.tasks[0].done);
+        assert_eq!(texts(&app.archive), ["b"]);
+    }
+
+    #[test]
+    fn a_purge_can_be_undone() {
+        let mut app = App::new();
+        app.archive = vec![old_done_task("b"), old_done_task("c")];
+        app.archive_state.select(Some(0));
+
+        app.purge(Purge::All);
+        assert!(app.archive.is_empty());
+        app.undo();
+        assert_eq!(texts(&app.archive), ["b", "c"]);
+        assert_eq!(app.archive_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn undoing_a_backup_restore_brings_the_journal_back() {
+        let mut app = App::new();
+        let day = app.clock.today();
+        app.tasks = vec![Task::new(day, "", "now")];
+        app.journal.insert(day, "written today".to_string());
+        app.backups = vec![Backup {
+            modified: None,
+            contents: Ok(backup::Contents {
+                tasks: vec![Task::new(day, "", "then")],
+                journal: Journal::new(),
+                archive: None,
+            }),
+        }];
+        app.backup_state.select(Some(0));
+
+        app.restore_backup();
+        assert_eq!(texts(&app.tasks), ["then"]);
+        assert!(app.journal.is_empty());
+
+        app.dirty = false;
+        app.undo();
+        assert_eq!(texts(&app.tasks), ["now"]);
+        assert_eq!(app.journal.get(&day).map(String::as_str), Some("written today"));
+        assert!(app.dirty);
+    }
+
+    #[test]
+    fn conflicts_left_for_later_can_be_reopened() {
+        let mut app = App::new();
+        let day = app.clock.today();
+        let conflict = |text: &str| Conflict::Journal { day, mine: Some("mine".into()), theirs: Some(text.into()) };
+        app.show_conflicts(vec![conflict("one"), conflict("two")], "origin".into());
+        app.pending_push = true;
+
+        app.resolve_conflict(true);
+        app.handle_conflict_key(KeyCode::Esc);
+        assert!(app.page == Page::Day);
+        assert_eq!(app.conflicts.len(), 1);
+        assert!(app.pending_push);
+
+        app.reopen_conflicts();
+        assert!(app.page == Page::Conflict);
+        app.resolve_conflict(false);
+        assert!(app.page == Page::Day);
+        assert_eq!(app.journal.get(&day).map(String::as_str), Some("mine"));
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_journal_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn the_journal_editor_saves_on_close_and_removes_cleared_entries() {
+        let mut app = App::new();
+        let day = app.selected_day();
+        app.journal.insert(day, "Slept badly".into());
+
+        app.open_journal();
+        assert_eq!(app.journal_editor.value(), "Slept badly");
+        app.handle_paste("\r\nStandup ran long 👩‍💻");
+        // One Backspace removes the whole emoji cluster.
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Esc);
+
+        assert!(app.page == Page::Day);
+        assert!(app.dirty);
+        // Trailing line breaks are dropped.
+        assert_eq!(app.journal.get(&day).map(String::as_str), Some("Slept badly\nStandup ran long"));
+
+        app.open_journal();
+        app.journal_editor.clear();
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.journal.contains_key(&day));
+    }
+
+    // A window on its own clone of `remote`, with a fresh store in it.
+    fn clone_app(remote: &std::path::Path, dir: &std::path::Path) -> App {
+        let status = std::process::Command::new("git")
+            .args(["clone", "--quiet"])
+            .arg(remote)
+            .arg(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let mut app = App::new();
+        app.store = Store::new(dir);
+        app.load().unwrap();
+        app
+    }
+
+    #[test]
+    fn pulling_diverged_clones_merges_task_by_task() {
+        let root = std::env::temp_dir().join(format!("ex05-git-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let remote = root.join("remote.git");
+        let status = std::process::Command::new("git").args(["init", "--quiet", "--bare"]).arg(&remote).status().unwrap();
+        assert!(status.success());
+
+        let mut a = clone_app(&remote, &root.join("a"));
+        let mut b = clone_app(&remote, &root.join("b"));
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+
+        a.tasks = vec![Task::new(day, "09:00", "shared")];
+        a.journal.insert(day, "from the start".into());
+        a.dirty = true;
+        a.sync_remote();
+        b.sync_remote();
+        assert_eq!(texts(&b.tasks), ["shared"]);
+
+        // A edits the shared task and the journal; B adds a task and
+        // edits the same journal day without pulling first.
+        a.tasks[0].text = "shared, edited in a".into();
+        a.journal.insert(day, "written in a".into());
+        a.dirty = true;
+        a.sync_remote();
+
+        b.tasks.push(Task::new(day, "10:00", "added in b"));
+        b.journal.insert(day, "written in b".into());
+        b.dirty = true;
+        b.sync_remote();
+
+        assert_eq!(texts(&b.tasks), ["shared, edited in a", "added in b"]);
+        assert!(b.page == Page::Conflict);
+        assert!(matches!(
+            &b.conflicts[..],
+            [Conflict::Journal { mine: Some(mine), theirs: Some(theirs), .. }] if mine == "written in b" && theirs == "written in a"
+        ));
+
+        // Taking theirs finishes the merge and pushes it for A to pull.
+        b.resolve_conflict(true);
+        assert_eq!(b.journal.get(&day).map(String::as_str), Some("written in a"));
+        a.sync_remote();
+        assert_eq!(texts(&a.tasks), ["shared, edited in a", "added in b"]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    // The screen as text, one string per row.
+    fn render(app: &mut App, page: Page) -> Vec<String> {
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 40)).unwrap();
+        app.page = page;
+        terminal
+            .draw(|frame| match app.page {
+                Page::Week => app.render_week_view(frame),
+                _ => app.render_month_view(frame),
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn week_and_month_views_show_holiday_names() {
+        let dir = std::env::temp_dir().join(format!("ex05-overview-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("holidays.csv"), "2026-12-25,Xmas\n").unwrap();
+
+        let mut app = App::new();
+        app.calendar = Calendar::load(&dir, &[std::path::PathBuf::from("holidays.csv")], vec![Weekday::Mon], false).unwrap();
+        app.clock = Clock::resolve(Some(chrono_tz::UTC), NaiveDate::from_ymd_opt(2026, 12, 23), None).unwrap();
+        app.tasks = vec![Task::new(NaiveDate::from_ymd_opt(2026, 12, 24).unwrap(), "18:00", "Wrap")];
+
+        let week = render(&mut app, Page::Week).join("\n");
+        assert!(week.contains("★ Xmas"), "{week}");
+        assert!(week.contains("18:00 Wrap"), "{week}");
+
+        let month = render(&mut app, Page::Month).join("\n");
+        assert!(month.contains("December 2026"), "{month}");
+        assert!(month.contains("★ Xmas"), "{month}");
+        assert!(month.contains("1 task(s)"), "{month}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+
+use crate::store::{Journal, Snapshot};
+use crate::task::Task;
+
+// A task or journal day changed differently here and in another window
+// since the store was last read. A journal side is `None` when that side
+// deleted the entry.
+pub enum Conflict {
+    Task { mine: Box<Task>, theirs: Box<Task> },
+    Journal { day: NaiveDate, mine: Option<String>, theirs: Option<String> },
+}
+
+pub struct Merged {
+    pub tasks: Vec<Task>,
+    pub conflicts: Vec<Conflict>,
+}
+
+// Three-way merge by task id against `base`, the store as last read or
+// written. A change on one side wins over an untouched task on the other;
+// an edit wins over a delete. When both sides edited a task, ours is kept
+// and the pair is reported as a conflict.
+pub fn merge(base: &[Task], mine: &[Task], theirs: &[Task]) -> Merged {
+    let find = |tasks: &[Task], id: &str| tasks.iter().find(|t| t.id == id).cloned();
+    let mut tasks = vec![];
+    let mut conflicts = vec![];
+
+    for task in mine {
+        match (find(base, &task.id), find(theirs, &task.id)) {
+            (_, Some(their)) if their == *task => tasks.push(task.clone()),
+            (Some(old), Some(their)) if old == *task => tasks.push(their),
+            (Some(old), Some(their)) if old == their => tasks.push(task.clone()),
+            (_, Some(their)) => {
+                tasks.push(task.clone());
+                conflicts.push(Conflict::Task { mine: Box::new(task.clone()), theirs: Box::new(their) });
+            }
+            (Some(old), None) if old == *task => {}
+            (_, None) => tasks.push(task.clone()),
+        }
+    }
+
+    for their in theirs.iter().filter(|t| find(mine, &t.id).is_none()) {
+        match find(base, &their.id) {
+            Some(old) if old == *their => {}
+            _ => tasks.push(their.clone()),
+        }
+    }
+
+    Merged { tasks, conflicts }
+}
+
+pub struct MergedStore {
+    pub tasks: Vec<Task>,
+    pub archive: Vec<Task>,
+    pub journal: Journal,
+    pub conflicts: Vec<Conflict>,
+}
+
+// Merges tasks, archive and journal together. Archiving moves a task
+// from one list to the other, so merging the lists on their own would
+// keep a task edited on one side and archived on the other in both; like
+// any edit over a delete, the edit wins and the task stays in the
+// planner, where it can be archived again. Conflicts between two edits
+// of an archived task keep ours without asking.
+pub fn merge_store(base: &Snapshot, mine: &Snapshot, theirs: &Snapshot) -> MergedStore {
+    let tasks = merge(base.tasks, mine.tasks, theirs.tasks);
+    let mut archive = merge(base.archive, mine.archive, theirs.archive).tasks;
+    let (journal, journal_conflicts) = merge_journal(base.journal, mine.journal, theirs.journal);
+
+    archive.retain(|archived| !tasks.tasks.iter().any(|t| t.id == archived.id));
+
+    MergedStore {
+        tasks: tasks.tasks,
+        archive,
+        journal,
+        conflicts: tasks.conflicts.into_iter().chain(journal_conflicts).collect(),
+    }
+}
+
+// Same rules for journal entries; when both sides edited a day, ours is
+// kept and the pair is reported as a conflict.
+pub fn merge_journal(base: &Journal, mine: &Journal, theirs: &Journal) -> (Journal, Vec<Conflict>) {
+    let days: BTreeSet<NaiveDate> = mine.keys().chain(theirs.keys()).copied().collect();
+    let mut journal = Journal::new();
+    let mut conflicts = vec![];
+
+    for day in days {
+        let text = match (base.get(&day), mine.get(&day), theirs.get(&day)) {
+            (_, m, t) if m == t => m,
+            (b, m, t) if b == m => t,
+            (b, m, t) if b == t => m,
+            (_, m, t) => {
+                conflicts.push(Conflict::Journal { day, mine: m.cloned(), theirs: t.cloned() });
+                m.or(t)
+            }
+        };
+        if let Some(text) = text {
+            journal.insert(day, text.clone());
+        }
+    }
+
+    (journal, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `("a", "1")` is task `a` with the text `1`.
+    type Spec = &'static [(&'static str, &'static str)];
+    // A store as (tasks, archive).
+    type Lists = (Spec, Spec);
+
+    fn tasks(spec: Spec) -> Vec<Task> {
+        spec.iter()
+            .map(|(id, text)| {
+                let mut task = Task::new(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), "", text);
+                task.id = id.to_string();
+                task
+            })
+            .collect()
+    }
+
+    fn spec(tasks: &[Task]) -> Vec<(String, String)> {
+        let mut spec: Vec<_> = tasks.iter().map(|t| (t.id.clone(), t.text.clone())).collect();
+        spec.sort();
+        spec
+    }
+
+    fn snapshot<'a>((tasks, archive): &'a (Vec<Task>, Vec<Task>), journal: &'a Journal) -> Snapshot<'a> {
+        Snapshot { tasks, archive, journal }
+    }
+
+    fn owned(spec: Spec) -> Vec<(String, String)> {
+        spec.iter().map(|(id, text)| (id.to_string(), text.to_string())).collect()
+    }
+
+    #[test]
+    fn merges_tasks_and_archive_by_id() {
+        // (case, [base, mine, theirs, expected], conflicts expected)
+        let cases: &[(&str, [Lists; 4], usize)] = &[
+            ("added here", [(&[], &[]), (&[("a", "1")], &[]), (&[], &[]), (&[("a", "1")], &[])], 0),
+            ("added there", [(&[], &[]), (&[], &[]), (&[("a", "1")], &[]), (&[("a", "1")], &[])], 0),
+            ("edited here", [(&[("a", "1")], &[]), (&[("a", "2")], &[]), (&[("a", "1")], &[]), (&[("a", "2")], &[])], 0),
+            ("edited there", [(&[("a", "1")], &[]), (&[("a", "1")], &[]), (&[("a", "2")], &[]), (&[("a", "2")], &[])], 0),
+            ("deleted here", [(&[("a", "1")], &[]), (&[], &[]), (&[("a", "1")], &[]), (&[], &[])], 0),
+            ("deleted there", [(&[("a", "1")], &[]), (&[("a", "1")], &[]), (&[], &[]), (&[], &[])], 0),
+            ("edited here, deleted there", [(&[("a", "1")], &[]), (&[("a", "2")], &[]), (&[], &[]), (&[("a", "2")], &[])], 0),
+            ("deleted here, edited there", [(&[("a", "1")], &[]), (&[], &[]), (&[("a", "2")], &[]), (&[("a", "2")], &[])], 0),
+            ("same edit on both sides", [(&[("a", "1")], &[]), (&[("a", "2")], &[]), (&[("a", "2")], &[]), (&[("a", "2")], &[])], 0),
+            ("edited on both sides", [(&[("a", "1")], &[]), (&[("a", "2")], &[]), (&[("a", "3")], &[]), (&[("a", "2")], &[])], 1),
+            ("archived here", [(&[("a", "1")], &[]), (&[], &[("a", "1")]), (&[("a", "1")], &[]), (&[], &[("a", "1")])], 0),
+            ("archived there", [(&[("a", "1")], &[]), (&[("a", "1")], &[]), (&[], &[("a", "1")]), (&[], &[("a", "1")])], 0),
+            ("edited here, archived there", [(&[("a", "1")], &[]), (&[("a", "2")], &[]), (&[], &[("a", "1")]), (&[("a", "2")], &[])], 0),
+            ("archived here, edited there", [(&[("a", "1")], &[]), (&[], &[("a", "1")]), (&[("a", "2")], &[]), (&[("a", "2")], &[])], 0),
+            ("restored there", [(&[], &[("a", "1")]), (&[], &[("a", "1")]), (&[("a", "1")], &[]), (&[("a", "1")], &[])], 0),
+            ("purged there", [(&[], &[("a", "1")]), (&[], &[("a", "1")]), (&[], &[]), (&[], &[])], 0),
+            (
+                "untouched tasks stay put",
+                [(&[("a", "1"), ("b", "1")], &[("c", "1")]), (&[("a", "2"), ("b", "1")], &[("c", "1")]), (&[("a", "1"), ("b", "1")], &[("c", "1")]), (&[("a", "2"), ("b", "1")], &[("c", "1")])],
+                0,
+            ),
+        ];
+
+        let journal = Journal::new();
+        for (case, [base, mine, theirs, expected], conflicts) in cases {
+            let [base, mine, theirs] = [base, mine, theirs].map(|(t, a)| (tasks(t), tasks(a)));
+            let merged = merge_store(&snapshot(&base, &journal), &snapshot(&mine, &journal), &snapshot(&theirs, &journal));
+
+            assert_eq!(spec(&merged.tasks), owned(expected.0), "tasks: {case}");
+            assert_eq!(spec(&merged.archive), owned(expected.1), "archive: {case}");
+            assert_eq!(merged.conflicts.len(), *conflicts, "conflicts: {case}");
+        }
+    }
+
+    #[test]
+    fn merges_journal_days() {
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let journal = |text: Option<&str>| -> Journal { text.map(|text| (day, text.to_string())).into_iter().collect() };
+
+        // (case, base, mine, theirs, expected, conflict)
+        let cases = [
+            ("written here", None, Some("a"), None, Some("a"), false),
+            ("written there", None, None, Some("a"), Some("a"), false),
+            ("edited here", Some("a"), Some("b"), Some("a"), Some("b"), false),
+            ("edited there", Some("a"), Some("a"), Some("b"), Some("b"), false),
+            ("deleted there", Some("a"), Some("a"), None, None, false),
+            ("edited here, deleted there", Some("a"), Some("b"), None, Some("b"), true),
+            ("deleted here, edited there", Some("a"), None, Some("b"), Some("b"), true),
+            ("edited on both sides", Some("a"), Some("b"), Some("c"), Some("b"), true),
+        ];
+
+        for (case, base, mine, theirs, expected, conflict) in cases {
+            let (merged, conflicts) = merge_journal(&journal(base), &journal(mine), &journal(theirs));
+
+            assert_eq!(merged, journal(expected), "{case}");
+            assert_eq!(!conflicts.is_empty(), conflict, "{case}");
+        }
+    }
+}
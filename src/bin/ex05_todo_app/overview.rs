@@ -0,0 +1,97 @@
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+// The seven days of the week that holds `day`, starting on `week_start`.
+// At the ends of the calendar the days that don't exist repeat the first
+// or last one.
+pub fn week(day: NaiveDate, week_start: Weekday) -> [NaiveDate; 7] {
+    let offset = (7 + day.weekday().num_days_from_monday() - week_start.num_days_from_monday()) % 7;
+    let first = day.checked_sub_days(Days::new(offset.into())).unwrap_or(NaiveDate::MIN);
+    std::array::from_fn(|i| first.checked_add_days(Days::new(i as u64)).unwrap_or(NaiveDate::MAX))
+}
+
+// The weeks that hold a day of `day`'s month, each starting on
+// `week_start`; days of the months around it fill the first and last week.
+pub fn month(day: NaiveDate, week_start: Weekday) -> Vec<[NaiveDate; 7]> {
+    let mut weeks = vec![week(day.with_day(1).unwrap_or(day), week_start)];
+
+    while let Some(next) = weeks.last().and_then(|last| last[6].succ_opt())
+        && next.month() == day.month() {
+        weeks.push(week(next, week_start));
+    }
+
+    weeks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn a_week_runs_from_its_first_day() {
+        // 2024-05-01 is a Wednesday.
+        let days = week(date(2024, 5, 1), Weekday::Mon);
+
+        assert_eq!(days[0], date(2024, 4, 29));
+        assert_eq!(days[6], date(2024, 5, 5));
+        assert_eq!(week(date(2024, 4, 29), Weekday::Mon), days);
+        assert_eq!(week(date(2024, 5, 5), Weekday::Mon), days);
+    }
+
+    #[test]
+    fn the_week_starts_on_the_configured_day() {
+        let sunday = week(date(2024, 5, 1), Weekday::Sun);
+        let saturday = week(date(2024, 5, 1), Weekday::Sat);
+
+        assert_eq!((sunday[0], sunday[6]), (date(2024, 4, 28), date(2024, 5, 4)));
+        assert_eq!((saturday[0], saturday[6]), (date(2024, 4, 27), date(2024, 5, 3)));
+        // A day that is itself the week start opens its week.
+        assert_eq!(week(date(2024, 4, 28), Weekday::Sun)[0], date(2024, 4, 28));
+    }
+
+    #[test]
+    fn a_month_is_covered_by_whole_weeks() {
+        let weeks = month(date(2024, 5, 17), Weekday::Mon);
+
+        assert_eq!(weeks.len(), 5);
+        assert_eq!(weeks[0][0], date(2024, 4, 29));
+        assert_eq!(weeks[4][6], date(2024, 6, 2));
+        assert!(weeks.windows(2).all(|pair| pair[0][6].succ_opt() == Some(pair[1][0])));
+    }
+
+    #[test]
+    fn the_month_grid_follows_the_week_start() {
+        // June 2024 starts on a Saturday: a Sunday-first grid needs six rows.
+        let weeks = month(date(2024, 6, 1), Weekday::Sun);
+
+        assert_eq!(weeks.len(), 6);
+        assert_eq!(weeks[0][0], date(2024, 5, 26));
+        assert_eq!(weeks[0][0].weekday(), Weekday::Sun);
+        assert_eq!(weeks[5][6], date(2024, 7, 6));
+    }
+
+    #[test]
+    fn the_ends_of_the_calendar_do_not_panic() {
+        let first = week(NaiveDate::MIN, Weekday::Mon);
+        assert_eq!(first[0], NaiveDate::MIN);
+
+        let last = week(NaiveDate::MAX, Weekday::Mon);
+        assert_eq!(last[6], NaiveDate::MAX);
+        assert!(last.contains(&NaiveDate::MAX));
+
+        assert_eq!(month(NaiveDate::MAX, Weekday::Sun).last().unwrap()[6], NaiveDate::MAX);
+        assert_eq!(month(NaiveDate::MIN, Weekday::Sun)[0][0], NaiveDate::MIN);
+    }
+
+    #[test]
+    fn a_month_that_fills_its_weeks_exactly_takes_four() {
+        // February 2021 starts on a Monday and has 28 days.
+        let weeks = month(date(2021, 2, 10), Weekday::Mon);
+
+        assert_eq!(weeks.len(), 4);
+        assert_eq!((weeks[0][0], weeks[3][6]), (date(2021, 2, 1), date(2021, 2, 28)));
+    }
+}
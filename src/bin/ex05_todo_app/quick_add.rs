@@ -0,0 +1,427 @@
+use chrono::{Datelike, Days, Duration, Months, NaiveDate, NaiveTime, Weekday};
+
+use crate::task::{Priority, Recurrence, Task};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenKind {
+    Text,
+    Date,
+    Time,
+    Duration,
+    Tag,
+    Priority,
+    Recurrence,
+    Unparsed,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub text: String,
+    pub kind: TokenKind,
+}
+
+pub struct Parsed {
+    pub task: Task,
+    pub tokens: Vec<Token>,
+}
+
+impl Parsed {
+    pub fn has_unparsed(&self) -> bool {
+        self.tokens.iter().any(|t| t.kind == TokenKind::Unparsed)
+    }
+}
+
+// Parses phrases like "tomorrow 14:00 review PR #work !high" into a task.
+// Relative dates are resolved against `today`; without a date the task lands
+// on `default_date`. Words that look like a date, time, priority or
+// recurrence but cannot be read are kept in the title and reported as
+// `TokenKind::Unparsed` so the preview can highlight them. "next week" is
+// the `week_start` day of the following week.
+pub fn parse(input: &str, today: NaiveDate, default_date: NaiveDate, week_start: Weekday) -> Parsed {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut tokens = vec![];
+    let mut date = None;
+    let mut time = None;
+    let mut duration = None;
+    let mut tags: Vec<String> = vec![];
+    let mut priority = None;
+    let mut recurrence = None;
+    let mut title = vec![];
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        let lower = word.to_lowercase();
+        let next = words.get(i + 1).map(|w| w.to_lowercase());
+        let after = words.get(i + 2).map(|w| w.to_lowercase());
+
+        let (kind, used) = if let Some((d, used)) = parse_date(&lower, next.as_deref(), after.as_deref(), today, week_start) {
+            match d {
+                Some(d) => (set_once(&mut date, d, TokenKind::Date), used),
+                None => (TokenKind::Unparsed, used),
+            }
+        } else if lower == "at" && next.as_deref().and_then(parse_time).is_some() {
+            let t = next.as_deref().and_then(parse_time).unwrap();
+            (set_once(&mut time, t, TokenKind::Time), 2)
+        } else if let Some(t) = parse_time(&lower) {
+            (set_once(&mut time, t, TokenKind::Time), 1)
+        } else if lower == "for" && next.as_deref().and_then(parse_duration).is_some() {
+            let d = next.as_deref().and_then(parse_duration).unwrap();
+            (set_once(&mut duration, d, TokenKind::Duration), 2)
+        } else if let Some(d) = parse_duration(&lower) {
+            (set_once(&mut duration, d, TokenKind::Duration), 1)
+        } else if looks_like_time(&lower) || looks_like_date(&lower) || looks_like_duration(&lower) {
+            (TokenKind::Unparsed, 1)
+        } else if let Some(tag) = word.strip_prefix('#') {
+            if tag.is_empty() {
+                (TokenKind::Unparsed, 1)
+            } else {
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+                (TokenKind::Tag, 1)
+            }
+        } else if let Some(level) = lower.strip_prefix('!') {
+            match parse_priority(level) {
+                Some(p) => (set_once(&mut priority, p, TokenKind::Priority), 1),
+                None => (TokenKind::Unparsed, 1),
+            }
+        } else if lower == "every" {
+            match next.as_deref().and_then(parse_every) {
+                Some(r) => (set_once(&mut recurrence, r, TokenKind::Recurrence), 2),
+                None => (TokenKind::Unparsed, 1 + usize::from(next.is_some())),
+            }
+        } else if let Some(r) = parse_repeat_word(&lower) {
+            (set_once(&mut recurrence, r, TokenKind::Recurrence), 1)
+        } else {
+            (TokenKind::Text, 1)
+        };
+
+        let text = words[i..i + used].join(" ");
+        if matches!(kind, TokenKind::Text | TokenKind::Unparsed) {
+            title.push(text.clone());
+        }
+        tokens.push(Token { text, kind });
+        i += used;
+    }
+
+    // "every monday" without an explicit date starts on the next monday.
+    let date = date.unwrap_or_else(|| match recurrence {
+        Some(Recurrence::On(weekday)) => upcoming(default_date, weekday, 0).unwrap_or(default_date),
+        _ => default_date,
+    });
+
+    let mut task = Task::new(date, &time.unwrap_or_default(), &title.join(" "));
+    task.duration = duration.unwrap_or_default();
+    task.tags = tags;
+    task.priority = priority.unwrap_or_default();
+    task.recurrence = recurrence;
+
+    Parsed { task, tokens }
+}
+
+// A phrase that is nothing but a date, like "next monday" or "2026-03-01".
+pub fn parse_date_phrase(input: &str, today: NaiveDate, week_start: Weekday) -> Option<NaiveDate> {
+    let parsed = parse(input, today, today, week_start);
+    match parsed.tokens.as_slice() {
+        [token] if token.kind == TokenKind::Date => Some(parsed.task.date),
+        _ => None,
+    }
+}
+
+// Stores the first value seen; a second date/time/priority is ambiguous.
+fn set_once<T>(slot: &mut Option<T>, value: T, kind: TokenKind) -> TokenKind {
+    if slot.is_some() {
+        return TokenKind::Unparsed;
+    }
+    *slot = Some(value);
+    kind
+}
+
+// A date phrase and how many words it took. The date is None when the
+// phrase reads fine but lands outside the calendar ("in 99999999 days").
+fn parse_date(word: &str, next: Option<&str>, after: Option<&str>, today: NaiveDate, week_start: Weekday) -> Option<(Option<NaiveDate>, usize)> {
+    match word {
+        "today" => return Some((Some(today), 1)),
+        "tomorrow" => return Some((today.succ_opt(), 1)),
+        "yesterday" => return Some((today.pred_opt(), 1)),
+        "next" => {
+            return match next? {
+                "week" => Some((upcoming(today, week_start, 1), 2)),
+                "month" => Some((today.checked_add_months(Months::new(1)), 2)),
+                day => Some((upcoming(today, parse_weekday(day)?, 1), 2)),
+            };
+        }
+        "in" => {
+            let amount: i64 = next?.parse().ok()?;
+            let days = match after? {
+                "day" | "days" => Some(amount),
+                "week" | "weeks" => amount.checked_mul(7),
+                _ => return None,
+            };
+            let date = days
+                .and_then(Duration::try_days)
+                .and_then(|days| today.checked_add_signed(days));
+            return Some((date, 3));
+        }
+        _ => {}
+    }
+
+    if let Some(weekday) = parse_weekday(word) {
+        return Some((upcoming(today, weekday, 0), 1));
+    }
+
+    NaiveDate::parse_from_str(word, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(word, "%d-%m-%Y"))
+        .ok()
+        .map(|d| (Some(d), 1))
+}
+
+// Next `weekday` on or after `from + min_days`; None past the end of the calendar.
+fn upcoming(from: NaiveDate, weekday: Weekday, min_days: u64) -> Option<NaiveDate> {
+    let start = from.checked_add_days(Days::new(min_days))?;
+    let ahead = (7 + weekday.num_days_from_monday() - start.weekday().num_days_from_monday()) % 7;
+    start.checked_add_days(Days::new(ahead.into()))
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_time(word: &str) -> Option<String> {
+    let time = if let Some(hour) = word.strip_suffix("am").or_else(|| word.strip_suffix("pm")) {
+        let hour: u32 = hour.parse().ok().filter(|h| (1..=12).contains(h))?;
+        let hour = match (word.ends_with("pm"), hour) {
+            (false, 12) => 0,
+            (true, 12) => 12,
+            (true, h) => h + 12,
+            (false, h) => h,
+        };
+        NaiveTime::from_hms_opt(hour, 0, 0)?
+    } else {
+        NaiveTime::parse_from_str(word, "%H:%M").ok()?
+    };
+
+    Some(time.format("%H:%M").to_string())
+}
+
+// A task is planned within one day, so a longer duration is a typo.
+const MAX_DURATION: u32 = 24 * 60;
+
+// "30m", "45min", "2h", "1h30" or "1h30m", in minutes, up to a day.
+fn parse_duration(word: &str) -> Option<u32> {
+    let (hours, minutes) = match word.split_once('h') {
+        Some((hours, minutes)) => (hours, minutes.strip_suffix('m').unwrap_or(minutes)),
+        None => ("0", word.strip_suffix("min").or_else(|| word.strip_suffix('m'))?),
+    };
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = if minutes.is_empty() { 0 } else { minutes.parse().ok()? };
+
+    hours.checked_mul(60)?.checked_add(minutes).filter(|total| (1..=MAX_DURATION).contains(total))
+}
+
+fn looks_like_time(word: &str) -> bool {
+    word.contains(':') && word.chars().all(|c| c.is_ascii_digit() || c == ':')
+}
+
+fn looks_like_date(word: &str) -> bool {
+    word.contains('-') && word.chars().all(|c| c.is_ascii_digit() || c == '-') && word.len() >= 6
+}
+
+// Shaped like a duration ("99999999h") even if the number is too big to use.
+fn looks_like_duration(word: &str) -> bool {
+    let number = word.strip_suffix("min").or_else(|| word.strip_suffix('m')).unwrap_or(word);
+    let (hours, minutes) = match number.split_once('h') {
+        Some(parts) => parts,
+        None if number.len() < word.len() => ("0", number),
+        None => return false,
+    };
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    !number.is_empty() && !hours.is_empty() && digits(hours) && digits(minutes)
+}
+
+fn parse_priority(level: &str) -> Option<Priority> {
+    match level {
+        "high" | "h" | "1" => Some(Priority::High),
+        "normal" | "med" | "medium" | "m" | "2" => Some(Priority::Normal),
+        "low" | "l" | "3" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+fn parse_every(word: &str) -> Option<Recurrence> {
+    match word {
+        "day" => Some(Recurrence::Daily),
+        "weekday" | "weekdays" => Some(Recurrence::Weekdays),
+        "week" => Some(Recurrence::Weekly),
+        "month" => Some(Recurrence::Monthly),
+        day => parse_weekday(day).map(Recurrence::On),
+    }
+}
+
+fn parse_repeat_word(word: &str) -> Option<Recurrence> {
+    match word {
+        "daily" => Some(Recurrence::Daily),
+        "weekly" => Some(Recurrence::Weekly),
+        "monthly" => Some(Recurrence::Monthly),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Wednesday.
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 4).unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn quick(input: &str) -> Parsed {
+        parse(input, today(), date(3, 10), Weekday::Mon)
+    }
+
+    fn kinds(parsed: &Parsed) -> Vec<(&str, TokenKind)> {
+        parsed.tokens.iter().map(|t| (t.text.as_str(), t.kind)).collect()
+    }
+
+    #[test]
+    fn splits_a_phrase_into_a_task() {
+        let parsed = quick("tomorrow 14:00 review PR #work !high for 30m");
+        assert_eq!(parsed.task.date, date(3, 5));
+        assert_eq!(parsed.task.time, "14:00");
+        assert_eq!(parsed.task.text, "review PR");
+        assert_eq!(parsed.task.tags, ["work"]);
+        assert_eq!(parsed.task.priority, Priority::High);
+        assert_eq!(parsed.task.duration, 30);
+        assert!(!parsed.has_unparsed());
+    }
+
+    #[test]
+    fn date_words() {
+        let cases = [
+            ("today", date(3, 4)),
+            ("tomorrow", date(3, 5)),
+            ("yesterday", date(3, 3)),
+            ("friday", date(3, 6)),
+            ("wed", date(3, 4)),
+            ("next wednesday", date(3, 11)),
+            ("next week", date(3, 9)),
+            ("next month", date(4, 4)),
+            ("in 3 days", date(3, 7)),
+            ("in 2 weeks", date(3, 18)),
+            ("2026-05-01", date(5, 1)),
+            ("01-05-2026", date(5, 1)),
+            ("call mom", date(3, 10)),
+        ];
+        for (input, expected) in cases {
+            let parsed = quick(input);
+            assert_eq!(parsed.task.date, expected, "{input}");
+            assert!(!parsed.has_unparsed(), "{input}");
+        }
+    }
+
+    #[test]
+    fn next_week_starts_on_the_configured_day() {
+        let parsed = parse("next week", today(), today(), Weekday::Sun);
+        assert_eq!(parsed.task.date, date(3, 8));
+    }
+
+    #[test]
+    fn times() {
+        let cases = [("14:00", "14:00"), ("at 9:30", "09:30"), ("3pm", "15:00"), ("12am", "00:00"), ("12pm", "12:00")];
+        for (input, expected) in cases {
+            assert_eq!(quick(input).task.time, expected, "{input}");
+        }
+        assert_eq!(kinds(&quick("25:00")), [("25:00", TokenKind::Unparsed)]);
+        assert_eq!(kinds(&quick("13pm")), [("13pm", TokenKind::Text)]);
+    }
+
+    #[test]
+    fn durations() {
+        let cases = [("30m", 30), ("45min", 45), ("2h", 120), ("1h30", 90), ("for 1h30m", 90)];
+        for (input, expected) in cases {
+            let parsed = quick(input);
+            assert_eq!(parsed.task.duration, expected, "{input}");
+            assert!(parsed.task.text.is_empty(), "{input}");
+        }
+        assert_eq!(kinds(&quick("0m")), [("0m", TokenKind::Unparsed)]);
+        assert_eq!(quick("24h").task.duration, 24 * 60);
+        assert_eq!(kinds(&quick("24h1m")), [("24h1m", TokenKind::Unparsed)]);
+        assert_eq!(kinds(&quick("for 1500min")), [("for", TokenKind::Text), ("1500min", TokenKind::Unparsed)]);
+    }
+
+    #[test]
+    fn recurrence() {
+        let cases = [
+            ("daily", Recurrence::Daily),
+            ("weekly", Recurrence::Weekly),
+            ("monthly", Recurrence::Monthly),
+            ("every day", Recurrence::Daily),
+            ("every weekday", Recurrence::Weekdays),
+            ("every friday", Recurrence::On(Weekday::Fri)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(quick(input).task.recurrence, Some(expected), "{input}");
+        }
+
+        // Without a date, "every <day>" starts on the first such day.
+        assert_eq!(quick("every friday").task.date, date(3, 13));
+        assert_eq!(kinds(&quick("every blue")), [("every blue", TokenKind::Unparsed)]);
+    }
+
+    #[test]
+    fn tags_and_priority() {
+        let parsed = quick("#work plan #home #work !low");
+        assert_eq!(parsed.task.tags, ["work", "home"]);
+        assert_eq!(parsed.task.priority, Priority::Low);
+        assert_eq!(parsed.task.text, "plan");
+
+        assert_eq!(kinds(&quick("#")), [("#", TokenKind::Unparsed)]);
+        assert_eq!(kinds(&quick("!urgent")), [("!urgent", TokenKind::Unparsed)]);
+    }
+
+    #[test]
+    fn a_second_value_is_unparsed_and_kept_in_the_title() {
+        let parsed = quick("today friday 9am 10am !high !low task");
+        assert_eq!(parsed.task.date, date(3, 4));
+        assert_eq!(parsed.task.time, "09:00");
+        assert_eq!(parsed.task.priority, Priority::High);
+        assert_eq!(parsed.task.text, "friday 10am !low task");
+    }
+
+    #[test]
+    fn numbers_too_big_are_unparsed_instead_of_panicking() {
+        assert_eq!(kinds(&quick("in 99999999 days")), [("in 99999999 days", TokenKind::Unparsed)]);
+        assert_eq!(kinds(&quick("in 9223372036854775807 weeks")), [("in 9223372036854775807 weeks", TokenKind::Unparsed)]);
+        assert_eq!(kinds(&quick("99999999h")), [("99999999h", TokenKind::Unparsed)]);
+        assert_eq!(kinds(&quick("for 71582789h")), [("for", TokenKind::Text), ("71582789h", TokenKind::Unparsed)]);
+        assert_eq!(quick("in 99999999 days").task.date, date(3, 10));
+
+        let last = NaiveDate::MAX;
+        for input in ["tomorrow", "next month", "next week", "friday", "in 1 days"] {
+            let parsed = parse(input, last, last, Weekday::Mon);
+            assert!(parsed.has_unparsed(), "{input}");
+        }
+    }
+
+    #[test]
+    fn date_phrases() {
+        assert_eq!(parse_date_phrase("next monday", today(), Weekday::Mon), Some(date(3, 9)));
+        assert_eq!(parse_date_phrase("monday lunch", today(), Weekday::Mon), None);
+        assert_eq!(parse_date_phrase("in 99999999 days", today(), Weekday::Mon), None);
+    }
+}
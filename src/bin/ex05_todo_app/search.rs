@@ -0,0 +1,109 @@
+use chrono::NaiveDate;
+
+use crate::store::Journal;
+use crate::task::Task;
+
+pub enum HitKind {
+    Task { done: bool },
+    Journal,
+}
+
+pub struct SearchHit {
+    pub date: NaiveDate,
+    pub kind: HitKind,
+    pub text: String,
+}
+
+// Case-insensitive match over task titles, times and tags plus every line
+// of the journal, ordered by date.
+pub fn search(tasks: &[Task], journal: &Journal, query: &str) -> Vec<SearchHit> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return vec![];
+    }
+
+    let mut hits: Vec<SearchHit> = tasks
+        .iter()
+        .filter(|t| {
+            t.text.to_lowercase().contains(&query)
+                || t.time.contains(&query)
+                || t.tags.iter().any(|tag| tag.to_lowercase().contains(query.trim_start_matches('#')))
+        })
+        .map(|t| SearchHit {
+            date: t.date,
+            kind: HitKind::Task { done: t.done },
+            text: format!("{:5}  {}", t.time, t.text),
+        })
+        .collect();
+
+    for (date, entry) in journal {
+        for line in entry.lines().filter(|line| line.to_lowercase().contains(&query)) {
+            hits.push(SearchHit {
+                date: *date,
+                kind: HitKind::Journal,
+                text: line.trim().to_string(),
+            });
+        }
+    }
+
+    hits.sort_by_key(|hit| hit.date);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    fn planner() -> (Vec<Task>, Journal) {
+        let mut docs = Task::new(date(2), "09:00", "Write docs");
+        docs.tags = vec!["work".into()];
+        let mut milk = Task::new(date(1), "", "Buy milk");
+        milk.tags = vec!["Home".into()];
+        milk.done = true;
+
+        let mut journal = Journal::new();
+        journal.insert(date(1), "  Docs review went well\nSlept badly".into());
+        journal.insert(date(3), "Nothing to note".into());
+        (vec![docs, milk], journal)
+    }
+
+    // Date, whether the hit is a journal line, and its text.
+    fn hits(query: &str) -> Vec<(NaiveDate, bool, String)> {
+        let (tasks, journal) = planner();
+        search(&tasks, &journal, query)
+            .into_iter()
+            .map(|hit| (hit.date, matches!(hit.kind, HitKind::Journal), hit.text))
+            .collect()
+    }
+
+    #[test]
+    fn matches_titles_and_journal_lines_by_date() {
+        assert_eq!(
+            hits("DOCS"),
+            [(date(1), true, "Docs review went well".to_string()), (date(2), false, "09:00  Write docs".to_string())]
+        );
+        assert_eq!(hits("slept"), [(date(1), true, "Slept badly".to_string())]);
+    }
+
+    #[test]
+    fn matches_times_and_tags() {
+        assert_eq!(hits("09:"), [(date(2), false, "09:00  Write docs".to_string())]);
+        // A leading # is optional and tags match in any case.
+        assert_eq!(hits("#home"), [(date(1), false, "       Buy milk".to_string())]);
+        assert_eq!(hits("wor"), [(date(2), false, "09:00  Write docs".to_string())]);
+    }
+
+    #[test]
+    fn done_tasks_are_marked_and_blank_queries_find_nothing() {
+        let (tasks, journal) = planner();
+        let found = search(&tasks, &journal, "milk");
+        assert!(matches!(found[..], [SearchHit { kind: HitKind::Task { done: true }, .. }]));
+
+        assert!(hits("   ").is_empty());
+        assert!(hits("nowhere").is_empty());
+    }
+}
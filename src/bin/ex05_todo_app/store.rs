@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::NaiveDate;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::crypt::{self, Cipher};
+use crate::task::Task;
+
+pub const DATA_DIR_ENV: &str = "EX05_DATA_DIR";
+// Unlocks an encrypted store for the command-line modes, which can't ask.
+pub const PASSPHRASE_ENV: &str = "EX05_PASSPHRASE";
+const STORE_FILE: &str = "tasks.jsonl";
+const ARCHIVE_FILE: &str = "archive.jsonl";
+const LOCK_FILE: &str = "store.lock";
+
+pub type Journal = BTreeMap<NaiveDate, String>;
+
+// Everything the store holds, borrowed from wherever it lives.
+pub struct Snapshot<'a> {
+    pub tasks: &'a [Task],
+    pub archive: &'a [Task],
+    pub journal: &'a Journal,
+}
+
+// One JSON object per line, so a diff or merge of the store touches
+// whole tasks and journal entries instead of fragments of them.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Record {
+    Task(Box<Task>),
+    Journal { date: NaiveDate, text: String },
+}
+
+pub struct Store {
+    dir: PathBuf,
+    // Set once an encrypted store is unlocked, or to encrypt a plain one;
+    // every file is then written encrypted.
+    cipher: Option<Cipher>,
+}
+
+// Modification time and size of the store and archive files, compared to
+// notice writes from another process.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stamp {
+    store: Option<(SystemTime, u64)>,
+    archive: Option<(SystemTime, u64)>,
+}
+
+// Held while reading or writing; the advisory lock is released when the
+// file is closed on drop.
+pub struct StoreLock {
+    _file: File,
+}
+
+impl Store {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), cipher: None }
+    }
+
+    // `$EX05_DATA_DIR`, falling back to `~/.ex05_todo_app`.
+    pub fn from_env() -> Self {
+        let dir = std::env::var_os(DATA_DIR_ENV)
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".ex05_todo_app")))
+            .unwrap_or_else(|| PathBuf::from(".ex05_todo_app"));
+
+        Self::new(dir)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(STORE_FILE)
+    }
+
+    pub fn archive_path(&self) -> PathBuf {
+        self.dir.join(ARCHIVE_FILE)
+    }
+
+    // Every planner sharing the directory takes this lock around a
+    // read-merge-write, so two windows never interleave their saves. A
+    // separate file is locked because saving replaces the store file.
+    pub fn lock(&self) -> Result<StoreLock> {
+        fs::create_dir_all(&self.dir).wrap_err_with(|| format!("creating {}", self.dir.display()))?;
+
+        let path = self.dir.join(LOCK_FILE);
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .wrap_err_with(|| format!("opening {}", path.display()))?;
+        file.lock().wrap_err_with(|| format!("locking {}", path.display()))?;
+
+        Ok(StoreLock { _file: file })
+    }
+
+    pub fn stamp(&self) -> Stamp {
+        let stamp = |path: PathBuf| {
+            fs::metadata(path)
+                .ok()
+                .and_then(|meta| Some((meta.modified().ok()?, meta.len())))
+        };
+
+        Stamp {
+            store: stamp(self.path()),
+            archive: stamp(self.archive_path()),
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        [self.path(), self.archive_path()]
+            .iter()
+            .any(|path| fs::read_to_string(path).is_ok_and(|text| crypt::is_encrypted(&text)))
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    // Checks the passphrase against the encrypted files and keeps their
+    // keys (not the passphrase).
+    pub fn unlock(&mut self, passphrase: &str) -> Result<()> {
+        let texts: Vec<String> = [self.path(), self.archive_path()]
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .filter(|text| crypt::is_encrypted(text))
+            .collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+        self.cipher = Some(Cipher::unlock(passphrase, &texts)?);
+        Ok(())
+    }
+
+    pub fn cipher(&self) -> Option<&Cipher> {
+        self.cipher.as_ref()
+    }
+
+    // `None` writes plain files from the next save on.
+    pub fn set_cipher(&mut self, cipher: Option<Cipher>) {
+        self.cipher = cipher;
+    }
+
+    pub fn load(&self) -> Result<(Vec<Task>, Journal)> {
+        self.read_records(&self.path())
+    }
+
+    // Any file in the store format, such as a backup.
+    pub fn load_from(&self, path: &Path) -> Result<(Vec<Task>, Journal)> {
+        self.read_records(path)
+    }
+
+    pub fn save(&self, tasks: &[Task], journal: &Journal) -> Result<()> {
+        self.write_records(&self.path(), &encode(tasks, journal)?)
+    }
+
+    // Archived tasks use the same record format, without journal entries.
+    pub fn load_archive(&self) -> Result<Vec<Task>> {
+        self.read_records(&self.archive_path()).map(|(tasks, _)| tasks)
+    }
+
+    pub fn save_archive(&self, tasks: &[Task]) -> Result<()> {
+        self.write_records(&self.archive_path(), &encode(tasks, &Journal::new())?)
+    }
+
+    // Decodes the contents of a store file, plain or encrypted.
+    pub fn decode_file(&self, text: &str) -> Result<(Vec<Task>, Journal)> {
+        if !crypt::is_encrypted(text) {
+            return decode(text);
+        }
+
+        match &self.cipher {
+            Some(cipher) => decode(&cipher.decrypt(text)?),
+            None => Err(eyre!("the store is encrypted; unlock it first")),
+        }
+    }
+
+    fn read_records(&self, path: &Path) -> Result<(Vec<Task>, Journal)> {
+        match fs::read_to_string(path) {
+            Ok(text) => self.decode_file(&text).wrap_err_with(|| format!("reading {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok((vec![], Journal::new())),
+            Err(err) => Err(err).wrap_err_with(|| format!("reading {}", path.display())),
+        }
+    }
+
+    fn write_records(&self, path: &Path, text: &str) -> Result<()> {
+        match &self.cipher {
+            Some(cipher) => write_file(path, &cipher.encrypt(text)?),
+            None => write_file(path, text),
+        }
+    }
+}
+
+// Writes through a temporary file so a crash never leaves half a store.
+fn write_file(path: &Path, text: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+    }
+
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, text).wrap_err_with(|| format!("writing {}", tmp.display()))?;
+    fs::rename(&tmp, path).wrap_err_with(|| format!("writing {}", path.display()))?;
+
+    Ok(())
+}
+
+pub fn encode(tasks: &[Task], journal: &Journal) -> Result<String> {
+    let mut out = String::new();
+
+    for task in tasks {
+        out.push_str(&serde_json::to_string(&Record::Task(Box::new(task.clone())))?);
+        out.push('\n');
+    }
+
+    for (date, text) in journal.iter().filter(|(_, text)| !text.trim().is_empty()) {
+        let record = Record::Journal { date: *date, text: text.clone() };
+        out.push_str(&serde_json::to_string(&record)?);
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+pub fn decode(text: &str) -> Result<(Vec<Task>, Journal)> {
+    let mut tasks = vec![];
+    let mut journal = Journal::new();
+
+    for (number, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let record: Record = serde_json::from_str(line).wrap_err_with(|| format!("line {}", number + 1))?;
+        match record {
+            Record::Task(task) => tasks.push(*task),
+            Record::Journal { date, text } => {
+                journal.insert(date, text);
+            }
+        }
+    }
+
+    Ok((tasks, journal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn encode_and_decode_round_trip() {
+        let mut task = Task::new(date(2024, 5, 1), "09:00", "Write \"docs\"\nand more");
+        task.tags = vec!["work".into()];
+        let tasks = vec![task, Task::new(date(2024, 5, 2), "", "Rest")];
+        let journal = Journal::from([(date(2024, 5, 1), "Slept badly.\nStandup went long".to_string())]);
+
+        let text = encode(&tasks, &journal).unwrap();
+
+        assert_eq!(text.lines().count(), 3);
+        assert!(text.lines().last().unwrap().starts_with(r#"{"kind":"journal","date":"2024-05-01""#));
+        assert_eq!(decode(&text).unwrap(), (tasks, journal));
+    }
+
+    #[test]
+    fn blank_journal_entries_are_not_written() {
+        let journal = Journal::from([(date(2024, 5, 1), "  \n".to_string()), (date(2024, 5, 2), "kept".to_string())]);
+
+        let (_, decoded) = decode(&encode(&[], &journal).unwrap()).unwrap();
+
+        assert_eq!(decoded, Journal::from([(date(2024, 5, 2), "kept".to_string())]));
+    }
+
+    #[test]
+    fn decode_fills_in_fields_older_files_lack() {
+        let text = "\n{\"kind\":\"task\",\"id\":\"a\",\"date\":\"2024-05-01\",\"text\":\"old\"}\n\n";
+
+        let (tasks, journal) = decode(text).unwrap();
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!((tasks[0].time.as_str(), tasks[0].done, tasks[0].zone), ("", false, None));
+        assert!(journal.is_empty());
+    }
+
+    #[test]
+    fn decode_reports_the_bad_line() {
+        let text = "{\"kind\":\"journal\",\"date\":\"2024-05-01\",\"text\":\"ok\"}\n{\"kind\":\"task\"}\n";
+
+        let err = decode(text).unwrap_err();
+
+        assert_eq!(err.to_string(), "line 2");
+    }
+
+    #[test]
+    fn saves_and_loads_the_store_and_archive() {
+        let dir = std::env::temp_dir().join(format!("ex05-store-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let store = Store::new(&dir);
+        let tasks = vec![Task::new(date(2024, 5, 1), "09:00", "current")];
+        let archive = vec![Task::new(date(2024, 1, 1), "", "old")];
+        let journal = Journal::from([(date(2024, 5, 1), "note".to_string())]);
+
+        assert_eq!(store.load().unwrap(), (vec![], Journal::new()));
+        store.save(&tasks, &journal).unwrap();
+        store.save_archive(&archive).unwrap();
+
+        assert_eq!(store.load().unwrap(), (tasks, journal));
+        assert_eq!(store.load_archive().unwrap(), archive);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
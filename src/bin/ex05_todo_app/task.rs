@@ -0,0 +1,266 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::calendar::Calendar;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily,
+    Weekdays,
+    Weekly,
+    Monthly,
+    On(Weekday),
+}
+
+impl Recurrence {
+    pub fn label(self) -> String {
+        match self {
+            Recurrence::Daily => "every day".into(),
+            Recurrence::Weekdays => "every weekday".into(),
+            Recurrence::Weekly => "every week".into(),
+            Recurrence::Monthly => "every month".into(),
+            Recurrence::On(day) => format!("every {}", weekday_name(day)),
+        }
+    }
+
+    fn matches(self, start: NaiveDate, day: NaiveDate, calendar: &Calendar) -> bool {
+        match self {
+            Recurrence::Daily => true,
+            Recurrence::Weekdays => calendar.has_weekday_tasks(day),
+            Recurrence::Weekly => day.weekday() == start.weekday(),
+            Recurrence::Monthly => day.day() == start.day(),
+            Recurrence::On(weekday) => day.weekday() == weekday,
+        }
+    }
+}
+
+// A timestamped note on a task, as Taskwarrior keeps them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub entry: DateTime<Utc>,
+    pub text: String,
+}
+
+// What a Taskwarrior import knew that the planner has no field for, so
+// an export gives it back instead of making values up.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Imported {
+    pub entry: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    // The record had no `scheduled`; the task's day came from `due`.
+    pub unscheduled: bool,
+    // A priority the planner shows as normal, such as "M".
+    pub priority: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub id: String,
+    pub date: NaiveDate,
+    #[serde(default)]
+    pub time: String,
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub duration: u32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub project: String,
+    // Position among the day's tasks that share the same time.
+    #[serde(default)]
+    pub order: u32,
+    #[serde(default)]
+    pub due: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    // The zone `date` and `time` are in; floating tasks (None) happen at
+    // that wall-clock time wherever the planner is.
+    #[serde(default)]
+    pub zone: Option<Tz>,
+    #[serde(default)]
+    pub imported: Imported,
+}
+
+impl Task {
+    pub fn new(date: NaiveDate, time: &str, text: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            date,
+            time: time.to_string(),
+            text: text.to_string(),
+            done: false,
+            duration: 0,
+            tags: vec![],
+            priority: Priority::Normal,
+            recurrence: None,
+            project: String::new(),
+            order: 0,
+            due: None,
+            annotations: vec![],
+            zone: None,
+            imported: Imported::default(),
+        }
+    }
+
+    // Whether the task shows up on `day` in `zone`; a zoned time can move
+    // it to the day before or after its own date.
+    pub fn occurs_on(&self, day: NaiveDate, zone: Tz, calendar: &Calendar) -> bool {
+        self.own_day(day, zone, calendar).is_some()
+    }
+
+    // The time shown on `day` in `zone`, empty for tasks without one.
+    pub fn time_on(&self, day: NaiveDate, zone: Tz, calendar: &Calendar) -> String {
+        self.own_day(day, zone, calendar)
+            .and_then(|own| self.start_in(own, zone))
+            .map_or_else(|| self.time.clone(), |start| start.format("%H:%M").to_string())
+    }
+
+    // Minutes after midnight on `day`, or None for tasks without a (valid) time.
+    pub fn start_minutes(&self, day: NaiveDate, zone: Tz, calendar: &Calendar) -> Option<u32> {
+        NaiveTime::parse_from_str(&self.time_on(day, zone, calendar), "%H:%M")
+            .ok()
+            .map(|t| t.hour() * 60 + t.minute())
+    }
+
+    // The task's own date and time as seen from `zone`.
+    pub fn localized(&self, zone: Tz) -> (NaiveDate, String) {
+        match self.start_in(self.date, zone) {
+            Some(start) => (start.date(), start.format("%H:%M").to_string()),
+            None => (self.date, self.time.clone()),
+        }
+    }
+
+    // The occurrence, in the task's own calendar, that lands on `day` in `zone`.
+    fn own_day(&self, day: NaiveDate, zone: Tz, calendar: &Calendar) -> Option<NaiveDate> {
+        // The days around `day` don't exist at the ends of the calendar.
+        let candidates = if self.start_in(self.date, zone).is_some() {
+            vec![Some(day), day.pred_opt(), day.succ_opt()]
+        } else {
+            vec![Some(day)]
+        };
+
+        candidates.into_iter().flatten().find(|own| {
+            self.repeats_on(*own, calendar) && self.start_in(*own, zone).is_none_or(|start| start.date() == day)
+        })
+    }
+
+    fn repeats_on(&self, day: NaiveDate, calendar: &Calendar) -> bool {
+        match self.recurrence {
+            None => self.date == day,
+            Some(recurrence) => day >= self.date && recurrence.matches(self.date, day, calendar),
+        }
+    }
+
+    // Start of the occurrence on `own` converted to `zone`; None for
+    // floating or untimed tasks, which need no conversion.
+    fn start_in(&self, own: NaiveDate, zone: Tz) -> Option<NaiveDateTime> {
+        let time = NaiveTime::parse_from_str(&self.time, "%H:%M").ok()?;
+        let start = self.zone?.from_local_datetime(&own.and_time(time)).earliest()?;
+        Some(start.with_timezone(&zone).naive_local())
+    }
+}
+
+pub fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::{America::New_York, Asia::Tokyo, Europe::Berlin};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn pinned(day: NaiveDate, time: &str, zone: Tz) -> Task {
+        Task { zone: Some(zone), ..Task::new(day, time, "call") }
+    }
+
+    #[test]
+    fn pinned_tasks_move_to_the_viewers_clock() {
+        let calendar = Calendar::default();
+        let task = pinned(date(2024, 5, 1), "09:00", Berlin);
+
+        // 09:00 in Berlin summer time is 03:00 in New York and 16:00 in Tokyo.
+        assert_eq!(task.time_on(date(2024, 5, 1), New_York, &calendar), "03:00");
+        assert_eq!(task.time_on(date(2024, 5, 1), Tokyo, &calendar), "16:00");
+        assert_eq!(task.localized(Tokyo), (date(2024, 5, 1), "16:00".to_string()));
+    }
+
+    #[test]
+    fn pinned_tasks_can_land_on_the_next_or_previous_day() {
+        let calendar = Calendar::default();
+        let late = pinned(date(2024, 5, 1), "22:00", Berlin);
+        let early = pinned(date(2024, 5, 1), "01:00", Berlin);
+
+        assert!(late.occurs_on(date(2024, 5, 2), Tokyo, &calendar));
+        assert!(!late.occurs_on(date(2024, 5, 1), Tokyo, &calendar));
+        assert_eq!(late.time_on(date(2024, 5, 2), Tokyo, &calendar), "05:00");
+
+        assert!(early.occurs_on(date(2024, 4, 30), New_York, &calendar));
+        assert_eq!(early.start_minutes(date(2024, 4, 30), New_York, &calendar), Some(19 * 60));
+    }
+
+    #[test]
+    fn pinned_tasks_at_the_ends_of_the_calendar_do_not_panic() {
+        let calendar = Calendar::default();
+        for day in [NaiveDate::MIN, NaiveDate::MAX] {
+            let task = pinned(day, "12:00", Berlin);
+            assert!(task.occurs_on(day, Berlin, &calendar));
+            // Looking for it from another zone checks the days around it.
+            task.occurs_on(day, Tokyo, &calendar);
+            task.occurs_on(day, New_York, &calendar);
+        }
+    }
+
+    #[test]
+    fn daylight_saving_changes_the_offset_not_the_pinned_time() {
+        let calendar = Calendar::default();
+        let mut task = pinned(date(2024, 3, 1), "09:00", Berlin);
+        task.recurrence = Some(Recurrence::Daily);
+
+        // Berlin and New York switch to summer time two weeks apart.
+        assert_eq!(task.time_on(date(2024, 3, 1), New_York, &calendar), "03:00");
+        assert_eq!(task.time_on(date(2024, 3, 20), New_York, &calendar), "04:00");
+        assert_eq!(task.time_on(date(2024, 4, 1), New_York, &calendar), "03:00");
+    }
+
+    #[test]
+    fn floating_and_untimed_tasks_are_not_converted() {
+        let calendar = Calendar::default();
+        let floating = Task::new(date(2024, 5, 1), "09:00", "stretch");
+        let untimed = pinned(date(2024, 5, 1), "", Berlin);
+
+        assert_eq!(floating.time_on(date(2024, 5, 1), Tokyo, &calendar), "09:00");
+        assert_eq!(untimed.time_on(date(2024, 5, 1), Tokyo, &calendar), "");
+        assert!(untimed.occurs_on(date(2024, 5, 1), Tokyo, &calendar));
+        assert_eq!(untimed.start_minutes(date(2024, 5, 1), Tokyo, &calendar), None);
+    }
+}
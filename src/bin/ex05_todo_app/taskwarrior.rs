@@ -0,0 +1,372 @@
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::task::{Annotation, Imported, Priority, Task};
+
+// Taskwarrior's timestamps: UTC, basic ISO 8601.
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+// The fields of a `task export` record that the planner understands;
+// anything else is ignored on import.
+#[derive(Serialize, Deserialize)]
+struct TwTask {
+    uuid: String,
+    description: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scheduled: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<TwAnnotation>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TwAnnotation {
+    entry: String,
+    description: String,
+}
+
+#[derive(Default)]
+pub struct Summary {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    // Whether an archived task was updated, removed or taken back out.
+    pub archive_changed: bool,
+}
+
+// Merges a Taskwarrior export into `tasks` and `archive` by UUID, so
+// importing the same export again (or the planner's own export, archived
+// tasks included) updates tasks instead of duplicating them. Deleted tasks
+// are removed; planner-only fields (duration, recurrence, order) are kept.
+// An archived task that is no longer completed goes back to `tasks`.
+// Scheduled tasks are pinned to `zone`.
+pub fn import(tasks: &mut Vec<Task>, archive: &mut Vec<Task>, json: &str, zone: Tz) -> Result<Summary> {
+    let records: Vec<TwTask> = serde_json::from_str(json).wrap_err("reading Taskwarrior JSON")?;
+    let mut summary = Summary::default();
+
+    for record in records {
+        // Recurring templates only describe the instances exported next to them.
+        if record.status == "recurring" {
+            continue;
+        }
+
+        if let Some(i) = archive.iter().position(|t| t.id == record.uuid) {
+            summary.archive_changed = true;
+            if record.status == "deleted" {
+                archive.remove(i);
+                summary.removed += 1;
+                continue;
+            }
+            apply(&mut archive[i], record, zone);
+            summary.updated += 1;
+            if !archive[i].done {
+                tasks.push(archive.remove(i));
+            }
+            continue;
+        }
+
+        let existing = tasks.iter().position(|t| t.id == record.uuid);
+        if record.status == "deleted" {
+            if let Some(i) = existing {
+                tasks.remove(i);
+                summary.removed += 1;
+            }
+            continue;
+        }
+
+        match existing {
+            Some(i) => {
+                apply(&mut tasks[i], record, zone);
+                summary.updated += 1;
+            }
+            None => {
+                let mut task = Task::new(Utc::now().with_timezone(&zone).date_naive(), "", "");
+                task.id = record.uuid.clone();
+                apply(&mut task, record, zone);
+                tasks.push(task);
+                summary.added += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+// A JSON array `task import` accepts. A task's day and time in its own
+// zone (`zone` for floating tasks) become `scheduled`; tasks without a
+// time are scheduled at midnight. An imported task whose day still comes
+// from `due` stays unscheduled, and `entry`, `end` and a medium priority
+// are written only as they were imported.
+pub fn export(tasks: &[Task], zone: Tz) -> Result<String> {
+    let records: Vec<TwTask> = tasks
+        .iter()
+        .map(|task| {
+            let time = NaiveTime::parse_from_str(&task.time, "%H:%M").unwrap_or(NaiveTime::MIN);
+            let scheduled = to_utc(task.date.and_time(time), task.zone.unwrap_or(zone));
+            let unscheduled = task.imported.unscheduled && task.due == Some(scheduled);
+
+            TwTask {
+                uuid: task.id.clone(),
+                description: task.text.clone(),
+                status: if task.done { "completed" } else { "pending" }.into(),
+                entry: task.imported.entry.map(format_date),
+                end: task.imported.end.filter(|_| task.done).map(format_date),
+                due: task.due.map(format_date),
+                scheduled: (!unscheduled).then(|| format_date(scheduled)),
+                tags: task.tags.clone(),
+                priority: match task.priority {
+                    Priority::High => Some("H".into()),
+                    Priority::Normal => task.imported.priority.clone(),
+                    Priority::Low => Some("L".into()),
+                },
+                project: (!task.project.is_empty()).then(|| task.project.clone()),
+                annotations: task.annotations
+                    .iter()
+                    .map(|note| TwAnnotation { entry: format_date(note.entry), description: note.text.clone() })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(serde_json::to_string_pretty(&records)?)
+}
+
+fn apply(task: &mut Task, record: TwTask, zone: Tz) {
+    task.text = record.description;
+    task.done = record.status == "completed";
+    task.due = record.due.as_deref().and_then(parse_date);
+    task.tags = record.tags;
+    task.project = record.project.unwrap_or_default();
+    task.priority = match record.priority.as_deref() {
+        Some("H") => Priority::High,
+        Some("L") => Priority::Low,
+        _ => Priority::Normal,
+    };
+    task.imported = Imported {
+        entry: record.entry.as_deref().and_then(parse_date),
+        end: record.end.as_deref().and_then(parse_date),
+        unscheduled: record.scheduled.is_none(),
+        priority: record.priority.filter(|_| task.priority == Priority::Normal),
+    };
+    task.annotations = record.annotations
+        .into_iter()
+        .filter_map(|note| Some(Annotation { entry: parse_date(&note.entry)?, text: note.description }))
+        .collect();
+
+    // The planner day comes from when the task is scheduled, else when it
+    // is due; midnight in `zone` means the task has no time.
+    if let Some(when) = record.scheduled.or(record.due).as_deref().and_then(parse_date) {
+        let local = when.with_timezone(&zone);
+        task.date = local.date_naive();
+        task.time = if local.time() == NaiveTime::MIN { String::new() } else { local.format("%H:%M").to_string() };
+        task.zone = Some(zone);
+    }
+}
+
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text, DATE_FORMAT).ok().map(|naive| naive.and_utc())
+}
+
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+fn to_utc(naive: NaiveDateTime, zone: Tz) -> DateTime<Utc> {
+    zone
+        .from_local_datetime(&naive)
+        .earliest()
+        .map_or_else(|| naive.and_utc(), |local| local.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use chrono_tz::Europe::Berlin;
+
+    const EXPORT: &str = r#"[
+        {
+            "uuid": "0b8c6a3e-5a4e-4f4e-9a57-0d3c4f1e2a10",
+            "description": "Write docs",
+            "status": "completed",
+            "entry": "20240430T080000Z",
+            "end": "20240501T100000Z",
+            "scheduled": "20240501T070000Z",
+            "due": "20240502T160000Z",
+            "tags": ["work", "docs"],
+            "priority": "H",
+            "project": "lab",
+            "annotations": [{"entry": "20240501T090000Z", "description": "half done"}],
+            "urgency": 8.9
+        },
+        {
+            "uuid": "5f2d1c7b-2b6a-4c1e-8f0d-6e9a7b3c4d21",
+            "description": "Pay rent",
+            "status": "pending",
+            "due": "20240531T220000Z",
+            "priority": "L"
+        },
+        {
+            "uuid": "9a1b2c3d-4e5f-4a6b-8c7d-0e1f2a3b4c5d",
+            "description": "Stand-up",
+            "status": "recurring"
+        }
+    ]"#;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        parse_date(text).unwrap()
+    }
+
+    #[test]
+    fn import_maps_taskwarrior_fields() {
+        let mut tasks = vec![];
+        let summary = import(&mut tasks, &mut vec![], EXPORT, Berlin).unwrap();
+
+        assert_eq!((summary.added, summary.updated, summary.removed), (2, 0, 0));
+        let [docs, rent] = &tasks[..] else { panic!("expected two tasks, got {}", tasks.len()) };
+
+        assert_eq!(docs.id, "0b8c6a3e-5a4e-4f4e-9a57-0d3c4f1e2a10");
+        assert_eq!(docs.text, "Write docs");
+        assert!(docs.done);
+        // Scheduled 07:00 UTC is 09:00 in Berlin summer time.
+        assert_eq!((docs.date, docs.time.as_str(), docs.zone), (date(2024, 5, 1), "09:00", Some(Berlin)));
+        assert_eq!(docs.due, Some(utc("20240502T160000Z")));
+        assert_eq!(docs.tags, ["work", "docs"]);
+        assert_eq!(docs.priority, Priority::High);
+        assert_eq!(docs.project, "lab");
+        assert_eq!(docs.annotations, [Annotation { entry: utc("20240501T090000Z"), text: "half done".into() }]);
+
+        // Without `scheduled` the day comes from `due`; midnight in the
+        // zone means no time.
+        assert!(!rent.done);
+        assert_eq!((rent.date, rent.time.as_str()), (date(2024, 6, 1), ""));
+        assert_eq!(rent.priority, Priority::Low);
+        assert_eq!(rent.project, "");
+    }
+
+    #[test]
+    fn importing_again_updates_by_uuid_and_keeps_planner_fields() {
+        let mut tasks = vec![];
+        import(&mut tasks, &mut vec![], EXPORT, Berlin).unwrap();
+        tasks[0].duration = 45;
+
+        let again = EXPORT.replace("Write docs", "Write the docs");
+        let summary = import(&mut tasks, &mut vec![], &again, Berlin).unwrap();
+
+        assert_eq!((summary.added, summary.updated, summary.removed), (0, 2, 0));
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].text, "Write the docs");
+        assert_eq!(tasks[0].duration, 45);
+    }
+
+    #[test]
+    fn deleted_records_remove_the_task() {
+        let mut tasks = vec![];
+        import(&mut tasks, &mut vec![], EXPORT, Berlin).unwrap();
+
+        let deleted = r#"[{"uuid": "5f2d1c7b-2b6a-4c1e-8f0d-6e9a7b3c4d21", "description": "Pay rent", "status": "deleted"}]"#;
+        let summary = import(&mut tasks, &mut vec![], deleted, Berlin).unwrap();
+
+        assert_eq!(summary.removed, 1);
+        assert_eq!(tasks.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(), ["Write docs"]);
+    }
+
+    #[test]
+    fn archived_tasks_are_matched_by_uuid_too() {
+        let mut tasks = vec![];
+        let mut archive = vec![];
+        import(&mut tasks, &mut archive, EXPORT, Berlin).unwrap();
+        // The completed task was archived since.
+        archive.push(tasks.remove(0));
+
+        let again = EXPORT.replace("Write docs", "Write the docs");
+        let summary = import(&mut tasks, &mut archive, &again, Berlin).unwrap();
+        assert_eq!((summary.added, summary.updated, summary.removed), (0, 2, 0));
+        assert!(summary.archive_changed);
+        assert_eq!((tasks.len(), archive[0].text.as_str()), (1, "Write the docs"));
+
+        // Pending again, it leaves the archive.
+        let reopened = again.replacen("\"completed\"", "\"pending\"", 1);
+        import(&mut tasks, &mut archive, &reopened, Berlin).unwrap();
+        assert!(archive.is_empty());
+        assert_eq!(tasks.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(), ["Pay rent", "Write the docs"]);
+    }
+
+    #[test]
+    fn export_maps_planner_fields() {
+        let mut task = Task::new(date(2024, 5, 1), "09:00", "Write docs");
+        task.done = true;
+        task.priority = Priority::High;
+        task.tags = vec!["work".into()];
+        task.zone = Some(Berlin);
+
+        let json: serde_json::Value = serde_json::from_str(&export(&[task.clone()], Tz::UTC).unwrap()).unwrap();
+        let record = &json[0];
+
+        assert_eq!(record["uuid"], task.id.as_str());
+        assert_eq!(record["description"], "Write docs");
+        assert_eq!(record["status"], "completed");
+        assert_eq!(record["scheduled"], "20240501T070000Z");
+        // The planner doesn't know when the task was entered or finished.
+        assert!(record.get("entry").is_none());
+        assert!(record.get("end").is_none());
+        assert_eq!(record["priority"], "H");
+        assert_eq!(record["tags"], serde_json::json!(["work"]));
+        assert!(record.get("project").is_none());
+        assert!(record.get("due").is_none());
+    }
+
+    #[test]
+    fn export_and_import_round_trip() {
+        let mut first = Task::new(date(2024, 5, 1), "09:30", "Write docs");
+        first.priority = Priority::Low;
+        first.project = "lab".into();
+        first.tags = vec!["work".into()];
+        first.due = Some(utc("20240503T120000Z"));
+        first.annotations = vec![Annotation { entry: utc("20240501T090000Z"), text: "started".into() }];
+        let mut second = Task::new(date(2024, 12, 24), "", "Wrap presents");
+        second.done = true;
+        let tasks = vec![first, second];
+
+        let mut imported = vec![];
+        import(&mut imported, &mut vec![], &export(&tasks, Berlin).unwrap(), Berlin).unwrap();
+
+        // Floating tasks come back pinned to the zone they were exported in.
+        let expected: Vec<Task> = tasks.iter().cloned().map(|task| Task { zone: Some(Berlin), ..task }).collect();
+        assert_eq!(imported, expected);
+
+        // A Taskwarrior record goes back out as it came in: a medium
+        // priority, its own entry and end, and no made-up `scheduled`.
+        let record = r#"[{
+            "uuid": "7c3e1f2a-6b5d-4e8f-9a0b-1c2d3e4f5a6b",
+            "description": "Review",
+            "status": "completed",
+            "entry": "20240430T080000Z",
+            "end": "20240501T100000Z",
+            "due": "20240502T160000Z",
+            "priority": "M"
+        }]"#;
+        import(&mut imported, &mut vec![], record, Berlin).unwrap();
+        let exported: serde_json::Value = serde_json::from_str(&export(&imported[2..], Berlin).unwrap()).unwrap();
+        assert_eq!(exported, serde_json::from_str::<serde_json::Value>(record).unwrap());
+    }
+}
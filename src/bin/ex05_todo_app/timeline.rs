@@ -0,0 +1,208 @@
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+
+use crate::calendar::Calendar;
+use crate::task::Task;
+
+pub const DAY_START: u32 = 6 * 60;
+pub const DAY_END: u32 = 23 * 60;
+pub const SLOT_MINUTES: u32 = 30;
+const MIDNIGHT: u32 = 24 * 60;
+// Tasks with a time but no duration still take up one slot.
+const DEFAULT_DURATION: u32 = SLOT_MINUTES;
+
+pub struct TimeBlock {
+    pub task_index: usize,
+    pub start: u32,
+    pub end: u32,
+    pub column: usize,
+    pub columns: usize,
+    pub conflict: bool,
+}
+
+impl TimeBlock {
+    pub fn first_slot(&self) -> usize {
+        ((self.start.max(DAY_START) - DAY_START) / SLOT_MINUTES) as usize
+    }
+
+    pub fn last_slot(&self) -> usize {
+        ((self.end.min(DAY_END) - DAY_START).div_ceil(SLOT_MINUTES) as usize).max(self.first_slot() + 1) - 1
+    }
+}
+
+pub fn slot_count() -> usize {
+    ((DAY_END - DAY_START) / SLOT_MINUTES) as usize
+}
+
+pub fn slot_start(slot: usize) -> u32 {
+    DAY_START + slot as u32 * SLOT_MINUTES
+}
+
+pub fn slot_for(minutes: u32) -> Option<usize> {
+    (DAY_START..DAY_END)
+        .contains(&minutes)
+        .then(|| ((minutes - DAY_START) / SLOT_MINUTES) as usize)
+}
+
+pub fn format_minutes(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+// Lays out the timed tasks of `day` as seen in `zone`. Overlapping tasks
+// form a group that shares the width, each task taking the first column
+// that is free at its start; every task in a group of two or more is a
+// conflict.
+pub fn layout_blocks<'a>(tasks: impl Iterator<Item = (usize, &'a Task)>, day: NaiveDate, zone: Tz, calendar: &Calendar) -> Vec<TimeBlock> {
+    let mut blocks: Vec<TimeBlock> = tasks
+        .filter_map(|(task_index, task)| {
+            let start = task.start_minutes(day, zone, calendar)?;
+            // A stored duration can be anything; the block ends at midnight
+            // at the latest.
+            let duration = if task.duration == 0 { DEFAULT_DURATION } else { task.duration };
+            let end = start.saturating_add(duration).min(MIDNIGHT);
+            (start < DAY_END && end > DAY_START).then_some(TimeBlock {
+                task_index,
+                start,
+                end,
+                column: 0,
+                columns: 1,
+                conflict: false,
+            })
+        })
+        .collect();
+
+    blocks.sort_by_key(|b| (b.start, b.end));
+
+    let mut group_start = 0;
+    let mut group_end = 0;
+    let mut column_ends: Vec<u32> = vec![];
+
+    for i in 0..=blocks.len() {
+        if i == blocks.len() || blocks[i].start >= group_end {
+            let columns = column_ends.len().max(1);
+            for block in &mut blocks[group_start..i] {
+                block.columns = columns;
+                block.conflict = i - group_start > 1;
+            }
+            if i == blocks.len() {
+                break;
+            }
+            group_start = i;
+            column_ends.clear();
+        }
+
+        let block = &mut blocks[i];
+        block.column = match column_ends.iter().position(|end| *end <= block.start) {
+            Some(column) => column,
+            None => {
+                column_ends.push(0);
+                column_ends.len() - 1
+            }
+        };
+        column_ends[block.column] = block.end;
+        group_end = group_end.max(block.end);
+    }
+
+    blocks
+}
+
+pub fn is_free(blocks: &[TimeBlock], slot: usize) -> bool {
+    !blocks.iter().any(|b| (b.first_slot()..=b.last_slot()).contains(&slot))
+}
+
+// Next free slot from `slot` in direction `step`, or `slot` if none is left.
+pub fn next_free_slot(blocks: &[TimeBlock], slot: usize, step: isize) -> usize {
+    let mut candidate = slot as isize + step;
+    while candidate >= 0 && (candidate as usize) < slot_count() {
+        if is_free(blocks, candidate as usize) {
+            return candidate as usize;
+        }
+        candidate += step;
+    }
+    slot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::UTC;
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+    }
+
+    fn task(time: &str, duration: u32) -> Task {
+        Task { duration, ..Task::new(day(), time, "block") }
+    }
+
+    fn layout(tasks: &[Task]) -> Vec<TimeBlock> {
+        layout_blocks(tasks.iter().enumerate(), day(), UTC, &Calendar::default())
+    }
+
+    // (task index, column, columns, conflict) of each block, by task index.
+    fn shape(blocks: &[TimeBlock]) -> Vec<(usize, usize, usize, bool)> {
+        let mut shape: Vec<_> = blocks.iter().map(|b| (b.task_index, b.column, b.columns, b.conflict)).collect();
+        shape.sort();
+        shape
+    }
+
+    #[test]
+    fn overlapping_tasks_share_the_width_and_conflict() {
+        let tasks = [
+            task("09:00", 60),
+            task("09:30", 60),
+            // Starts when the first one ends, so it reuses its column.
+            task("10:00", 30),
+            // Touches the group's end without overlapping it.
+            task("10:30", 30),
+            task("14:00", 0),
+        ];
+        let blocks = layout(&tasks);
+
+        assert_eq!(
+            shape(&blocks),
+            vec![(0, 0, 2, true), (1, 1, 2, true), (2, 0, 2, true), (3, 0, 1, false), (4, 0, 1, false)]
+        );
+        // No duration takes up one slot.
+        let untimed = blocks.iter().find(|b| b.task_index == 4).unwrap();
+        assert_eq!((untimed.start, untimed.end), (14 * 60, 14 * 60 + SLOT_MINUTES));
+    }
+
+    #[test]
+    fn tasks_outside_the_day_are_left_out() {
+        let tasks = [task("05:00", 30), task("23:30", 30), task("", 30), task("05:30", 60)];
+        let blocks = layout(&tasks);
+
+        assert_eq!(shape(&blocks), vec![(3, 0, 1, false)]);
+        assert_eq!((blocks[0].first_slot(), blocks[0].last_slot()), (0, 0));
+    }
+
+    #[test]
+    fn huge_durations_end_at_midnight() {
+        let blocks = layout(&[task("22:00", u32::MAX)]);
+
+        assert_eq!(blocks[0].end, MIDNIGHT);
+        assert_eq!(blocks[0].last_slot(), slot_count() - 1);
+    }
+
+    #[test]
+    fn next_free_slot_skips_busy_slots() {
+        let blocks = layout(&[task("09:00", 60), task("10:30", 30)]);
+        let nine = slot_for(9 * 60).unwrap();
+
+        assert!(!is_free(&blocks, nine));
+        assert_eq!(next_free_slot(&blocks, nine - 1, 1), nine + 2);
+        assert_eq!(next_free_slot(&blocks, nine + 2, 1), nine + 4);
+        assert_eq!(next_free_slot(&blocks, nine + 4, -1), nine + 2);
+        // Nothing free past the last slot keeps the cursor where it is.
+        assert_eq!(next_free_slot(&blocks, slot_count() - 1, 1), slot_count() - 1);
+        assert_eq!(next_free_slot(&blocks, 0, -1), 0);
+    }
+
+    #[test]
+    fn formats_minutes_as_clock_times() {
+        assert_eq!(format_minutes(9 * 60 + 5), "09:05");
+        assert_eq!(slot_start(1), DAY_START + SLOT_MINUTES);
+        assert_eq!(slot_for(DAY_END), None);
+    }
+}
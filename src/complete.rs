@@ -0,0 +1,313 @@
+use std::fs;
+use std::path::PathBuf;
+
+// Ratatui, to draw the dropdown
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+use ratatui::Frame;
+
+// Used to calculate how wide a string is in the terminal
+use unicode_width::UnicodeWidthStr;
+
+
+// A COMPLETION PROVIDER
+// Given the word before the cursor, a completer returns what that word
+// could become, best match first
+// Anything can be one: a list of words, the files on disk, a database...
+pub trait Completer {
+    fn complete(&self, word: &str) -> Vec<String>;
+}
+
+// How many candidates the dropdown shows at once (it scrolls for more)
+pub const POPUP_HEIGHT: usize = 6;
+
+
+// A FIXED LIST OF WORDS (commands, tags, names...)
+pub struct WordList {
+    words: Vec<String>,
+}
+
+impl WordList {
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = S>) -> Self {
+        Self { words: words.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl Completer for WordList {
+    fn complete(&self, word: &str) -> Vec<String> {
+        // Punctuation in front ("#wo", "-wo") is kept, the rest is matched
+        let name = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+        let sigil = &word[..word.len() - name.len()];
+
+        rank(self.words.iter().map(String::as_str), name)
+            .into_iter()
+            .map(|found| format!("{sigil}{found}"))
+            .collect()
+    }
+}
+
+
+// FILES AND DIRECTORIES
+// The word is a path: the part up to the last '/' is the directory to
+// look in, the rest is matched against the names in it
+// Directories get a '/' at the end, so Tab can go on inside them
+pub struct FilePaths {
+    root: PathBuf, // where relative paths start
+}
+
+impl FilePaths {
+    // Paths relative to the current directory
+    pub fn new() -> Self {
+        Self::in_dir(".")
+    }
+
+    pub fn in_dir(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl Default for FilePaths {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Completer for FilePaths {
+    fn complete(&self, word: &str) -> Vec<String> {
+        let (dir, name) = match word.rsplit_once('/') {
+            Some(("", name)) => ("/", name), // "/us" looks in the root
+            Some((dir, name)) => (dir, name),
+            None => ("", word),
+        };
+        let folder = self.root.join(dir);
+        let typed_dir = &word[..word.len() - name.len()];
+
+        let Ok(entries) = fs::read_dir(&folder) else {
+            return Vec::new(); // not a directory (yet)
+        };
+
+        // Hidden files only show up once a '.' is typed, like in a shell
+        let names: Vec<(String, bool)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                let hidden = file_name.starts_with('.') && !name.starts_with('.');
+                (!hidden).then(|| (file_name, entry.path().is_dir()))
+            })
+            .collect();
+
+        rank(names.iter().map(|(file_name, _)| file_name.as_str()), name)
+            .into_iter()
+            .map(|found| {
+                let is_dir = names.iter().any(|(file_name, dir)| file_name == found && *dir);
+                format!("{typed_dir}{found}{}", if is_dir { "/" } else { "" })
+            })
+            .collect()
+    }
+}
+
+
+// FUZZY MATCHING
+// "wrk" matches "work" and "homework": the letters of the pattern have
+// to appear in order, but not next to each other
+// A higher score is a better match: letters at the start of a word and
+// letters right after each other count extra, so "wo" prefers "work"
+pub fn fuzzy_score(candidate: &str, pattern: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut next = 0; // where to look for the next letter of the pattern
+
+    for wanted in pattern.chars() {
+        let same = |c: char| c.to_lowercase().eq(wanted.to_lowercase());
+        let found = (next..candidate.len()).find(|&i| same(candidate[i]))?;
+
+        score += 1;
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 8; // start of a word
+        } else if next > 0 && found == next {
+            score += 5; // right after the previous letter
+        }
+        next = found + 1;
+    }
+    Some(score)
+}
+
+// The candidates that match `pattern`, best first
+// Equal scores put the shorter one first, then alphabetical order
+pub fn rank<'a>(candidates: impl IntoIterator<Item = &'a str>, pattern: &str) -> Vec<&'a str> {
+    let mut found: Vec<(i32, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| Some((fuzzy_score(candidate, pattern)?, candidate)))
+        .collect();
+
+    found.sort_by(|(a_score, a), (b_score, b)| {
+        b_score.cmp(a_score).then(a.len().cmp(&b.len())).then(a.cmp(b))
+    });
+    found.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+// Where the word before `cursor` (a byte index) starts: words are
+// separated by spaces, so a path like "src/bin" is one word
+pub fn word_start(text: &str, cursor: usize) -> usize {
+    text[..cursor]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| !c.is_whitespace())
+        .last()
+        .map_or(cursor, |(i, _)| i)
+}
+
+
+// THE DROPDOWN
+// The candidates for the word before the cursor, and which one is
+// highlighted; Enter puts that one in place of the word
+pub struct Popup {
+    pub candidates: Vec<String>,
+    pub selected: usize,
+    pub word_start: usize, // byte index in the text where the word begins
+}
+
+impl Popup {
+    // Ask `completer` about the word that ends at byte `cursor` of `text`
+    // None when nothing matches, or when the only match is the word itself
+    pub fn open(completer: &dyn Completer, text: &str, cursor: usize) -> Option<Self> {
+        let start = word_start(text, cursor);
+        let word = &text[start..cursor];
+        let candidates = completer.complete(word);
+
+        match candidates.as_slice() {
+            [] => None,
+            [only] if only == word => None,
+            _ => Some(Self { candidates, selected: 0, word_start: start }),
+        }
+    }
+
+    // Highlight the next candidate (Tab, Down), wrapping around
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.candidates.len();
+    }
+
+    // Highlight the previous candidate (Shift-Tab, Up), wrapping around
+    pub fn previous(&mut self) {
+        self.selected = self.selected.checked_sub(1).unwrap_or(self.candidates.len() - 1);
+    }
+
+    // Replace the word (up to byte `cursor`) with the highlighted candidate
+    // and return the byte index right after it, for the cursor
+    pub fn accept(&self, text: &mut String, cursor: usize) -> usize {
+        let candidate = &self.candidates[self.selected];
+        text.replace_range(self.word_start..cursor, candidate);
+        self.word_start + candidate.len()
+    }
+
+    // Draw the dropdown under `field` (the input box), its left edge at
+    // column `x`; when there is no room below, it goes above the box
+    pub fn render(&self, frame: &mut Frame, field: Rect, x: u16) {
+        let screen = frame.area();
+        let longest = self.candidates.iter().map(|c| c.width()).max().unwrap_or(0);
+        let width = (longest as u16 + 2).max(12).min(screen.width);
+        let height = self.candidates.len().min(POPUP_HEIGHT) as u16 + 2;
+
+        // keep it on the screen
+        let x = x.min(screen.right().saturating_sub(width));
+        let y = if field.bottom() + height <= screen.bottom() {
+            field.bottom()
+        } else {
+            field.y.saturating_sub(height)
+        };
+        let area = Rect::new(x, y, width, height).intersection(screen);
+
+        // With more candidates than rows, the title says where we are
+        let mut block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray));
+        if self.candidates.len() > POPUP_HEIGHT {
+            block = block.title_bottom(Line::from(format!("{}/{}", self.selected + 1, self.candidates.len())).right_aligned());
+        }
+
+        let items: Vec<ListItem> = self.candidates.iter().map(|c| ListItem::new(c.as_str())).collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        // The list scrolls by itself to keep the highlighted row visible
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+}
+
+
+// TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match_needs_the_letters_in_order() {
+        assert!(fuzzy_score("homework", "hwk").is_some());
+        assert!(fuzzy_score("homework", "kwh").is_none());
+        assert!(fuzzy_score("Work", "wO").is_some());
+    }
+
+    #[test]
+    fn prefixes_and_word_starts_rank_first() {
+        let words = ["homework", "network", "work", "workshop", "new-order"];
+        assert_eq!(rank(words, "wo"), ["work", "workshop", "new-order", "network", "homework"]);
+        assert_eq!(rank(words, "no"), ["new-order", "network"]);
+    }
+
+    #[test]
+    fn word_list_keeps_the_sigil() {
+        let tags = WordList::new(["work", "home", "reading"]);
+        assert_eq!(tags.complete("#wo"), ["#work"]);
+        assert_eq!(tags.complete("-rd"), ["-reading"]);
+        assert_eq!(tags.complete("#").len(), 3);
+    }
+
+    #[test]
+    fn word_is_everything_after_the_last_space() {
+        assert_eq!(word_start("cat src/bin", 11), 4);
+        assert_eq!(word_start("cat ", 4), 4);
+        assert_eq!(word_start("日本 語", "日本 語".len()), "日本 ".len());
+    }
+
+    #[test]
+    fn file_paths_look_in_the_typed_directory() {
+        let root = std::env::temp_dir().join(format!("ex04_complete_{}", std::process::id()));
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("src/.hidden"), "").unwrap();
+
+        let paths = FilePaths::in_dir(&root);
+        assert_eq!(paths.complete("sr"), ["src/"]);
+        assert_eq!(paths.complete("src/"), ["src/bin/", "src/main.rs"]);
+        assert_eq!(paths.complete("src/.h"), ["src/.hidden"]);
+        assert!(paths.complete("nowhere/x").is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn popup_cycles_and_replaces_the_word() {
+        let words = WordList::new(["rust", "ruby", "python"]);
+        let mut text = String::from("I like ru!");
+        let mut popup = Popup::open(&words, &text, 9).unwrap();
+        assert_eq!(popup.candidates, ["ruby", "rust"]);
+
+        popup.previous();
+        assert_eq!(popup.selected, 1);
+        popup.next();
+        popup.next();
+        let end = popup.accept(&mut text, 9);
+        assert_eq!(text, "I like rust!");
+        assert_eq!(end, 11);
+    }
+
+    #[test]
+    fn nothing_to_show_for_an_exact_match() {
+        let words = WordList::new(["rust"]);
+        assert!(Popup::open(&words, "rust", 4).is_none());
+        assert!(Popup::open(&words, "go", 2).is_none());
+    }
+}
@@ -0,0 +1,1699 @@
+use std::ops::Range;
+
+// Crossterm key events
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+// Ratatui, to draw the field
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+// Splits text into grapheme clusters: what a person sees as ONE character,
+// even when it is made of several chars (e.g. "e" + accent, or 👍🏽)
+use unicode_segmentation::UnicodeSegmentation;
+
+// Used to calculate how wide a string is in the terminal
+// This is important for Unicode characters (like emoji or accented letters)
+use unicode_width::UnicodeWidthStr;
+
+use crate::complete::{self, Completer, Popup};
+use crate::mask::{Mask, SLOT};
+use crate::validator::Validator;
+
+
+// A ONE-LINE TEXT FIELD
+// Everything needed to edit a line of text: cursor, kill ring, selection,
+// scrolling, plus optional rules (validators) and a mask
+// The app decides what Enter, Up/Down and Esc mean; the field does the rest
+// (except while its completion dropdown is open)
+pub struct TextField {
+    pub input: String,          // What was typed (for a template mask: only the digits)
+    pub character_index: usize, // Cursor position (in grapheme clusters, not bytes or chars!)
+    pub keymap: Keymap,         // Which editing keys are used
+    pub vi_command: bool,       // In vi command mode (vi keymap, after Esc)
+    kill_ring: Vec<String>,     // Killed (cut) text, newest last
+    last_yank: Option<Yank>,    // What the last Ctrl-Y pasted, for Alt-Y
+    scroll: usize,              // First cell shown when the input is too long
+    selection_anchor: Option<usize>, // Where Shift+arrows started selecting (grapheme index)
+    pub clipboard: String,      // Text copied with Ctrl-C or cut with Ctrl-X
+    pub host_clipboard: Option<String>, // Copied text still to send to the terminal (OSC 52)
+    validators: Vec<Validator>, // Rules the value has to follow
+    mask: Mask,                 // How the text is shown
+    pub touched: bool,          // Edited (or submitted) since it was last cleared
+    completer: Option<Box<dyn Completer>>, // Suggests words for Tab
+    completion: Option<Popup>,  // The dropdown, while it is open
+}
+
+// The two classic line-editing styles (like readline in a shell)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Keymap {
+    Emacs,  // Always inserting; Esc cancels
+    Vi,     // Esc switches to command mode (h, l, w, b, x, ...)
+}
+
+// What the border colour says about the value
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FieldState {
+    Neutral,  // nothing to say yet (not edited, or no rules)
+    Valid,
+    Invalid,
+}
+
+impl FieldState {
+    // Red when the value breaks a rule, green when it follows them all
+    pub fn border_style(self) -> Style {
+        match self {
+            FieldState::Neutral => Style::default(),
+            FieldState::Valid => Style::default().fg(Color::Green),
+            FieldState::Invalid => Style::default().fg(Color::Red),
+        }
+    }
+}
+
+// Where the last yank was pasted, so Alt-Y can swap it for an older kill
+struct Yank {
+    start: usize,       // grapheme index where the pasted text begins
+    ring_index: usize,  // which kill ring entry was pasted
+}
+
+// The part of a long input that fits in the box, after scrolling
+pub struct InputView {
+    pub text: String,       // the visible graphemes
+    pub padding: usize,     // blank columns after them, up to the right edge
+    pub overflow: bool,     // too long to fit: the edges are kept for indicators
+    pub more_left: bool,    // some text is hidden before the view
+    pub more_right: bool,   // some text is hidden after the view
+    pub cursor_x: usize,    // cursor column, counted from the left edge
+    pub selected: Range<usize>, // bytes of `text` that are selected (empty = none)
+}
+
+// How many killed texts the kill ring remembers
+pub const KILL_RING_SIZE: usize = 16;
+
+
+impl Default for TextField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextField {
+
+    // An empty field without rules, like a plain prompt
+    pub fn new() -> Self {
+        Self {
+            input: String::new(),          // start with empty input
+            character_index: 0,            // cursor at start
+            keymap: Keymap::Emacs,         // readline's default
+            vi_command: false,
+            kill_ring: Vec::new(),         // nothing killed yet
+            last_yank: None,
+            scroll: 0,                     // nothing scrolled away
+            selection_anchor: None,        // nothing selected
+            clipboard: String::new(),
+            host_clipboard: None,
+            validators: Vec::new(),        // anything goes
+            mask: Mask::Plain,
+            touched: false,
+            completer: None,               // no dropdown
+            completion: None,
+        }
+    }
+
+    // Add a rule, e.g. TextField::new().validator(Validator::NonEmpty)
+    pub fn validator(mut self, validator: Validator) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    // Show the text through a mask, e.g. .mask(Mask::Template("__:__"))
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    // Suggest completions, e.g. .completer(FilePaths::new())
+    pub fn completer(mut self, completer: impl Completer + 'static) -> Self {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    // VALUE AND VALIDATION
+
+    // The value of the field (for a template: the filled-in template)
+    pub fn value(&self) -> String {
+        self.mask.format(&self.input)
+    }
+
+    // Replace the whole value, cursor at the end
+    pub fn set_value(&mut self, value: &str) {
+        self.input = self.mask.typed(value);
+        self.selection_anchor = None;
+        self.completion = None;
+        self.move_cursor_end();
+
+        // in vi command mode the cursor sits ON the last character
+        if self.vi_command {
+            self.move_cursor_left();
+        }
+    }
+
+    // Whether the text must not be shown, copied or remembered
+    pub fn is_secret(&self) -> bool {
+        self.mask.is_secret()
+    }
+
+    // Ok, or what is wrong with the value
+    pub fn validate(&self) -> Result<(), String> {
+        let value = self.value();
+
+        // A template has to be filled in completely (or not at all)
+        if let Some(slots) = self.mask.slots()
+            && !self.input.is_empty()
+            && self.grapheme_count() < slots
+            && let Mask::Template(template) = self.mask
+        {
+            return Err(format!("Fill in every {SLOT} of {template}"));
+        }
+
+        self.validators.iter().try_for_each(|validator| validator.check(&value))
+    }
+
+    // The message to show under the field; nothing until it was edited,
+    // so an empty form doesn't start out covered in errors
+    pub fn error(&self) -> Option<String> {
+        if self.touched { self.validate().err() } else { None }
+    }
+
+    // For the border colour
+    pub fn state(&self) -> FieldState {
+        let has_rules = !self.validators.is_empty() || self.mask.slots().is_some();
+        match self.error() {
+            Some(_) => FieldState::Invalid,
+            None if self.touched && has_rules => FieldState::Valid,
+            None => FieldState::Neutral,
+        }
+    }
+
+    // The value when it is valid, and the field is cleared for the next one
+    // When it isn't, nothing changes and the error shows up under the field
+    pub fn submit(&mut self) -> Option<String> {
+        self.touched = true;
+        self.validate().ok()?;
+
+        let value = self.value();
+        self.clear();
+        Some(value)
+    }
+
+    // Empty the field (the keymap, kill ring and clipboard stay)
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.character_index = 0;
+        self.vi_command = false;
+        self.last_yank = None;
+        self.scroll = 0;
+        self.selection_anchor = None;
+        self.touched = false;
+        self.completion = None;
+    }
+
+    // CURSOR MOVEMENT
+
+    // Move cursor one grapheme cluster to the left
+    pub fn move_cursor_left(&mut self) {
+        // saturating_sub prevents going below 0
+        let new_pos = self.character_index.saturating_sub(1);
+
+        // clamp makes sure cursor stays inside valid range
+        self.character_index = self.clamp_cursor(new_pos);
+    }
+
+    // Move cursor one grapheme cluster to the right
+    pub fn move_cursor_right(&mut self) {
+        let new_pos = self.character_index.saturating_add(1);
+        self.character_index = self.clamp_cursor(new_pos);
+    }
+
+    // Move cursor to the start of the line (Home, Ctrl-A)
+    pub fn move_cursor_home(&mut self) {
+        self.character_index = 0;
+    }
+
+    // Move cursor to the end of the line (End, Ctrl-E)
+    pub fn move_cursor_end(&mut self) {
+        self.character_index = self.grapheme_count();
+    }
+
+    // Put the cursor at grapheme `index`; with `select`, the text between
+    // where it was and where it goes gets selected (like Shift+arrows)
+    pub fn move_cursor_to(&mut self, index: usize, select: bool) {
+        if select {
+            self.selection_anchor.get_or_insert(self.character_index);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.character_index = self.clamp_cursor(index);
+    }
+
+    // Move cursor to the start of the previous word (Ctrl-Left, Alt-B, vi b)
+    pub fn move_word_left(&mut self) {
+        self.character_index = self.word_start_before();
+    }
+
+    // Move cursor to the end of the next word (Ctrl-Right, Alt-F)
+    pub fn move_word_right(&mut self) {
+        self.character_index = self.word_end_after();
+    }
+
+    // Move cursor to the start of the next word (vi w)
+    pub fn move_next_word_start(&mut self) {
+        let graphemes: Vec<&str> = self.input.graphemes(true).collect();
+        let mut i = self.character_index;
+
+        // first leave the current word, then skip the gap after it
+        while i < graphemes.len() && is_word(graphemes[i]) {
+            i += 1;
+        }
+        while i < graphemes.len() && !is_word(graphemes[i]) {
+            i += 1;
+        }
+        self.character_index = i;
+    }
+
+    // TYPING AND DELETING
+
+    // Insert a new character at the cursor position
+    pub fn enter_char(&mut self, new_char: char) {
+        self.insert_text(new_char.encode_utf8(&mut [0; 4]));
+    }
+
+    // Insert text at the cursor position and move the cursor after it
+    pub fn insert_text(&mut self, text: &str) {
+        // Typing over a selection replaces it
+        self.delete_selection();
+
+        // A template only takes digits, and only as many as it has slots
+        let text = self.mask.accept(text, self.grapheme_count());
+
+        // Convert from grapheme index to byte index
+        let index = self.byte_index();
+
+        // Insert the text into the string
+        self.input.insert_str(index, &text);
+
+        // The text may JOIN the cluster before it (a combining accent,
+        // a skin tone, a zero-width joiner...), so count the clusters up to
+        // the end of the inserted text instead of just adding its length
+        let end = index + text.len();
+        self.character_index = self.input[..end].graphemes(true).count();
+    }
+
+    // Text pasted into the terminal (bracketed paste), as one line
+    pub fn paste(&mut self, text: &str) {
+        self.last_yank = None;
+        self.completion = None;
+        self.insert_text(&single_line(text));
+        self.touched = true;
+    }
+
+    // Convert grapheme index → byte index
+    // Rust strings are UTF-8, and one grapheme may be several chars,
+    // each of them using 1 to 4 bytes
+    pub fn byte_index(&self) -> usize {
+        self.byte_index_of(self.character_index)
+    }
+
+    // Same as byte_index, for any grapheme position
+    fn byte_index_of(&self, grapheme_index: usize) -> usize {
+        self.input
+            .grapheme_indices(true) // get all grapheme cluster positions
+            .map(|(i, _)| i)        // take only the byte index
+            .nth(grapheme_index)
+            .unwrap_or(self.input.len())
+    }
+
+    // Delete the grapheme cluster BEFORE the cursor
+    // (a whole 👩‍👩‍👧 family or "é" at once, never half of it)
+    pub fn delete_char(&mut self) {
+        if self.character_index == 0 {
+            return; // nothing to delete
+        }
+
+        // Get current byte index
+        let idx = self.byte_index();
+
+        // Find previous grapheme cluster
+        let prev = self.input[..idx].graphemes(true).next_back().unwrap();
+
+        // Remove that cluster from the string
+        let from = idx - prev.len();
+        self.input.replace_range(from..idx, "");
+
+        // Move cursor left
+        self.move_cursor_left();
+    }
+
+    // Delete the grapheme cluster UNDER/AFTER the cursor (Delete, Ctrl-D)
+    pub fn delete_char_forward(&mut self) {
+        let from = self.byte_index();
+        let to = self.byte_index_of(self.character_index + 1);
+        self.input.replace_range(from..to, "");
+    }
+
+    // KILL RING (emacs-style cut and paste)
+
+    // Kill the word before the cursor (Ctrl-W, Alt-Backspace)
+    pub fn kill_word_before(&mut self) {
+        self.kill(self.word_start_before(), self.character_index);
+    }
+
+    // Kill from the cursor to the end of the next word (Alt-D)
+    pub fn kill_word_after(&mut self) {
+        self.kill(self.character_index, self.word_end_after());
+    }
+
+    // Kill everything before the cursor (Ctrl-U)
+    pub fn kill_to_start(&mut self) {
+        self.kill(0, self.character_index);
+    }
+
+    // Kill everything from the cursor on (Ctrl-K)
+    pub fn kill_to_end(&mut self) {
+        self.kill(self.character_index, self.grapheme_count());
+    }
+
+    // Remove the graphemes in from..to, remember them in the kill ring
+    // and leave the cursor where the text was
+    pub fn kill(&mut self, from: usize, to: usize) {
+        let (start, end) = (self.byte_index_of(from), self.byte_index_of(to));
+        if start == end {
+            return; // nothing to kill
+        }
+
+        let killed: String = self.input.drain(start..end).collect();
+        self.kill_ring.push(killed);
+        if self.kill_ring.len() > KILL_RING_SIZE {
+            self.kill_ring.remove(0); // forget the oldest kill
+        }
+        self.character_index = from;
+    }
+
+    // Paste the newest kill at the cursor (Ctrl-Y, vi P)
+    pub fn yank(&mut self) {
+        let Some(ring_index) = self.kill_ring.len().checked_sub(1) else {
+            return; // nothing was killed yet
+        };
+
+        // Yanking over a selection replaces it: the pasted text starts
+        // where the selection did, not where the cursor was
+        self.delete_selection();
+        let start = self.character_index;
+        self.insert_text(&self.kill_ring[ring_index].clone());
+        self.last_yank = Some(Yank { start, ring_index });
+    }
+
+    // Right after a yank, replace the pasted text with the kill before it
+    // (Alt-Y); keeps cycling through the ring when pressed again
+    pub fn yank_pop(&mut self) {
+        let Some(Yank { start, ring_index }) = self.last_yank.take() else {
+            return; // only works straight after Ctrl-Y
+        };
+
+        let (from, to) = (self.byte_index_of(start), self.byte_index());
+        self.input.replace_range(from..to, "");
+        self.character_index = start;
+
+        let ring_index = ring_index.checked_sub(1).unwrap_or(self.kill_ring.len() - 1);
+        self.insert_text(&self.kill_ring[ring_index].clone());
+        self.last_yank = Some(Yank { start, ring_index });
+    }
+
+    // SELECTION AND CLIPBOARD
+
+    // The selected graphemes as start..end, when something is selected
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.selection_anchor?;
+        let cursor = self.character_index;
+        (anchor != cursor).then(|| anchor.min(cursor)..anchor.max(cursor))
+    }
+
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some(range) => &self.input[self.byte_index_of(range.start)..self.byte_index_of(range.end)],
+            None => "",
+        }
+    }
+
+    // Remove the selected text, if any; returns whether there was some
+    pub fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection() else {
+            self.selection_anchor = None;
+            return false;
+        };
+
+        let (from, to) = (self.byte_index_of(range.start), self.byte_index_of(range.end));
+        self.input.replace_range(from..to, "");
+        self.character_index = range.start;
+        self.selection_anchor = None;
+        true
+    }
+
+    // Copy the selection to our clipboard and the terminal's (Ctrl-C)
+    // A password can't be copied out of its field
+    pub fn copy_selection(&mut self) {
+        if self.selection().is_some() && !self.is_secret() {
+            self.clipboard = self.selected_text().to_string();
+            self.host_clipboard = Some(self.clipboard.clone());
+        }
+    }
+
+    // Copy the selection, then remove it (Ctrl-X)
+    pub fn cut_selection(&mut self) {
+        if !self.is_secret() {
+            self.copy_selection();
+            self.delete_selection();
+        }
+    }
+
+    // Paste our clipboard at the cursor, over the selection (Ctrl-V)
+    pub fn paste_clipboard(&mut self) {
+        self.insert_text(&self.clipboard.clone());
+    }
+
+    // WORDS
+
+    // Start of the word before the cursor: skip the gap, then the word
+    fn word_start_before(&self) -> usize {
+        let graphemes: Vec<&str> = self.input.graphemes(true).collect();
+        let mut i = self.character_index.min(graphemes.len());
+
+        while i > 0 && !is_word(graphemes[i - 1]) {
+            i -= 1;
+        }
+        while i > 0 && is_word(graphemes[i - 1]) {
+            i -= 1;
+        }
+        i
+    }
+
+    // End of the word after the cursor: skip the gap, then the word
+    fn word_end_after(&self) -> usize {
+        let graphemes: Vec<&str> = self.input.graphemes(true).collect();
+        let mut i = self.character_index.min(graphemes.len());
+
+        while i < graphemes.len() && !is_word(graphemes[i]) {
+            i += 1;
+        }
+        while i < graphemes.len() && is_word(graphemes[i]) {
+            i += 1;
+        }
+        i
+    }
+
+    // KEYS
+
+    // Handle a key while the field has focus
+    // Returns false for an Esc the field doesn't use (emacs keymap, or vi
+    // command mode), meaning "leave the field"
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.handle_completion_key(key) {
+            return true;
+        }
+        let before = self.input.clone();
+
+        let handled = if self.vi_command {
+            self.handle_vi_key(key)
+        } else {
+            self.handle_editing_key(key)
+        };
+
+        // Typing updates the dropdown, anything else (moving...) closes it
+        if self.input != before {
+            self.touched = true;
+            self.update_completion();
+        } else {
+            self.completion = None;
+        }
+        handled
+    }
+
+    // COMPLETION
+
+    // Whether the completion dropdown is open
+    pub fn completing(&self) -> bool {
+        self.completion.is_some()
+    }
+
+    // Keys for the dropdown: Tab opens it; while it is open Tab/Down and
+    // Shift-Tab/Up move through it, Enter takes the highlighted word and
+    // Esc closes it
+    // An app that uses Enter, Up or Down itself calls this first; returns
+    // whether the key was used
+    pub fn handle_completion_key(&mut self, key: KeyEvent) -> bool {
+        if self.completer.is_none() || self.vi_command {
+            return false;
+        }
+
+        let Some(popup) = &mut self.completion else {
+            // Tab asks for candidates, even before anything was typed
+            if key.code == KeyCode::Tab {
+                self.open_completion();
+                return true;
+            }
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => popup.next(),
+            KeyCode::BackTab | KeyCode::Up => popup.previous(),
+            KeyCode::Enter => self.accept_completion(),
+            KeyCode::Esc => self.completion = None,
+            _ => return false,
+        }
+        true
+    }
+
+    // Ask the completer about the word before the cursor
+    fn open_completion(&mut self) {
+        let cursor = self.byte_index();
+        if let Some(completer) = &self.completer {
+            self.completion = Popup::open(completer.as_ref(), &self.input, cursor);
+        }
+    }
+
+    // After typing: show the candidates for the word being typed, or
+    // nothing right after a space
+    fn update_completion(&mut self) {
+        let cursor = self.byte_index();
+        if self.completer.is_some() && complete::word_start(&self.input, cursor) < cursor {
+            self.open_completion();
+        } else {
+            self.completion = None;
+        }
+    }
+
+    // Put the highlighted candidate in place of the word
+    fn accept_completion(&mut self) {
+        if let Some(popup) = self.completion.take() {
+            let cursor = self.byte_index();
+            let end = popup.accept(&mut self.input, cursor);
+            self.character_index = self.input[..end].graphemes(true).count();
+            self.touched = true;
+        }
+    }
+
+    // Keys while typing; the same in both keymaps, except Esc
+    fn handle_editing_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        // Alt-Y only means something straight after a yank
+        let yank_pop = alt && key.code == KeyCode::Char('y');
+        if !yank_pop {
+            self.last_yank = None;
+        }
+
+        // Shift + a movement key selects: the selection runs from where
+        // the cursor was when selecting started to where it is now
+        if shift && matches!(key.code, KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End) {
+            self.selection_anchor.get_or_insert(self.character_index);
+            match key.code {
+                KeyCode::Left if ctrl => self.move_word_left(),
+                KeyCode::Right if ctrl => self.move_word_right(),
+                KeyCode::Left => self.move_cursor_left(),
+                KeyCode::Right => self.move_cursor_right(),
+                KeyCode::Home => self.move_cursor_home(),
+                _ => self.move_cursor_end(),
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Esc => match self.keymap {
+                Keymap::Emacs => return false,
+                Keymap::Vi => {
+                    // like vim, the cursor steps back onto the last character
+                    self.vi_command = true;
+                    self.move_cursor_left();
+                }
+            },
+            KeyCode::Backspace | KeyCode::Delete if self.delete_selection() => {}
+            KeyCode::Backspace if ctrl || alt => self.kill_word_before(),
+            KeyCode::Backspace => self.delete_char(),
+            KeyCode::Delete => self.delete_char_forward(),
+            KeyCode::Left if ctrl => self.move_word_left(),
+            KeyCode::Right if ctrl => self.move_word_right(),
+            // Left/Right without Shift go to that end of the selection
+            KeyCode::Left if self.selection().is_some() => {
+                self.character_index = self.selection().unwrap().start;
+            }
+            KeyCode::Right if self.selection().is_some() => {
+                self.character_index = self.selection().unwrap().end;
+            }
+            KeyCode::Left => self.move_cursor_left(),
+            KeyCode::Right => self.move_cursor_right(),
+            KeyCode::Home => self.move_cursor_home(),
+            KeyCode::End => self.move_cursor_end(),
+            KeyCode::Char(c) if ctrl => match c {
+                'c' => self.copy_selection(),
+                'x' => self.cut_selection(),
+                'v' => self.paste_clipboard(),
+                'a' => self.move_cursor_home(),
+                'e' => self.move_cursor_end(),
+                'b' => self.move_cursor_left(),
+                'f' => self.move_cursor_right(),
+                'd' => self.delete_char_forward(),
+                'h' => self.delete_char(),
+                'w' => self.kill_word_before(),
+                'u' => self.kill_to_start(),
+                'k' => self.kill_to_end(),
+                'y' => self.yank(),
+                _ => {}
+            },
+            KeyCode::Char(c) if alt => match c {
+                'b' => self.move_word_left(),
+                'f' => self.move_word_right(),
+                'd' => self.kill_word_after(),
+                'y' => self.yank_pop(),
+                _ => {}
+            },
+            KeyCode::Char(c) => self.enter_char(c),
+            _ => {}
+        }
+
+        // Any other key ends the selection (copying keeps it)
+        if !(ctrl && key.code == KeyCode::Char('c')) {
+            self.selection_anchor = None;
+        }
+        true
+    }
+
+    // Keys in vi command mode (a small subset of vim's normal mode)
+    fn handle_vi_key(&mut self, key: KeyEvent) -> bool {
+        self.last_yank = None;
+        self.selection_anchor = None;
+
+        match key.code {
+            KeyCode::Esc => return false,
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => self.move_cursor_left(),
+            KeyCode::Char('l' | ' ') | KeyCode::Right => self.move_cursor_right(),
+            KeyCode::Char('0' | '^') | KeyCode::Home => self.move_cursor_home(),
+            KeyCode::Char('$') | KeyCode::End => self.move_cursor_end(),
+            KeyCode::Char('w') => self.move_next_word_start(),
+            KeyCode::Char('b') => self.move_word_left(),
+            KeyCode::Char('x') | KeyCode::Delete => {
+                self.kill(self.character_index, self.character_index + 1);
+            }
+            KeyCode::Char('X') => {
+                self.kill(self.character_index.saturating_sub(1), self.character_index);
+            }
+            KeyCode::Char('D') => self.kill_to_end(),
+            KeyCode::Char('p') => {
+                // paste AFTER the character under the cursor
+                self.move_cursor_right();
+                self.yank();
+                self.move_cursor_left();
+            }
+            KeyCode::Char('P') => {
+                self.yank();
+                self.move_cursor_left();
+            }
+            KeyCode::Char('i') => self.vi_command = false,
+            KeyCode::Char('a') => {
+                self.move_cursor_right();
+                self.vi_command = false;
+            }
+            KeyCode::Char('I') => {
+                self.move_cursor_home();
+                self.vi_command = false;
+            }
+            KeyCode::Char('A') => {
+                self.move_cursor_end();
+                self.vi_command = false;
+            }
+            KeyCode::Char('C') => {
+                self.kill_to_end();
+                self.vi_command = false;
+            }
+            _ => {}
+        }
+
+        // In command mode the cursor sits ON a character, so it can't be
+        // past the last one (but it can be at the end in insert mode)
+        if self.vi_command {
+            let last = self.grapheme_count().saturating_sub(1);
+            self.character_index = self.character_index.min(last);
+        }
+        true
+    }
+
+    // DRAWING
+
+    // What is drawn, one cell per grapheme: the text, dots for a password
+    // or the filled-in template; plus, for every cursor position in
+    // `input` (0..=len), the cell the cursor is drawn at
+    fn cells(&self) -> (Vec<&str>, Vec<usize>) {
+        let graphemes: Vec<&str> = self.input.graphemes(true).collect();
+
+        match &self.mask {
+            Mask::Plain => {
+                let positions = (0..=graphemes.len()).collect();
+                (graphemes, positions)
+            }
+            Mask::Password => (vec!["•"; graphemes.len()], (0..=graphemes.len()).collect()),
+            Mask::Template(template) => {
+                let mut cells = Vec::new();
+                let mut positions = Vec::new();
+                let mut typed = graphemes.iter();
+
+                for (i, cell) in template.graphemes(true).enumerate() {
+                    if cell == SLOT.to_string() {
+                        match typed.next() {
+                            Some(digit) => {
+                                positions.push(i);
+                                cells.push(*digit);
+                            }
+                            None => {
+                                // the cursor waits at the first empty slot
+                                if positions.len() == graphemes.len() {
+                                    positions.push(i);
+                                }
+                                cells.push(cell);
+                            }
+                        }
+                    } else {
+                        cells.push(cell);
+                    }
+                }
+                // every slot filled: the cursor goes after the template
+                if positions.len() == graphemes.len() {
+                    positions.push(cells.len());
+                }
+                (cells, positions)
+            }
+        }
+    }
+
+    // How many terminal columns are drawn before the cursor
+    // Measured cluster by cluster, so 👍🏽 counts as one wide
+    // character (2 columns) and "e" + accent as one narrow one
+    pub fn cursor_column(&self) -> usize {
+        let (cells, positions) = self.cells();
+        cells[..positions[self.character_index]].iter().map(|cell| grapheme_width(cell)).sum()
+    }
+
+    // Scroll so the cursor stays visible in a box `width` columns wide and
+    // return what to draw there
+    // The view only moves when the cursor would leave it, like in a shell
+    pub fn input_view(&mut self, width: usize) -> InputView {
+        let (cells, positions) = self.cells();
+        let widths: Vec<usize> = cells.iter().map(|cell| grapheme_width(cell)).collect();
+        let cursor = positions[self.character_index.min(positions.len() - 1)];
+
+        let selection = self.selection().map_or(0..0, |range| positions[range.start]..positions[range.end]);
+        let mut scroll = self.scroll;
+        let columns = |from: usize, to: usize| widths[from..to].iter().sum::<usize>();
+        let bytes = |from: usize, to: usize| cells[from..to].iter().map(|cell| cell.len()).sum::<usize>();
+
+        // Everything fits, including the cursor after the last character
+        if widths.iter().sum::<usize>() < width {
+            let view = InputView {
+                text: cells.concat(),
+                padding: 0,
+                overflow: false,
+                more_left: false,
+                more_right: false,
+                cursor_x: self.cursor_column(),
+                selected: bytes(0, selection.start)..bytes(0, selection.end),
+            };
+            self.scroll = 0;
+            return view;
+        }
+
+        // One column on each side is kept for the < and > indicators
+        let room = width.saturating_sub(2);
+
+        // Scroll left when the cursor went before the view...
+        scroll = scroll.min(cursor);
+        // ...right while the cursor (1 column) doesn't fit after the text...
+        while scroll < cursor && columns(scroll, cursor) + 1 > room {
+            scroll += 1;
+        }
+        // ...and back left when deleting left empty space at the end
+        while scroll > 0 && columns(scroll - 1, cells.len()) < room {
+            scroll -= 1;
+        }
+
+        // Take graphemes until the box is full; a wide one that would
+        // straddle the right edge is left out and its column stays blank
+        let mut text = String::new();
+        let mut used = 0;
+        let mut end = scroll;
+        while end < cells.len() && used + widths[end] <= room {
+            text.push_str(cells[end]);
+            used += widths[end];
+            end += 1;
+        }
+
+        // The part of the selection that is in view, in bytes of `text`
+        let in_view = |cell: usize| bytes(scroll, cell.clamp(scroll, end));
+
+        // The blanks are kept apart from the text: drawn in the same span,
+        // a space could join the last cluster (after a "prepend" character
+        // like U+0600) and shift everything after it
+        let view = InputView {
+            text,
+            padding: room - used,
+            overflow: true,
+            more_left: scroll > 0,
+            more_right: end < cells.len(),
+            cursor_x: 1 + columns(scroll, cursor),
+            selected: in_view(selection.start)..in_view(selection.end),
+        };
+        self.scroll = scroll;
+        view
+    }
+
+    // Draw the field: a box with `label` and the text, its border coloured
+    // by the validation state, and the error (if any) on the line below
+    // `area` should be 4 rows high; the cursor is placed when `focused`
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, label: &str, focused: bool) {
+        let [box_area, error_area] = Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).areas(area);
+
+        // Only the part of the input around the cursor fits in the box
+        let label_width = label.width() as u16;
+        let room = box_area.width.saturating_sub(2 + label_width);
+        let view = self.input_view(room as usize);
+
+        // < and > show that there is more text past the edges
+        let indicator = |more: bool, arrow: &'static str| {
+            Span::styled(if more { arrow } else { " " }, Style::default().fg(Color::DarkGray))
+        };
+
+        // The selected part is drawn in reverse video
+        let (before, rest) = view.text.split_at(view.selected.start);
+        let (selected, after) = rest.split_at(view.selected.len());
+        let text_spans = [
+            Span::raw(before.to_string()),
+            Span::styled(selected.to_string(), Style::default().add_modifier(Modifier::REVERSED)),
+            Span::raw(after.to_string()),
+            Span::raw(" ".repeat(view.padding)),
+        ];
+
+        let mut spans = vec![Span::styled(label.to_string(), Style::default().fg(Color::White))];
+        if view.overflow {
+            spans.push(indicator(view.more_left, "<"));
+            spans.extend(text_spans);
+            spans.push(indicator(view.more_right, ">"));
+        } else {
+            spans.extend(text_spans);
+        }
+
+        let panel = Paragraph::new(Line::from(spans))
+            .block(Block::default().borders(Borders::ALL).border_style(self.state().border_style()));
+        frame.render_widget(panel, box_area);
+
+        if let Some(error) = self.error() {
+            let error_line = Paragraph::new(Span::styled(format!(" {error}"), Style::default().fg(Color::Red)));
+            frame.render_widget(error_line, error_area);
+        }
+
+        if focused {
+            let cursor_x = box_area.x + 1 + label_width + view.cursor_x as u16;
+            frame.set_cursor_position(Position::new(cursor_x, box_area.y + 1));
+
+            // The dropdown starts under the word it completes
+            if let Some(popup) = &self.completion {
+                let word = &self.input[popup.word_start..self.byte_index()];
+                popup.render(frame, box_area, cursor_x.saturating_sub(word.width() as u16));
+            }
+        }
+    }
+
+    // How many grapheme clusters the input has
+    pub fn grapheme_count(&self) -> usize {
+        self.input.graphemes(true).count()
+    }
+
+    // Prevent cursor from going outside valid range
+    fn clamp_cursor(&self, new_cursor_pos: usize) -> usize {
+        let max = self.grapheme_count();
+        new_cursor_pos.clamp(0, max)
+    }
+}
+
+// Is this grapheme part of a word? (letters, digits and '_')
+fn is_word(grapheme: &str) -> bool {
+    grapheme
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+// Pasted text for a one-line input: line breaks and tabs become spaces
+// (a trailing line break is dropped) and other control characters go
+pub fn single_line(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .trim_end_matches(['\r', '\n'])
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+// Width of one grapheme cluster in terminal columns
+// A cluster is drawn as a single character, so it is never wider than 2
+// columns, even when its chars add up to more (like a ZWJ sequence)
+pub fn grapheme_width(grapheme: &str) -> usize {
+    grapheme.width().min(2)
+}
+
+
+// TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Type every char of `text`, like a user would
+    fn typed(text: &str) -> TextField {
+        let mut field = TextField::new();
+        for c in text.chars() {
+            field.enter_char(c);
+        }
+        field
+    }
+
+    #[test]
+    fn ascii_moves_one_char_at_a_time() {
+        let mut field = typed("abc");
+        assert_eq!(field.character_index, 3);
+
+        field.move_cursor_left();
+        field.delete_char();
+        assert_eq!(field.input, "ac");
+        assert_eq!(field.character_index, 1);
+        assert_eq!(field.cursor_column(), 1);
+    }
+
+    #[test]
+    fn zwj_family_is_one_character() {
+        // man + ZWJ + woman + ZWJ + girl
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let mut field = typed(&format!("a{family}b"));
+        assert_eq!(field.character_index, 3);
+
+        field.move_cursor_left();
+        assert_eq!(field.byte_index(), 1 + family.len());
+        assert_eq!(field.cursor_column(), 3);
+
+        field.delete_char();
+        assert_eq!(field.input, "ab");
+        assert_eq!(field.character_index, 1);
+        assert_eq!(field.cursor_column(), 1);
+    }
+
+    #[test]
+    fn skin_tone_and_flag_are_one_character() {
+        let thumbs = "\u{1F44D}\u{1F3FD}";
+        let flag = "\u{1F1EE}\u{1F1E9}";
+        let mut field = typed(&format!("{thumbs}{flag}"));
+        assert_eq!(field.character_index, 2);
+        assert_eq!(field.cursor_column(), 4);
+
+        field.delete_char();
+        assert_eq!(field.input, thumbs);
+        field.delete_char();
+        assert_eq!(field.input, "");
+    }
+
+    #[test]
+    fn combining_marks_stay_with_their_letter() {
+        // "e" + combining acute, "o" + combining diaeresis and macron
+        let mut field = typed("e\u{301}o\u{308}\u{304}x");
+        assert_eq!(field.character_index, 3);
+        assert_eq!(field.cursor_column(), 3);
+
+        field.move_cursor_left();
+        field.move_cursor_left();
+        assert_eq!(field.cursor_column(), 1);
+
+        field.delete_char();
+        assert_eq!(field.input, "o\u{308}\u{304}x");
+        assert_eq!(field.character_index, 0);
+    }
+
+    #[test]
+    fn combining_mark_typed_after_cursor_joins_previous_letter() {
+        let mut field = typed("ab");
+        field.move_cursor_left();
+        field.enter_char('\u{301}');
+
+        assert_eq!(field.input, "a\u{301}b");
+        assert_eq!(field.character_index, 1);
+        assert_eq!(field.cursor_column(), 1);
+    }
+
+    #[test]
+    fn cjk_characters_are_two_columns_wide() {
+        let mut field = typed("日本語");
+        assert_eq!(field.character_index, 3);
+        assert_eq!(field.cursor_column(), 6);
+
+        field.move_cursor_left();
+        assert_eq!(field.cursor_column(), 4);
+
+        field.delete_char();
+        assert_eq!(field.input, "日語");
+        assert_eq!(field.cursor_column(), 2);
+    }
+
+    #[test]
+    fn hangul_jamo_form_one_syllable() {
+        // leading + vowel + trailing jamo render as one syllable
+        let mut field = typed("\u{1100}\u{1161}\u{11A8}");
+        assert_eq!(field.character_index, 1);
+        assert_eq!(field.cursor_column(), 2);
+
+        field.delete_char();
+        assert!(field.input.is_empty());
+    }
+
+    // Press a key in the field (in vi command mode, if it is in it)
+    fn press(field: &mut TextField, code: KeyCode, modifiers: KeyModifiers) {
+        field.handle_key(KeyEvent::new(code, modifiers));
+    }
+
+    // Press a plain key (no Ctrl or Alt) for each char of `keys`
+    fn press_keys(field: &mut TextField, keys: &str) {
+        for c in keys.chars() {
+            press(field, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    // The input with a '|' where the cursor is
+    fn with_cursor(field: &TextField) -> String {
+        let mut shown = field.input.clone();
+        shown.insert(field.byte_index(), '|');
+        shown
+    }
+
+    #[test]
+    fn home_end_and_delete() {
+        let mut field = typed("hello");
+        field.move_cursor_home();
+        field.delete_char_forward();
+        assert_eq!(with_cursor(&field), "|ello");
+
+        field.move_cursor_end();
+        field.delete_char_forward();
+        assert_eq!(with_cursor(&field), "ello|");
+    }
+
+    #[test]
+    fn delete_forward_removes_a_whole_cluster() {
+        let mut field = typed("a\u{1F44D}\u{1F3FD}b");
+        field.move_cursor_home();
+        field.move_cursor_right();
+        field.delete_char_forward();
+        assert_eq!(with_cursor(&field), "a|b");
+    }
+
+    #[test]
+    fn word_jumps_skip_punctuation_and_spaces() {
+        let mut field = typed("let x = foo_bar(1);");
+        field.move_word_left();
+        assert_eq!(with_cursor(&field), "let x = foo_bar(|1);");
+        field.move_word_left();
+        assert_eq!(with_cursor(&field), "let x = |foo_bar(1);");
+        field.move_word_left();
+        field.move_word_left();
+        field.move_word_left();
+        assert_eq!(with_cursor(&field), "|let x = foo_bar(1);");
+
+        field.move_word_right();
+        assert_eq!(with_cursor(&field), "let| x = foo_bar(1);");
+        field.move_word_right();
+        field.move_word_right();
+        assert_eq!(with_cursor(&field), "let x = foo_bar|(1);");
+    }
+
+    #[test]
+    fn word_jumps_treat_accented_letters_as_word() {
+        let mut field = typed("cafe\u{301} cre\u{300}me");
+        field.move_word_left();
+        assert_eq!(field.character_index, 5);
+        field.move_word_left();
+        assert_eq!(field.character_index, 0);
+    }
+
+    #[test]
+    fn ctrl_w_and_alt_backspace_kill_the_previous_word() {
+        let mut field = typed("one two  three");
+        press(&mut field, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "one two  |");
+
+        press(&mut field, KeyCode::Backspace, KeyModifiers::ALT);
+        assert_eq!(with_cursor(&field), "one |");
+        assert_eq!(field.kill_ring, ["three", "two  "]);
+    }
+
+    #[test]
+    fn ctrl_u_and_ctrl_k_kill_to_start_and_end() {
+        let mut field = typed("hello world");
+        field.move_word_left();
+        press(&mut field, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "hello |");
+
+        field.move_cursor_left();
+        press(&mut field, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "| ");
+        assert_eq!(field.kill_ring, ["world", "hello"]);
+    }
+
+    #[test]
+    fn ctrl_y_yanks_and_alt_y_cycles_the_kill_ring() {
+        let mut field = typed("a b c");
+        field.kill_word_before();
+        field.kill_word_before();
+        field.kill_word_before();
+        assert_eq!(field.input, "");
+
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "a |");
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&field), "b |");
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&field), "c|");
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&field), "a |");
+    }
+
+    #[test]
+    fn alt_y_after_yanking_over_a_selection() {
+        let mut field = typed("abc");
+        press(&mut field, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        press_keys(&mut field, "0123456789");
+        press(&mut field, KeyCode::Home, KeyModifiers::NONE);
+        for _ in 0..5 {
+            press(&mut field, KeyCode::Right, KeyModifiers::SHIFT);
+        }
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "abc|56789");
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&field), "abc|56789");
+    }
+
+    #[test]
+    fn alt_y_does_nothing_after_another_key() {
+        let mut field = typed("word");
+        field.kill_to_start();
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        press(&mut field, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut field, KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&field), "wor|d");
+    }
+
+    #[test]
+    fn kill_ring_forgets_the_oldest_kills() {
+        let mut field = TextField::new();
+        for i in 0..KILL_RING_SIZE + 2 {
+            field.insert_text(&i.to_string());
+            field.kill_to_start();
+        }
+        assert_eq!(field.kill_ring.len(), KILL_RING_SIZE);
+        assert_eq!(field.kill_ring[0], "2");
+    }
+
+    #[test]
+    fn control_keys_are_not_typed() {
+        let mut field = typed("ab");
+        press(&mut field, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut field, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "|ab");
+    }
+
+    #[test]
+    fn vi_esc_enters_command_mode_on_the_last_character() {
+        let mut field = typed("abc");
+        field.keymap = Keymap::Vi;
+        press(&mut field, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(field.vi_command);
+        assert_eq!(with_cursor(&field), "ab|c");
+
+        press_keys(&mut field, "$l");
+        assert_eq!(with_cursor(&field), "ab|c");
+
+        // Esc again is for the app: it cancels the input
+        assert!(!field.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn vi_motions_and_deletes() {
+        let mut field = typed("one two three");
+        field.keymap = Keymap::Vi;
+        field.vi_command = true;
+
+        press_keys(&mut field, "0w");
+        assert_eq!(with_cursor(&field), "one |two three");
+        press_keys(&mut field, "x");
+        assert_eq!(with_cursor(&field), "one |wo three");
+        press_keys(&mut field, "X");
+        assert_eq!(with_cursor(&field), "one|wo three");
+        press_keys(&mut field, "D");
+        assert_eq!(with_cursor(&field), "on|e");
+        press_keys(&mut field, "b");
+        assert_eq!(with_cursor(&field), "|one");
+    }
+
+    #[test]
+    fn vi_x_then_p_swaps_two_characters() {
+        let mut field = typed("ba");
+        field.keymap = Keymap::Vi;
+        field.vi_command = true;
+
+        press_keys(&mut field, "0xp");
+        assert_eq!(with_cursor(&field), "a|b");
+    }
+
+    #[test]
+    fn vi_insert_commands_return_to_editing() {
+        let mut field = typed("mid");
+        field.keymap = Keymap::Vi;
+        field.vi_command = true;
+
+        press_keys(&mut field, "I<");
+        assert!(!field.vi_command);
+        press(&mut field, KeyCode::Esc, KeyModifiers::NONE);
+        press_keys(&mut field, "A>");
+        assert_eq!(with_cursor(&field), "<mid>|");
+
+        press(&mut field, KeyCode::Esc, KeyModifiers::NONE);
+        press_keys(&mut field, "0aX");
+        assert_eq!(with_cursor(&field), "<X|mid>");
+
+        press(&mut field, KeyCode::Esc, KeyModifiers::NONE);
+        press_keys(&mut field, "0lCY");
+        assert_eq!(with_cursor(&field), "<Y|");
+    }
+
+    // Draw the view like the input box does: indicators on the edges
+    fn drawn(view: &InputView) -> String {
+        if !view.overflow {
+            return view.text.clone();
+        }
+        let left = if view.more_left { "<" } else { " " };
+        let right = if view.more_right { ">" } else { " " };
+        format!("{left}{}{}{right}", view.text, " ".repeat(view.padding))
+    }
+
+    #[test]
+    fn short_input_is_not_scrolled() {
+        let mut field = typed("hello");
+        let view = field.input_view(10);
+        assert_eq!(drawn(&view), "hello");
+        assert_eq!(view.cursor_x, 5);
+    }
+
+    #[test]
+    fn long_input_scrolls_to_keep_the_cursor_visible() {
+        let mut field = typed("abcdefghij");
+        let view = field.input_view(8);
+        // 6 columns between the indicators: 5 letters and the cursor
+        assert_eq!(drawn(&view), "<fghij  ");
+        assert_eq!(view.cursor_x, 6);
+
+        field.move_cursor_home();
+        let view = field.input_view(8);
+        assert_eq!(drawn(&view), " abcdef>");
+        assert_eq!(view.cursor_x, 1);
+    }
+
+    #[test]
+    fn view_only_moves_when_the_cursor_leaves_it() {
+        let mut field = typed("abcdefghij");
+        field.input_view(8);
+        field.move_cursor_left();
+        field.move_cursor_left();
+        let view = field.input_view(8);
+        assert_eq!(drawn(&view), "<fghij  ");
+        assert_eq!(view.cursor_x, 4);
+    }
+
+    #[test]
+    fn deleting_scrolls_back_to_fill_the_view() {
+        let mut field = typed("abcdefghijkl");
+        assert_eq!(drawn(&field.input_view(8)), "<hijkl  ");
+        field.delete_char();
+        field.delete_char();
+        field.delete_char();
+        let view = field.input_view(8);
+        assert_eq!(drawn(&view), "<efghi  ");
+        assert_eq!(view.cursor_x, 6);
+    }
+
+    #[test]
+    fn wide_character_at_the_right_edge_is_left_out() {
+        let mut field = typed("abcde日本");
+        field.move_cursor_home();
+        let view = field.input_view(8);
+        // "abcde" takes 5 of the 6 columns; 日 would need 2
+        assert_eq!(drawn(&view), " abcde >");
+    }
+
+    #[test]
+    fn wide_characters_scroll_by_whole_clusters() {
+        let mut field = typed("日本語のテキスト");
+        let view = field.input_view(9);
+        assert_eq!(drawn(&view), "<キスト  ");
+        assert_eq!(view.cursor_x, 7);
+
+        field.move_cursor_home();
+        let view = field.input_view(9);
+        assert_eq!(drawn(&view), " 日本語 >");
+        assert_eq!(view.cursor_x, 1);
+    }
+
+    #[test]
+    fn blanks_after_the_text_stay_out_of_its_last_cluster() {
+        // U+0603 joins whatever comes after it into one cluster, so a blank
+        // drawn with it would take its place and push the cursor aside
+        let mut field = typed("abcdefgh\u{603}");
+        let view = field.input_view(9);
+        assert_eq!(view.text, "defgh\u{603}");
+        assert_eq!(view.padding, 1);
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(11, 4)).unwrap();
+        terminal.draw(|frame| field.render(frame, frame.area(), "", true)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let row: Vec<&str> = (0..11).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert_eq!(row, ["│", "<", "d", "e", "f", "g", "h", "\u{603}", " ", " ", "│"]);
+        assert_eq!(terminal.get_cursor_position().unwrap(), Position::new(8, 1));
+    }
+
+    // Press a key with Shift (and maybe Ctrl) held
+    fn shift(field: &mut TextField, code: KeyCode) {
+        press(field, code, KeyModifiers::SHIFT);
+    }
+
+    #[test]
+    fn shift_arrows_select_whole_clusters() {
+        let mut field = typed("a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}e\u{301}");
+        shift(&mut field, KeyCode::Left);
+        assert_eq!(field.selected_text(), "e\u{301}");
+        shift(&mut field, KeyCode::Left);
+        assert_eq!(field.selected_text(), "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}e\u{301}");
+
+        // going back over the anchor selects the other way
+        field.move_cursor_end();
+        shift(&mut field, KeyCode::Home);
+        assert_eq!(field.selection(), Some(0..3));
+    }
+
+    #[test]
+    fn ctrl_shift_arrows_select_words() {
+        let mut field = typed("one two three");
+        press(&mut field, KeyCode::Left, KeyModifiers::SHIFT | KeyModifiers::CONTROL);
+        press(&mut field, KeyCode::Left, KeyModifiers::SHIFT | KeyModifiers::CONTROL);
+        assert_eq!(field.selected_text(), "two three");
+    }
+
+    #[test]
+    fn typing_replaces_the_selection() {
+        let mut field = typed("hello world");
+        shift(&mut field, KeyCode::Left);
+        shift(&mut field, KeyCode::Left);
+        press_keys(&mut field, "!");
+        assert_eq!(with_cursor(&field), "hello wor!|");
+        assert_eq!(field.selection(), None);
+    }
+
+    #[test]
+    fn backspace_and_delete_remove_the_selection() {
+        let mut field = typed("abcdef");
+        shift(&mut field, KeyCode::Left);
+        shift(&mut field, KeyCode::Left);
+        press(&mut field, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&field), "abcd|");
+
+        shift(&mut field, KeyCode::Home);
+        press(&mut field, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&field), "|");
+    }
+
+    #[test]
+    fn arrows_without_shift_collapse_the_selection() {
+        let mut field = typed("abcdef");
+        shift(&mut field, KeyCode::Left);
+        shift(&mut field, KeyCode::Left);
+        press(&mut field, KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&field), "abcdef|");
+        assert_eq!(field.selection(), None);
+
+        shift(&mut field, KeyCode::Left);
+        shift(&mut field, KeyCode::Left);
+        press(&mut field, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&field), "abcd|ef");
+    }
+
+    #[test]
+    fn copy_cut_and_paste() {
+        let mut field = typed("copy me");
+        shift(&mut field, KeyCode::Home);
+        press(&mut field, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(field.clipboard, "copy me");
+        assert_eq!(field.host_clipboard.as_deref(), Some("copy me"));
+        assert_eq!(field.selection(), Some(0..7));
+
+        press(&mut field, KeyCode::End, KeyModifiers::NONE);
+        press(&mut field, KeyCode::Char('v'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "copy mecopy me|");
+
+        press(&mut field, KeyCode::Left, KeyModifiers::SHIFT | KeyModifiers::CONTROL);
+        press(&mut field, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "copy mecopy |");
+        assert_eq!(field.clipboard, "me");
+
+        // pasting over a selection replaces it
+        shift(&mut field, KeyCode::Home);
+        press(&mut field, KeyCode::Char('v'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&field), "me|");
+    }
+
+    #[test]
+    fn other_keys_end_the_selection() {
+        let mut field = typed("abc");
+        shift(&mut field, KeyCode::Left);
+        press(&mut field, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(field.selection(), None);
+    }
+
+    #[test]
+    fn view_marks_the_selected_bytes() {
+        let mut field = typed("日本語");
+        shift(&mut field, KeyCode::Left);
+        let view = field.input_view(20);
+        assert_eq!(&view.text[view.selected], "語");
+
+        // only the part of the selection that is in view
+        let mut field = typed("abcdefghij");
+        field.move_cursor_home();
+        shift(&mut field, KeyCode::End);
+        let view = field.input_view(8);
+        assert_eq!(drawn(&view), "<fghij  ");
+        assert_eq!(&view.text[view.selected], "fghij");
+    }
+
+    #[test]
+    fn pasted_line_breaks_become_spaces() {
+        assert_eq!(single_line("one\ntwo\r\nthree\rfour\n"), "one two three four");
+        assert_eq!(single_line("tab\there\u{7}"), "tab here");
+    }
+
+    #[test]
+    fn paste_replaces_the_selection() {
+        let mut field = typed("hello world");
+        press(&mut field, KeyCode::Left, KeyModifiers::SHIFT | KeyModifiers::CONTROL);
+        field.paste("👋🏽");
+        assert_eq!(with_cursor(&field), "hello 👋🏽|");
+    }
+
+    #[test]
+    fn large_paste_is_one_insert() {
+        let mut field = TextField::new();
+        let text = "é👍🏽".repeat(50_000);
+        field.paste(&text);
+        assert_eq!(field.character_index, 100_000);
+        assert_eq!(field.input, text);
+    }
+
+    #[test]
+    fn cursor_never_leaves_the_text() {
+        let mut field = typed("\u{1F44D}\u{1F3FD}");
+        field.move_cursor_right();
+        assert_eq!(field.character_index, 1);
+
+        field.move_cursor_left();
+        field.move_cursor_left();
+        assert_eq!(field.character_index, 0);
+
+        field.delete_char();
+        assert_eq!(field.input, "\u{1F44D}\u{1F3FD}");
+    }
+
+    // `field` after typing `keys` into it
+    fn checked(mut field: TextField, keys: &str) -> TextField {
+        press_keys(&mut field, keys);
+        field
+    }
+
+    #[test]
+    fn errors_show_only_after_editing() {
+        let mut field = TextField::new().validator(Validator::NonEmpty);
+        assert_eq!(field.error(), None);
+        assert_eq!(field.state(), FieldState::Neutral);
+
+        press_keys(&mut field, "a");
+        assert_eq!(field.state(), FieldState::Valid);
+        press(&mut field, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(field.error().as_deref(), Some("Can't be empty"));
+        assert_eq!(field.state(), FieldState::Invalid);
+    }
+
+    #[test]
+    fn submit_refuses_invalid_values() {
+        let mut field = checked(TextField::new().validator(Validator::MaxLength(3)), "abcd");
+        assert_eq!(field.submit(), None);
+        assert_eq!(field.input, "abcd");
+        assert_eq!(field.error().as_deref(), Some("At most 3 characters"));
+
+        press(&mut field, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(field.submit().as_deref(), Some("abc"));
+        assert_eq!(field.input, "");
+        assert_eq!(field.error(), None);
+    }
+
+    #[test]
+    fn max_length_counts_graphemes() {
+        let field = checked(TextField::new().validator(Validator::MaxLength(2)), "e\u{301}\u{1F44D}\u{1F3FD}");
+        assert_eq!(field.validate(), Ok(()));
+    }
+
+    #[test]
+    fn range_wants_a_number_in_range() {
+        let range = Validator::Range(1.0..=10.0);
+        assert_eq!(range.check("7.5"), Ok(()));
+        assert_eq!(range.check("11").unwrap_err(), "A number from 1 to 10");
+        assert!(range.check("ten").is_err());
+    }
+
+    #[test]
+    fn only_non_empty_rejects_a_blank_field() {
+        let field = TextField::new().validator(Validator::Range(1.0..=10.0));
+        assert_eq!(field.validate(), Ok(()));
+        assert!(Validator::NonEmpty.check("   ").is_err());
+    }
+
+    #[test]
+    fn regex_must_match_the_whole_value() {
+        let email = || Validator::regex(r"[^@\s]+@[^@\s]+", "an email address").unwrap();
+        assert_eq!(email().check("me@example.com"), Ok(()));
+        assert_eq!(email().check("say me@example.com").unwrap_err(), "Should be an email address");
+        assert!(Validator::regex("(", "broken").is_err());
+    }
+
+    #[test]
+    fn dates_must_exist() {
+        let date = Validator::Date("%Y-%m-%d");
+        assert_eq!(date.check("2026-02-28"), Ok(()));
+        assert_eq!(date.check("2026-02-30").unwrap_err(), "Should be like 2026-02-13");
+        assert_eq!(Validator::Date("%H:%M").check("24:00").unwrap_err(), "Should be like 14:30");
+    }
+
+    #[test]
+    fn template_takes_only_digits_and_fills_the_slots() {
+        let mut field = checked(TextField::new().mask(Mask::Template("__:__")), "1a4:3x09");
+        assert_eq!(field.input, "1430");
+        assert_eq!(field.value(), "14:30");
+
+        // the cursor is drawn in the slot of the next digit
+        field.delete_char();
+        assert_eq!(field.value(), "14:3_");
+        assert_eq!(field.cursor_column(), 4);
+        field.move_cursor_left();
+        field.move_cursor_left();
+        assert_eq!(field.cursor_column(), 1);
+    }
+
+    #[test]
+    fn template_must_be_filled_in() {
+        let field = checked(TextField::new().mask(Mask::Template("__:__")).validator(Validator::Date("%H:%M")), "143");
+        assert_eq!(field.error().as_deref(), Some("Fill in every _ of __:__"));
+        assert_eq!(drawn(&checked(TextField::new().mask(Mask::Template("__:__")), "1").input_view(20)), "1_:__");
+    }
+
+    #[test]
+    fn template_value_can_be_set_back() {
+        let mut field = TextField::new().mask(Mask::Template("____-__-__"));
+        field.set_value("2026-02-13");
+        assert_eq!(field.input, "20260213");
+        assert_eq!(field.value(), "2026-02-13");
+    }
+
+    #[test]
+    fn password_is_drawn_as_dots_and_cannot_be_copied() {
+        let mut field = checked(TextField::new().mask(Mask::Password), "s3cr\u{1F44D}\u{1F3FD}t");
+        let view = field.input_view(20);
+        assert_eq!(view.text, "••••••");
+        assert_eq!(view.cursor_x, 6);
+
+        shift(&mut field, KeyCode::Home);
+        press(&mut field, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        press(&mut field, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(field.clipboard, "");
+        assert_eq!(field.host_clipboard, None);
+        assert_eq!(field.value(), "s3cr\u{1F44D}\u{1F3FD}t");
+    }
+
+    #[test]
+    fn typing_a_word_opens_the_completion() {
+        let mut field = TextField::new().completer(complete::WordList::new(["apple", "apricot"]));
+        press_keys(&mut field, "eat ap");
+        assert!(field.completing());
+
+        // moving the cursor closes it, Tab asks again
+        press(&mut field, KeyCode::Left, KeyModifiers::NONE);
+        assert!(!field.completing());
+        press(&mut field, KeyCode::Right, KeyModifiers::NONE);
+        press(&mut field, KeyCode::Tab, KeyModifiers::NONE);
+        press(&mut field, KeyCode::Tab, KeyModifiers::NONE);
+        press(&mut field, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&field), "eat apricot|");
+        assert!(!field.completing());
+
+        // a space ends the word, so there is nothing to complete
+        press_keys(&mut field, " ");
+        assert!(!field.completing());
+    }
+
+    #[test]
+    fn render_shows_the_error_under_the_box() {
+        use ratatui::backend::TestBackend;
+
+        let mut field = checked(TextField::new().validator(Validator::MaxLength(2)), "abc");
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(24, 4)).unwrap();
+        terminal.draw(|frame| field.render(frame, frame.area(), "Hi: ", true)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| (0..24).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(1), "│Hi: abc               │");
+        assert_eq!(row(3).trim_end(), " At most 2 characters");
+        assert_eq!(buffer[(0, 0)].fg, Color::Red);
+    }
+}
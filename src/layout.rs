@@ -0,0 +1,28 @@
+// Ratatui's layout engine
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+
+
+// A rectangle in the middle of `area`, `percent_width` of its width and
+// `percent_height` of its height (for popups and dialogs)
+pub fn centered_rect(percent_width: u16, percent_height: u16, area: Rect) -> Rect {
+    let [area] = Layout::vertical([Constraint::Percentage(percent_height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Percentage(percent_width)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+
+// TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centered_rect_is_in_the_middle() {
+        let area = Rect::new(0, 0, 100, 40);
+        assert_eq!(centered_rect(50, 25, area), Rect::new(25, 15, 50, 10));
+    }
+}
@@ -0,0 +1,21 @@
+// RUST TUI LAB
+// What the exercises share, so each binary only holds its own app:
+// - terminal: a Session that sets the terminal up and puts it back
+// - style and layout: the common looks and a centered popup area
+// - widgets: a list that remembers its selection
+// - field: a one-line text field (cursor by grapheme cluster, kill ring,
+//   selection, scrolling), with validator rules and masks for passwords
+//   and templates
+// - textarea: a multi-line box built on the field, with soft wrap and
+//   line-aware keys (ex04's notes, ex05's journal)
+// - complete: word completion with a dropdown (the field's, ex05's tags)
+// Use it from a binary as `rust_tui_lab::...`
+pub mod complete;
+pub mod field;
+pub mod layout;
+pub mod mask;
+pub mod style;
+pub mod terminal;
+pub mod textarea;
+pub mod validator;
+pub mod widgets;
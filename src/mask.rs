@@ -0,0 +1,66 @@
+// How a field SHOWS what was typed, without changing what was typed
+#[derive(Clone, Default)]
+pub enum Mask {
+    // The text as it is
+    #[default]
+    Plain,
+    // Every character drawn as a dot, like a password box
+    Password,
+    // A fixed format like "__:__" or "____-__-__": each `_` is a slot for
+    // one digit, everything else is filled in for you
+    Template(&'static str),
+}
+
+// The character that marks a slot in a template
+pub const SLOT: char = '_';
+
+impl Mask {
+    // Whether the text must not be shown (or copied, or remembered)
+    pub fn is_secret(&self) -> bool {
+        matches!(self, Mask::Password)
+    }
+
+    // How many characters can be typed (None = no limit)
+    pub fn slots(&self) -> Option<usize> {
+        match self {
+            Mask::Template(template) => Some(template.chars().filter(|&c| c == SLOT).count()),
+            _ => None,
+        }
+    }
+
+    // The part of `text` that may be typed when `filled` characters are
+    // there already: a template only takes digits, up to its free slots
+    pub fn accept(&self, text: &str, filled: usize) -> String {
+        match self.slots() {
+            Some(slots) => text
+                .chars()
+                .filter(char::is_ascii_digit)
+                .take(slots.saturating_sub(filled))
+                .collect(),
+            None => text.to_string(),
+        }
+    }
+
+    // The value of the field: the template with the typed digits in its
+    // slots ("12:3_"), or nothing when nothing was typed
+    pub fn format(&self, typed: &str) -> String {
+        match self {
+            Mask::Template(template) if !typed.is_empty() => {
+                let mut digits = typed.chars();
+                template
+                    .chars()
+                    .map(|c| if c == SLOT { digits.next().unwrap_or(SLOT) } else { c })
+                    .collect()
+            }
+            _ => typed.to_string(),
+        }
+    }
+
+    // The opposite of `format`: what has to be typed to get `value`
+    pub fn typed(&self, value: &str) -> String {
+        match self {
+            Mask::Template(_) => self.accept(value, 0),
+            _ => value.to_string(),
+        }
+    }
+}
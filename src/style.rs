@@ -0,0 +1,50 @@
+// Ratatui styles, text and blocks
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Padding};
+
+
+// SHARED STYLES
+// The exercises use the same few looks everywhere: white text, dim gray
+// text for hints, bold titles and an inverted entry for the current page
+
+// Normal text, or gray text when `dim` (hints, disabled things)
+pub fn default_style_text(dim: bool) -> Style {
+    if dim {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default().fg(Color::White)
+    }
+}
+
+// A bold title line
+pub fn title_text(title: &str, dim: bool) -> Line<'static> {
+    Line::from(Span::styled(title.to_string(), default_style_text(dim).add_modifier(Modifier::BOLD)))
+}
+
+// An entry of a menu: black on white when it is the one selected
+pub fn menu_style(selected: bool) -> Style {
+    if selected {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::White)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        default_style_text(false)
+    }
+}
+
+// The highlighted row of a list
+pub fn list_highlight_style() -> Style {
+    Style::default()
+        .fg(Color::White)
+        .bg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD)
+}
+
+// A block with only some `borders` and room inside them
+pub fn panel_block_with_padding_borders(left: u16, right: u16, top: u16, bottom: u16, borders: Borders) -> Block<'static> {
+    Block::default()
+        .borders(borders)
+        .padding(Padding::new(left, right, top, bottom))
+}
@@ -0,0 +1,154 @@
+use std::io::{self, Stdout};
+use std::ops::{Deref, DerefMut};
+use std::panic;
+use std::sync::Once;
+
+// Crossterm switches the terminal's modes
+use crossterm::cursor::Show;
+use crossterm::event::{
+    DisableBracketedPaste, EnableBracketedPaste, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
+};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen, LeaveAlternateScreen,
+};
+
+// Ratatui draws on the terminal through a backend
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+
+// A TERMINAL SESSION
+// Every exercise does the same dance:
+// - start: raw mode (keys arrive one by one, nothing is echoed) and the
+//   alternate screen (the shell's screen comes back untouched afterwards)
+// - draw and read keys in a loop
+// - stop: leave the alternate screen, raw mode off, cursor visible again
+// A Session does the start when it is created and the stop when it is
+// dropped, so the terminal is put back even when `?` returns early, or
+// the program panics halfway
+//
+//     let mut terminal = Session::start()?;
+//     loop {
+//         terminal.draw(|frame| { ... })?;
+//         ...
+//     }
+//     terminal.finish()?;
+//
+// It derefs to the ratatui Terminal, so draw(), backend_mut()... work on it
+pub struct Session {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    bracketed_paste: bool, // turned on with with_bracketed_paste()
+    enhanced_keys: bool,   // turned on with with_keyboard_enhancement()
+    active: bool,          // false once the terminal was put back
+}
+
+impl Session {
+    // Raw mode and the alternate screen
+    pub fn start() -> io::Result<Self> {
+        restore_on_panic();
+
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+
+        let terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        Ok(Self { terminal, bracketed_paste: false, enhanced_keys: false, active: true })
+    }
+
+    // Bracketed paste: the terminal sends a paste as one Event::Paste
+    // instead of typing it key by key
+    pub fn with_bracketed_paste(mut self) -> io::Result<Self> {
+        execute!(self.terminal.backend_mut(), EnableBracketedPaste)?;
+        self.bracketed_paste = true;
+        Ok(self)
+    }
+
+    // Ask the terminal to tell apart keys it normally sends the same way,
+    // like Ctrl-Enter and Enter (the kitty keyboard protocol)
+    // Terminals that don't speak it are left alone; keyboard_enhanced()
+    // says which one we got
+    pub fn with_keyboard_enhancement(mut self) -> io::Result<Self> {
+        if supports_keyboard_enhancement().unwrap_or(false) {
+            execute!(
+                self.terminal.backend_mut(),
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+            )?;
+            self.enhanced_keys = true;
+        }
+        Ok(self)
+    }
+
+    pub fn keyboard_enhanced(&self) -> bool {
+        self.enhanced_keys
+    }
+
+    // Put the terminal back and report what went wrong doing so
+    // (dropping the session does the same, but has to keep quiet)
+    pub fn finish(mut self) -> io::Result<()> {
+        self.restore()
+    }
+
+    // Undo start() and the with_... calls, in reverse order
+    // Every step runs even when one before it failed (a step that fails
+    // must not leave the alternate screen on); the first error is returned
+    fn restore(&mut self) -> io::Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+
+        let backend = self.terminal.backend_mut();
+        let results = [
+            disable_raw_mode(),
+            if self.enhanced_keys { execute!(backend, PopKeyboardEnhancementFlags) } else { Ok(()) },
+            if self.bracketed_paste { execute!(backend, DisableBracketedPaste) } else { Ok(()) },
+            execute!(backend, LeaveAlternateScreen),
+            self.terminal.show_cursor(),
+        ];
+        results.into_iter().collect()
+    }
+}
+
+impl Deref for Session {
+    type Target = Terminal<CrosstermBackend<Stdout>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl DerefMut for Session {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+// A panic message printed on the alternate screen, in raw mode, is lost
+// (or unreadable), so the terminal is put back first
+// The panic hook that was there before (color_eyre's, when installed)
+// still prints the message afterwards
+fn restore_on_panic() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // One at a time: a terminal may refuse one (Windows can't pop
+            // keyboard flags) and the others still have to happen
+            let mut stdout = io::stdout();
+            let _ = disable_raw_mode();
+            let _ = execute!(stdout, LeaveAlternateScreen);
+            let _ = execute!(stdout, Show);
+            let _ = execute!(stdout, DisableBracketedPaste);
+            let _ = execute!(stdout, PopKeyboardEnhancementFlags);
+            previous(info);
+        }));
+    });
+}
@@ -0,0 +1,540 @@
+// Key events from the terminal
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+// Ratatui, to draw the box
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+// Counting characters the way a person does (see field.rs)
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::field::{grapheme_width, FieldState, TextField};
+use crate::validator::Validator;
+
+
+// A MULTI-LINE TEXT AREA
+// The text is kept in a TextField, line breaks included, so every
+// editing key of the one-line field (words, kill ring, selection, vi...)
+// works here too. On top of that the area knows about lines:
+// - long lines wrap at the edge of the box (soft wrap: no '\n' is added)
+// - Up/Down move one row on screen and try to stay in the same column
+// - Home/End (Ctrl-A/E, vi 0/$) and Ctrl-K/U work on the current line
+// - Enter starts a new line; the submit keys (Ctrl-Enter) hand in the text
+// The methods are named like TextField's, so a form can hold both
+pub struct TextArea {
+    pub editor: TextField,      // The text, the cursor and the editing keys
+    line_numbers: bool,         // Show a line number in front of each line
+    submit_keys: Vec<(KeyCode, KeyModifiers)>, // Keys that submit instead of editing
+    desired_column: Option<usize>, // Column Up/Down aim for (kept while moving up and down)
+    wrap_width: usize,          // Columns a row may use (known after the first draw)
+    height: usize,              // Rows the box shows (known after the first draw)
+    scroll: usize,              // First row shown
+}
+
+// One row on screen: part of a line, from grapheme `start` up to `end`
+// (indices into the whole text; the '\n' itself is never in a row)
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Row {
+    start: usize,
+    end: usize,
+    line: usize,  // which line it belongs to (0 = first)
+    first: bool,  // the first row of its line (where the number goes)
+    last: bool,   // the last row of its line
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextArea {
+    // An empty area; Ctrl-Enter submits
+    pub fn new() -> Self {
+        Self {
+            editor: TextField::new(),
+            line_numbers: false,
+            submit_keys: vec![(KeyCode::Enter, KeyModifiers::CONTROL)],
+            desired_column: None,
+            wrap_width: usize::MAX, // no wrapping until we know the width
+            height: 1,
+            scroll: 0,
+        }
+    }
+
+    // Add a rule the value has to follow
+    pub fn validator(mut self, validator: Validator) -> Self {
+        self.editor = self.editor.validator(validator);
+        self
+    }
+
+    // Number the lines (rows that continue a wrapped line get no number)
+    pub fn line_numbers(mut self, on: bool) -> Self {
+        self.line_numbers = on;
+        self
+    }
+
+    // Which keys submit, e.g. [(KeyCode::Char('s'), KeyModifiers::CONTROL)]
+    // Many terminals send Ctrl-Enter as a plain Enter, so an app can offer
+    // another key as well
+    pub fn submit_keys(mut self, keys: impl IntoIterator<Item = (KeyCode, KeyModifiers)>) -> Self {
+        self.submit_keys = keys.into_iter().collect();
+        self
+    }
+
+    // VALUE AND VALIDATION (the same as TextField)
+
+    pub fn value(&self) -> String {
+        self.editor.value()
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.editor.error()
+    }
+
+    pub fn state(&self) -> FieldState {
+        self.editor.state()
+    }
+
+    // The text when it is valid, and the area is cleared for the next one
+    pub fn submit(&mut self) -> Option<String> {
+        let value = self.editor.submit()?;
+        self.clear();
+        Some(value)
+    }
+
+    // Empty the area (the keymap, kill ring and clipboard stay)
+    pub fn clear(&mut self) {
+        self.editor.clear();
+        self.desired_column = None;
+        self.scroll = 0;
+    }
+
+    // Start over with `value` in the area, cursor at the end
+    pub fn set_value(&mut self, value: &str) {
+        self.clear();
+        self.editor.set_value(&multi_line(value));
+    }
+
+    // Text pasted into the terminal: the line breaks stay
+    pub fn paste(&mut self, text: &str) {
+        self.desired_column = None;
+        self.editor.insert_text(&multi_line(text));
+        self.editor.touched = true;
+    }
+
+    // KEYS
+
+    // Whether the app should submit instead of passing the key on
+    pub fn is_submit_key(&self, key: KeyEvent) -> bool {
+        self.submit_keys.iter().any(|&(code, modifiers)| key.code == code && key.modifiers == modifiers)
+    }
+
+    // Handle a key while the area has focus
+    // Like TextField::handle_key, false means "leave the area" (Esc)
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let vi = self.editor.vi_command;
+        let before = self.editor.input.clone();
+
+        // Only moving up and down remembers the column
+        let vertical = matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown)
+            || (vi && matches!(key.code, KeyCode::Char('j' | 'k')));
+        if !vertical {
+            self.desired_column = None;
+        }
+
+        let page = self.height.max(1) as isize;
+        match key.code {
+            KeyCode::Up => self.move_rows(-1, shift),
+            KeyCode::Down => self.move_rows(1, shift),
+            KeyCode::PageUp => self.move_rows(-page, shift),
+            KeyCode::PageDown => self.move_rows(page, shift),
+            KeyCode::Char('k') if vi => self.move_rows(-1, false),
+            KeyCode::Char('j') if vi => self.move_rows(1, false),
+
+            // Home and End stay on the line; Ctrl-Home/End go through to the field
+            KeyCode::Home if !ctrl => self.editor.move_cursor_to(self.line_start(), shift),
+            KeyCode::End if !ctrl => self.editor.move_cursor_to(self.line_end(), shift),
+            KeyCode::Char('a') if ctrl && !vi => self.editor.move_cursor_to(self.line_start(), false),
+            KeyCode::Char('e') if ctrl && !vi => self.editor.move_cursor_to(self.line_end(), false),
+            KeyCode::Char('0' | '^') if vi => self.editor.move_cursor_to(self.line_start(), false),
+            // vi keeps the cursor ON the last character
+            KeyCode::Char('$') if vi => {
+                let end = self.line_end().saturating_sub(1).max(self.line_start());
+                self.editor.move_cursor_to(end, false);
+            }
+
+            // Ctrl-K kills to the end of the line, or the line break when
+            // already there (so pressing it again joins the next line)
+            KeyCode::Char('k') if ctrl && !vi => {
+                let cursor = self.editor.character_index;
+                let end = self.line_end();
+                let end = if end == cursor { (end + 1).min(self.editor.grapheme_count()) } else { end };
+                self.editor.kill(cursor, end);
+            }
+            KeyCode::Char('u') if ctrl && !vi => self.editor.kill(self.line_start(), self.editor.character_index),
+
+            KeyCode::Enter if !vi && key.modifiers.is_empty() => self.editor.insert_text("\n"),
+
+            _ => {
+                if !self.editor.handle_key(key) {
+                    return false;
+                }
+            }
+        }
+
+        if self.editor.input != before {
+            self.editor.touched = true;
+        }
+        true
+    }
+
+    // MOVING BETWEEN ROWS
+
+    // Move `delta` rows on screen (negative = up), keeping the column
+    // Moving past the first or last row goes to the start or end of the text
+    fn move_rows(&mut self, delta: isize, select: bool) {
+        let graphemes = graphemes(&self.editor.input);
+        let rows = rows(&graphemes, self.wrap_width);
+        let cursor = self.editor.character_index;
+        let current = cursor_row(&rows, cursor);
+
+        let column = *self.desired_column.get_or_insert_with(|| column_of(&graphemes, rows[current], cursor));
+
+        let target = current as isize + delta;
+        let index = if target < 0 {
+            0
+        } else if target as usize >= rows.len() {
+            graphemes.len()
+        } else {
+            index_at_column(&graphemes, rows[target as usize], column)
+        };
+        self.editor.move_cursor_to(index, select);
+    }
+
+    // Grapheme index where the cursor's line starts
+    fn line_start(&self) -> usize {
+        let graphemes = graphemes(&self.editor.input);
+        let cursor = self.editor.character_index;
+        (0..cursor).rev().find(|&i| graphemes[i] == "\n").map_or(0, |i| i + 1)
+    }
+
+    // Grapheme index of the '\n' ending the cursor's line (or the end)
+    fn line_end(&self) -> usize {
+        let graphemes = graphemes(&self.editor.input);
+        let cursor = self.editor.character_index;
+        (cursor..graphemes.len()).find(|&i| graphemes[i] == "\n").unwrap_or(graphemes.len())
+    }
+
+    // RENDERING
+
+    // Draw the area in `area`: a box titled `label`, with the error (if
+    // any) on the last row. Drawing also tells the area how wide and tall
+    // it is, for wrapping, Up/Down and PageUp/PageDown
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, label: &str, focused: bool) {
+        let [box_area, error_area] = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(area);
+
+        let graphemes = graphemes(&self.editor.input);
+        let line_count = graphemes.iter().filter(|&&g| g == "\n").count() + 1;
+        let gutter = if self.line_numbers { line_count.to_string().len() + 1 } else { 0 };
+
+        // One column stays free for the cursor at the end of a row
+        let text_width = (box_area.width as usize).saturating_sub(2 + gutter);
+        self.wrap_width = text_width.saturating_sub(1).max(1);
+        self.height = (box_area.height as usize).saturating_sub(2).max(1);
+
+        // Scroll just enough to keep the cursor's row in view
+        let rows = rows(&graphemes, self.wrap_width);
+        let cursor = self.editor.character_index;
+        let cursor_row = cursor_row(&rows, cursor);
+        self.scroll = self
+            .scroll
+            .min(rows.len().saturating_sub(self.height))
+            .min(cursor_row)
+            .max((cursor_row + 1).saturating_sub(self.height));
+
+        // The selection is drawn in reverse video
+        let selection = self.editor.selection().unwrap_or(0..0);
+        let number_style = Style::default().fg(Color::DarkGray);
+
+        let lines: Vec<Line> = rows
+            .iter()
+            .skip(self.scroll)
+            .take(self.height)
+            .map(|row| {
+                let mut spans = Vec::new();
+                if self.line_numbers {
+                    let number = if row.first { (row.line + 1).to_string() } else { String::new() };
+                    spans.push(Span::styled(format!("{number:>0$} ", gutter - 1), number_style));
+                }
+                let from = selection.start.clamp(row.start, row.end);
+                let to = selection.end.clamp(row.start, row.end);
+                spans.push(Span::raw(graphemes[row.start..from].concat()));
+                spans.push(Span::styled(graphemes[from..to].concat(), Style::default().add_modifier(Modifier::REVERSED)));
+                spans.push(Span::raw(graphemes[to..row.end].concat()));
+                Line::from(spans)
+            })
+            .collect();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(label)
+            .border_style(self.state().border_style());
+        frame.render_widget(Paragraph::new(lines).block(block), box_area);
+
+        if let Some(error) = self.error() {
+            let error_line = Paragraph::new(Span::styled(format!(" {error}"), Style::default().fg(Color::Red)));
+            frame.render_widget(error_line, error_area);
+        }
+
+        if focused {
+            let x = box_area.x + 1 + (gutter + column_of(&graphemes, rows[cursor_row], cursor)) as u16;
+            let y = box_area.y + 1 + (cursor_row - self.scroll) as u16;
+            frame.set_cursor_position(Position::new(x, y));
+        }
+    }
+}
+
+// The grapheme clusters of `text`
+fn graphemes(text: &str) -> Vec<&str> {
+    text.graphemes(true).collect()
+}
+
+// Split the text into the rows shown on screen, `width` columns at most
+// A line that is too long breaks after its last space that still fits,
+// or in the middle of a word when the word alone is too long
+fn rows(graphemes: &[&str], width: usize) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut line_start = 0;
+    let mut line = 0;
+
+    loop {
+        let line_end = (line_start..graphemes.len())
+            .find(|&i| graphemes[i] == "\n")
+            .unwrap_or(graphemes.len());
+
+        let mut start = line_start;
+        loop {
+            let end = wrap_end(graphemes, start, line_end, width);
+            let last = end == line_end;
+            rows.push(Row { start, end, line, first: start == line_start, last });
+            if last {
+                break;
+            }
+            start = end;
+        }
+
+        if line_end == graphemes.len() {
+            return rows;
+        }
+        line_start = line_end + 1; // skip the '\n'
+        line += 1;
+    }
+}
+
+// Where the row that starts at `start` ends
+fn wrap_end(graphemes: &[&str], start: usize, line_end: usize, width: usize) -> usize {
+    let mut used = 0;
+    let mut end = start;
+    let mut after_space = None;
+
+    while end < line_end && used + grapheme_width(graphemes[end]) <= width {
+        if graphemes[end].trim().is_empty() {
+            after_space = Some(end + 1);
+        }
+        used += grapheme_width(graphemes[end]);
+        end += 1;
+    }
+
+    if end < line_end {
+        end = after_space.unwrap_or(end);
+    }
+    // a character wider than the whole row still gets a row of its own
+    end.max(start + 1).min(line_end)
+}
+
+// The row the cursor is on
+// At the point where a line wraps, the cursor goes to the start of the
+// next row, except at the very end of a line
+fn cursor_row(rows: &[Row], cursor: usize) -> usize {
+    rows.iter()
+        .position(|row| row.start <= cursor && (cursor < row.end || (row.last && cursor == row.end)))
+        .unwrap_or(rows.len() - 1)
+}
+
+// Screen column of grapheme `index` within `row`
+fn column_of(graphemes: &[&str], row: Row, index: usize) -> usize {
+    graphemes[row.start..index].iter().map(|g| grapheme_width(g)).sum()
+}
+
+// The grapheme in `row` closest to `column`, without going past it
+fn index_at_column(graphemes: &[&str], row: Row, column: usize) -> usize {
+    // the end of a wrapped row is already the start of the next one
+    let limit = if row.last { row.end } else { row.end - 1 };
+    let mut index = row.start;
+    let mut used = 0;
+    while index < limit && used + grapheme_width(graphemes[index]) <= column {
+        used += grapheme_width(graphemes[index]);
+        index += 1;
+    }
+    index
+}
+
+// Text for a multi-line area: every line break becomes '\n', tabs become
+// spaces and other control characters go
+pub fn multi_line(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .chars()
+        .filter_map(|c| match c {
+            '\r' => Some('\n'),
+            '\t' => Some(' '),
+            c if c.is_control() && c != '\n' => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+
+// TESTS
+// Run with: cargo test --lib
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    // An area holding `text`, `width` columns for the text
+    fn area(text: &str, width: usize) -> TextArea {
+        let mut area = TextArea::new();
+        area.paste(text);
+        area.wrap_width = width;
+        area
+    }
+
+    // The text with a '|' where the cursor is
+    fn with_cursor(area: &TextArea) -> String {
+        let mut text = area.editor.input.clone();
+        text.insert(area.editor.byte_index(), '|');
+        text
+    }
+
+    #[test]
+    fn long_lines_wrap_after_a_space() {
+        let text = graphemes("the quick brown fox\nhi");
+        let wrapped = rows(&text, 10);
+        let shown: Vec<String> = wrapped.iter().map(|row| text[row.start..row.end].concat()).collect();
+        assert_eq!(shown, ["the quick ", "brown fox", "hi"]);
+        assert_eq!(wrapped.iter().map(|row| (row.line, row.first)).collect::<Vec<_>>(), [(0, true), (0, false), (1, true)]);
+
+        // no space to break at: the word is cut
+        let word = graphemes("abcdefgh");
+        assert_eq!(rows(&word, 3).len(), 3);
+    }
+
+    #[test]
+    fn up_and_down_keep_the_column() {
+        let mut area = area("a long line\nab\nanother line", 80);
+        area.editor.move_cursor_to(8, false); // "a long l|ine"
+
+        area.handle_key(key(KeyCode::Down));
+        assert_eq!(with_cursor(&area), "a long line\nab|\nanother line");
+        area.handle_key(key(KeyCode::Down));
+        assert_eq!(with_cursor(&area), "a long line\nab\nanother |line");
+        area.handle_key(key(KeyCode::Up));
+        area.handle_key(key(KeyCode::Up));
+        assert_eq!(with_cursor(&area), "a long l|ine\nab\nanother line");
+
+        // anything else forgets the column
+        area.handle_key(key(KeyCode::Left));
+        area.handle_key(key(KeyCode::Up));
+        assert_eq!(area.editor.character_index, 0);
+    }
+
+    #[test]
+    fn up_and_down_move_through_wrapped_rows() {
+        let mut area = area("the quick brown fox", 10);
+        area.editor.move_cursor_to(2, false); // "th|e"
+        area.handle_key(key(KeyCode::Down));
+        assert_eq!(with_cursor(&area), "the quick br|own fox");
+        area.handle_key(key(KeyCode::Down));
+        assert_eq!(area.editor.character_index, area.editor.grapheme_count());
+    }
+
+    #[test]
+    fn home_end_and_kills_stay_on_the_line() {
+        let mut area = area("one\ntwo words\nthree", 80);
+        area.editor.move_cursor_to(6, false); // "tw|o"
+
+        area.handle_key(key(KeyCode::End));
+        assert_eq!(with_cursor(&area), "one\ntwo words|\nthree");
+        area.handle_key(ctrl('a'));
+        assert_eq!(with_cursor(&area), "one\n|two words\nthree");
+
+        area.handle_key(ctrl('k'));
+        assert_eq!(with_cursor(&area), "one\n|\nthree");
+        area.handle_key(ctrl('k')); // at the end of the line: joins the next one
+        assert_eq!(with_cursor(&area), "one\n|three");
+        area.handle_key(ctrl('y')); // the newest kill: the line break
+        assert_eq!(with_cursor(&area), "one\n\n|three");
+    }
+
+    #[test]
+    fn enter_breaks_the_line_and_backspace_joins_it() {
+        let mut area = area("ab", 80);
+        area.editor.move_cursor_to(1, false);
+        area.handle_key(key(KeyCode::Enter));
+        assert_eq!(with_cursor(&area), "a\n|b");
+        area.handle_key(key(KeyCode::Backspace));
+        assert_eq!(with_cursor(&area), "a|b");
+    }
+
+    #[test]
+    fn ctrl_enter_submits_or_another_key() {
+        let mut area = TextArea::new().validator(Validator::NonEmpty);
+        let ctrl_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        assert!(area.is_submit_key(ctrl_enter));
+        assert!(!area.is_submit_key(key(KeyCode::Enter)));
+        assert!(area.submit().is_none());
+
+        area.paste("first\r\nsecond\tline");
+        assert_eq!(area.submit().as_deref(), Some("first\nsecond line"));
+        assert_eq!(area.value(), "");
+
+        let area = TextArea::new().submit_keys([(KeyCode::Char('s'), KeyModifiers::CONTROL)]);
+        assert!(area.is_submit_key(ctrl('s')));
+        assert!(!area.is_submit_key(ctrl_enter));
+    }
+
+    #[test]
+    fn scrolls_to_the_cursor_with_line_numbers() {
+        let mut area = TextArea::new().line_numbers(true);
+        area.paste("1\n2\n3\n4\n5\n6\n7\n8\n9\n10");
+        let mut terminal = Terminal::new(TestBackend::new(12, 6)).unwrap();
+        terminal.draw(|frame| area.render(frame, frame.area(), "Notes", true)).unwrap();
+
+        // 6 rows: the box has 3 for text, the last one is for errors
+        let screen = terminal.backend().buffer().clone();
+        let row = |y: u16| (0..12).map(|x| screen[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(0), "┌Notes─────┐");
+        assert_eq!(row(1), "│ 8 8      │");
+        assert_eq!(row(3), "│10 10     │");
+        assert_eq!(terminal.get_cursor_position().unwrap(), Position::new(6, 3));
+
+        // PageUp moves a box-full of rows up
+        area.handle_key(key(KeyCode::PageUp));
+        assert_eq!(with_cursor(&area), "1\n2\n3\n4\n5\n6\n7|\n8\n9\n10");
+    }
+}
@@ -0,0 +1,69 @@
+use std::ops::RangeInclusive;
+
+// Dates and times, for checking dates
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+// Regular expressions, for checking a pattern
+use regex::Regex;
+
+// Counting characters the way a person does (see field.rs)
+use unicode_segmentation::UnicodeSegmentation;
+
+// A rule the value of a field has to follow
+// Only NonEmpty rejects an empty field: the other rules check what was
+// typed, so a field without NonEmpty may be left blank
+pub enum Validator {
+    // Something other than spaces must be typed
+    NonEmpty,
+    // At most this many characters
+    MaxLength(usize),
+    // A number between the two ends, e.g. 0.0..=150.0
+    Range(RangeInclusive<f64>),
+    // A date and/or time in a chrono format, e.g. "%Y-%m-%d" or "%H:%M"
+    Date(&'static str),
+    // The value matches the pattern; the text says what is expected,
+    // e.g. "an email address" (build it with Validator::regex)
+    Regex(Regex, &'static str),
+}
+
+impl Validator {
+    // A Regex rule that the WHOLE value has to match, not just a part
+    pub fn regex(pattern: &str, expected: &'static str) -> Result<Self, regex::Error> {
+        Ok(Validator::Regex(Regex::new(&format!("^(?:{pattern})$"))?, expected))
+    }
+
+    // Ok, or the message to show under the field
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        if value.is_empty() && !matches!(self, Validator::NonEmpty) {
+            return Ok(());
+        }
+
+        match self {
+            Validator::NonEmpty if value.trim().is_empty() => Err("Can't be empty".to_string()),
+            Validator::MaxLength(max) if value.graphemes(true).count() > *max => {
+                Err(format!("At most {max} characters"))
+            }
+            Validator::Range(range) => match value.trim().parse::<f64>() {
+                Ok(number) if range.contains(&number) => Ok(()),
+                _ => Err(format!("A number from {} to {}", range.start(), range.end())),
+            },
+            Validator::Date(format) if !is_date(value, format) => Err(format!("Should be like {}", example(format))),
+            Validator::Regex(pattern, expected) if !pattern.is_match(value) => Err(format!("Should be {expected}")),
+            _ => Ok(()),
+        }
+    }
+}
+
+// A sample value in `format`, to show what is expected ("%H:%M" → "14:30")
+fn example(format: &str) -> String {
+    let sample = NaiveDate::from_ymd_opt(2026, 2, 13).and_then(|day| day.and_hms_opt(14, 30, 0));
+    sample.map_or(format.to_string(), |sample| sample.format(format).to_string())
+}
+
+// Whether `value` is a real date, time, or date and time in `format`
+// (so "2026-02-30" and "24:00" are not)
+fn is_date(value: &str, format: &str) -> bool {
+    NaiveDate::parse_from_str(value, format).is_ok()
+        || NaiveTime::parse_from_str(value, format).is_ok()
+        || NaiveDateTime::parse_from_str(value, format).is_ok()
+}
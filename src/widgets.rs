@@ -0,0 +1,78 @@
+// Ratatui's list and its selection
+use ratatui::widgets::ListState;
+
+
+// A LIST WITH A SELECTION
+// The items and which one is selected, moving with Up/Down and wrapping
+// around at the ends. Draw it with a ratatui List and render_stateful_widget:
+//
+//     frame.render_stateful_widget(List::new(items), area, &mut list.state);
+pub struct StatefulList<T> {
+    pub items: Vec<T>,
+    pub state: ListState,
+}
+
+impl<T> StatefulList<T> {
+    // The first item starts out selected (when there is one)
+    pub fn new(items: Vec<T>) -> Self {
+        let state = ListState::default().with_selected((!items.is_empty()).then_some(0));
+        Self { items, state }
+    }
+
+    // Select the next item; after the last one comes the first
+    pub fn next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.items.len() => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    // Select the previous item; before the first one comes the last
+    pub fn previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i > 0 => i - 1,
+            Some(_) => self.items.len() - 1,
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    // The selected item, if any
+    pub fn selected(&self) -> Option<&T> {
+        self.state.selected().and_then(|i| self.items.get(i))
+    }
+}
+
+
+// TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_wraps_around() {
+        let mut list = StatefulList::new(vec!["a", "b", "c"]);
+        assert_eq!(list.selected(), Some(&"a"));
+
+        list.previous();
+        assert_eq!(list.selected(), Some(&"c"));
+        list.next();
+        list.next();
+        assert_eq!(list.selected(), Some(&"b"));
+    }
+
+    #[test]
+    fn empty_list_selects_nothing() {
+        let mut list: StatefulList<String> = StatefulList::new(Vec::new());
+        list.next();
+        list.previous();
+        assert_eq!(list.selected(), None);
+    }
+}